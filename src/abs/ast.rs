@@ -1,39 +1,80 @@
 // tokens
 use crate::token::block::BlockBranch;
 use crate::token::comment::CommentBranch;
+use crate::token::decenum::DecEnumBranch;
+use crate::token::decfunc::DecFuncBranch;
+use crate::token::decstruct::DecStructBranch;
+use crate::token::error::ErrorBranch;
+use crate::token::field::FieldBranch;
 use crate::token::func::FuncBranch;
 use crate::token::item::ItemBranch;
 use crate::token::list::ListBranch;
 use crate::token::list_block::ListBlockBranch;
+use crate::token::match_syntax::MatchBranch;
+use crate::token::num::NumBranch;
 use crate::token::operator::OperatorBranch;
 use crate::token::paren_block::ParenBlockBranch;
 use crate::token::stmt::expr::ExprBranch;
 use crate::token::stmt::stmt::StmtBranch;
 use crate::token::string::StringBranch;
+use crate::token::structure::StructBranch;
 use crate::token::syntax::SyntaxBranch;
 use crate::token::syntax_box::SyntaxBoxBranch;
+use crate::token::ttype::array::ArrayTypeBranch;
 use crate::token::ttype::primitive::PrimitiveBranch;
 use crate::token::ttype::type_block::TypeBlockBranch;
 use crate::token::unknown::UnKnownBranch;
+use crate::token::variant::VariantBranch;
 use crate::token::word::WordBranch;
 // errors
 use crate::errors::parser_errors::ParserError;
+// span
+use crate::abs::span::Span;
 
 pub trait Token {
-    fn set_char_as_unknown(c: char) -> Self;
+    fn set_char_as_unknown(c: char, span: Span) -> Self;
     fn show(&self);
     fn get_show_as_string(&self) -> String;
     fn resolve_self(&mut self) -> Result<(), ParserError>;
+    /// ソース上の位置を返却する
+    fn get_span(&self) -> Span;
+    /// 空白文字の未定tokenかどうか
+    fn is_whitespace(&self) -> bool;
+    /// 回復モードで解決する
+    /// 解決できなかった場合はエラーを`errors`に記録して解析を続ける
+    fn resolve_self_recovering(&mut self, errors: &mut Vec<ParserError>) {
+        if let Err(e) = self.resolve_self() {
+            errors.push(e);
+        }
+    }
+}
+
+/// tokenの列全体を覆うspanを返却する
+/// 空の場合は`Span::default()`を返す
+pub fn merge_span<'a, T>(list: impl IntoIterator<Item = &'a T>) -> Span
+where
+    T: Token + 'a,
+{
+    merge_spans(list.into_iter().map(Token::get_span))
+}
+
+/// 複数のspanを覆うspanを返却する
+/// 空の場合は`Span::default()`を返す
+pub fn merge_spans(spans: impl IntoIterator<Item = Span>) -> Span {
+    spans
+        .into_iter()
+        .fold(Span::default(), |acc, span| acc.merge(&span))
 }
 
 pub trait ProcToken {
     // `ExprElem`と`StmtElem`に実装
     // 解析時に揺れがある
-    fn t_string(contents: String, depth: isize, loopdepth: isize) -> Self;
-    fn t_block(contents: Vec<StmtElem>, depth: isize, loopdepth: isize) -> Self;
-    fn t_parenblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize) -> Self;
-    fn t_listblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize) -> Self;
-    fn t_commentblock(contents: String, depth: isize, loopdepth: isize) -> Self;
+    fn t_string(contents: String, depth: isize, loopdepth: isize, span: Span) -> Self;
+    fn t_block(contents: Vec<StmtElem>, depth: isize, loopdepth: isize, span: Span) -> Self;
+    fn t_parenblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize, span: Span) -> Self;
+    fn t_listblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize, span: Span) -> Self;
+    fn t_commentblock(contents: String, depth: isize, loopdepth: isize, span: Span) -> Self;
+    fn t_error(error: ErrorBranch) -> Self;
 }
 
 /// # ExprElem
@@ -46,6 +87,10 @@ pub enum ExprElem {
     ParenBlockElem(ParenBlockBranch),
     SyntaxElem(SyntaxBranch),
     SyntaxBoxElem(SyntaxBoxBranch),
+    MatchElem(MatchBranch),
+    StructElem(StructBranch),
+    FieldElem(FieldBranch),
+    VariantElem(VariantBranch),
     FuncElem(FuncBranch),
     ListElem(ListBranch),
     ItemElem(ItemBranch),
@@ -53,14 +98,21 @@ pub enum ExprElem {
     CommentElem(CommentBranch),
     StringElem(StringBranch),
     WordElem(WordBranch),
+    NumElem(NumBranch),
     OpeElem(OperatorBranch),
     UnKnownElem(UnKnownBranch),
+    // 回復モードで解析できなかった要素
+    ErrorElem(ErrorBranch),
 }
 
 #[derive(Clone, Debug)]
 pub enum TypeElem {
     PrimitiveElem(PrimitiveBranch),
     TypeBlockElem(TypeBlockBranch),
+    // 配列の型(`[i32; 10]`)
+    ArrayElem(ArrayTypeBranch),
+    // ユーザー定義の型の名前(`Point`)
+    WordElem(WordBranch),
     UnKnownElem(UnKnownBranch),
 }
 
@@ -72,17 +124,22 @@ pub enum StmtElem {
     //
     ExprElem(ExprBranch),
     Special(StmtBranch),
+    DecFuncElem(DecFuncBranch),
+    DecStructElem(DecStructBranch),
+    DecEnumElem(DecEnumBranch),
     // without RecursiveAnalysisElements trait structures
     StringElem(StringBranch),
     WordElem(WordBranch),
     OpeElem(OperatorBranch),
     CommentElem(CommentBranch),
     UnKnownElem(UnKnownBranch),
+    // 回復モードで解析できなかった要素
+    ErrorElem(ErrorBranch),
 }
 
 impl Token for ExprElem {
-    fn set_char_as_unknown(c: char) -> Self {
-        ExprElem::UnKnownElem(UnKnownBranch { contents: c })
+    fn set_char_as_unknown(c: char, span: Span) -> Self {
+        ExprElem::UnKnownElem(UnKnownBranch { contents: c, span })
     }
 
     fn show(&self) {
@@ -93,13 +150,19 @@ impl Token for ExprElem {
             Self::ListBlockElem(e) => e.show(),
             Self::ParenBlockElem(e) => e.show(),
             Self::WordElem(e) => e.show(),
+            Self::NumElem(e) => e.show(),
             Self::SyntaxElem(e) => e.show(),
             Self::SyntaxBoxElem(e) => e.show(),
+            Self::MatchElem(e) => e.show(),
+            Self::StructElem(e) => e.show(),
+            Self::FieldElem(e) => e.show(),
+            Self::VariantElem(e) => e.show(),
             Self::FuncElem(e) => e.show(),
             Self::ItemElem(e) => e.show(),
             Self::OpeElem(e) => e.show(),
             Self::ListElem(e) => e.show(),
             Self::CommentElem(e) => e.show(),
+            Self::ErrorElem(e) => e.show(),
         }
     }
 
@@ -111,13 +174,19 @@ impl Token for ExprElem {
             Self::ListBlockElem(e) => e.get_show_as_string(),
             Self::ParenBlockElem(e) => e.get_show_as_string(),
             Self::WordElem(e) => e.get_show_as_string(),
+            Self::NumElem(e) => e.get_show_as_string(),
             Self::SyntaxElem(e) => e.get_show_as_string(),
             Self::SyntaxBoxElem(e) => e.get_show_as_string(),
+            Self::MatchElem(e) => e.get_show_as_string(),
+            Self::StructElem(e) => e.get_show_as_string(),
+            Self::FieldElem(e) => e.get_show_as_string(),
+            Self::VariantElem(e) => e.get_show_as_string(),
             Self::FuncElem(e) => e.get_show_as_string(),
             Self::ItemElem(e) => e.get_show_as_string(),
             Self::OpeElem(e) => e.get_show_as_string(),
             Self::ListElem(e) => e.get_show_as_string(),
             Self::CommentElem(e) => e.get_show_as_string(),
+            Self::ErrorElem(e) => e.get_show_as_string(),
         }
     }
 
@@ -129,6 +198,10 @@ impl Token for ExprElem {
             Self::ParenBlockElem(e) => e.resolve_self(),
             Self::SyntaxElem(e) => e.resolve_self(),
             Self::SyntaxBoxElem(e) => e.resolve_self(),
+            Self::MatchElem(e) => e.resolve_self(),
+            Self::StructElem(e) => e.resolve_self(),
+            Self::FieldElem(e) => e.resolve_self(),
+            Self::VariantElem(e) => e.resolve_self(),
             Self::FuncElem(e) => e.resolve_self(),
             Self::ListElem(e) => e.resolve_self(),
             Self::ItemElem(e) => e.resolve_self(),
@@ -136,63 +209,117 @@ impl Token for ExprElem {
             // unrecursive analysis elements
             Self::StringElem(_) => Ok(()),
             Self::WordElem(_) => Ok(()),
+            Self::NumElem(_) => Ok(()),
             Self::OpeElem(_) => Ok(()),
             Self::UnKnownElem(_) => Ok(()),
             Self::CommentElem(_) => Ok(()),
+            Self::ErrorElem(_) => Ok(()),
+        }
+    }
+    fn get_span(&self) -> Span {
+        match self {
+            Self::BlockElem(e) => e.span,
+            Self::ListBlockElem(e) => e.span,
+            Self::ParenBlockElem(e) => e.span,
+            Self::SyntaxElem(e) => e.span,
+            Self::SyntaxBoxElem(e) => e.span,
+            Self::MatchElem(e) => e.span,
+            Self::StructElem(e) => e.span,
+            Self::FieldElem(e) => e.span,
+            Self::VariantElem(e) => e.span,
+            Self::FuncElem(e) => e.span,
+            Self::ListElem(e) => e.span,
+            Self::ItemElem(e) => e.span,
+            Self::CommentElem(e) => e.span,
+            Self::StringElem(e) => e.span,
+            Self::WordElem(e) => e.span,
+            Self::NumElem(e) => e.span,
+            Self::OpeElem(e) => e.span,
+            Self::UnKnownElem(e) => e.span,
+            Self::ErrorElem(e) => e.span,
+        }
+    }
+
+    fn is_whitespace(&self) -> bool {
+        matches!(self, ExprElem::UnKnownElem(v) if v.contents.is_whitespace())
+    }
+
+    fn resolve_self_recovering(&mut self, errors: &mut Vec<ParserError>) {
+        match self {
+            // `{}`の内側は文ごとに回復する
+            Self::BlockElem(e) => e.resolve_self_recovering(errors),
+            Self::SyntaxBoxElem(e) => e.resolve_self_recovering(errors),
+            _ => {
+                if let Err(e) = self.resolve_self() {
+                    errors.push(e.clone());
+                    *self = Self::ErrorElem(ErrorBranch {
+                        error: e,
+                        depth: 0,
+                        loopdepth: 0,
+                        span: self.get_span(),
+                    });
+                }
+            }
         }
     }
 }
 
 impl ProcToken for ExprElem {
-    fn t_string(contents: String, depth: isize, loopdepth: isize) -> Self {
+    fn t_string(contents: String, depth: isize, loopdepth: isize, span: Span) -> Self {
         Self::StringElem(StringBranch {
             contents,
             depth,
             loopdepth,
+            span,
         })
     }
 
-    fn t_block(contents: Vec<StmtElem>, depth: isize, loopdepth: isize) -> Self {
+    fn t_block(contents: Vec<StmtElem>, depth: isize, loopdepth: isize, span: Span) -> Self {
         Self::BlockElem(BlockBranch {
             contents,
             depth,
             loopdepth,
+            span,
         })
     }
 
-    fn t_parenblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize) -> Self {
+    fn t_parenblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize, span: Span) -> Self {
         Self::ParenBlockElem(ParenBlockBranch {
             contents,
             depth,
             loopdepth,
+            span,
         })
     }
 
-    fn t_listblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize) -> Self {
-        Self::ListBlockElem(ListBlockBranch {
-            contents,
-            depth,
-            loopdepth,
-        })
+    fn t_listblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize, span: Span) -> Self {
+        Self::ListBlockElem(ASTAreaBranch::new(contents, depth, loopdepth, span))
     }
-    fn t_commentblock(contents: String, depth: isize, loopdepth: isize) -> Self {
+    fn t_commentblock(contents: String, depth: isize, loopdepth: isize, span: Span) -> Self {
         Self::CommentElem(CommentBranch {
             contents,
             depth,
             loopdepth,
+            span,
         })
     }
+
+    fn t_error(error: ErrorBranch) -> Self {
+        Self::ErrorElem(error)
+    }
 }
 
 impl Token for TypeElem {
-    fn set_char_as_unknown(c: char) -> Self {
-        TypeElem::UnKnownElem(UnKnownBranch { contents: c })
+    fn set_char_as_unknown(c: char, span: Span) -> Self {
+        TypeElem::UnKnownElem(UnKnownBranch { contents: c, span })
     }
 
     fn get_show_as_string(&self) -> String {
         match self {
             TypeElem::PrimitiveElem(e) => e.get_show_as_string(),
             TypeElem::TypeBlockElem(e) => e.get_show_as_string(),
+            TypeElem::ArrayElem(e) => e.get_show_as_string(),
+            TypeElem::WordElem(e) => e.get_show_as_string(),
             TypeElem::UnKnownElem(e) => e.get_show_as_string(),
         }
     }
@@ -201,18 +328,41 @@ impl Token for TypeElem {
         match self {
             TypeElem::PrimitiveElem(e) => e.show(),
             TypeElem::TypeBlockElem(e) => e.show(),
+            TypeElem::ArrayElem(e) => e.show(),
+            TypeElem::WordElem(e) => e.show(),
             TypeElem::UnKnownElem(e) => e.show(),
         }
     }
 
     fn resolve_self(&mut self) -> Result<(), ParserError> {
-        todo!()
+        match self {
+            TypeElem::TypeBlockElem(e) => e.resolve_self(),
+            // without RecursiveAnalysisElements trait structures
+            TypeElem::PrimitiveElem(_) => Ok(()),
+            TypeElem::ArrayElem(_) => Ok(()),
+            TypeElem::WordElem(_) => Ok(()),
+            TypeElem::UnKnownElem(_) => Ok(()),
+        }
+    }
+
+    fn get_span(&self) -> Span {
+        match self {
+            TypeElem::PrimitiveElem(e) => e.span,
+            TypeElem::TypeBlockElem(e) => e.span,
+            TypeElem::ArrayElem(e) => e.span,
+            TypeElem::WordElem(e) => e.span,
+            TypeElem::UnKnownElem(e) => e.span,
+        }
+    }
+
+    fn is_whitespace(&self) -> bool {
+        matches!(self, TypeElem::UnKnownElem(v) if v.contents.is_whitespace())
     }
 }
 
 impl Token for StmtElem {
-    fn set_char_as_unknown(c: char) -> Self {
-        StmtElem::UnKnownElem(UnKnownBranch { contents: c })
+    fn set_char_as_unknown(c: char, span: Span) -> Self {
+        StmtElem::UnKnownElem(UnKnownBranch { contents: c, span })
     }
 
     fn get_show_as_string(&self) -> String {
@@ -221,6 +371,9 @@ impl Token for StmtElem {
             Self::ListBlockElem(e) => e.get_show_as_string(),
             Self::ParenBlockElem(e) => e.get_show_as_string(),
             Self::Special(e) => e.get_show_as_string(),
+            Self::DecFuncElem(e) => e.get_show_as_string(),
+            Self::DecStructElem(e) => e.get_show_as_string(),
+            Self::DecEnumElem(e) => e.get_show_as_string(),
             // without RecursiveAnalysisElements trait structures
             Self::StringElem(e) => e.get_show_as_string(),
             Self::CommentElem(e) => e.get_show_as_string(),
@@ -228,6 +381,7 @@ impl Token for StmtElem {
             Self::WordElem(e) => e.get_show_as_string(),
             Self::OpeElem(e) => e.get_show_as_string(),
             Self::UnKnownElem(e) => e.get_show_as_string(),
+            Self::ErrorElem(e) => e.get_show_as_string(),
         }
     }
 
@@ -237,6 +391,9 @@ impl Token for StmtElem {
             Self::ListBlockElem(e) => e.show(),
             Self::ParenBlockElem(e) => e.show(),
             Self::Special(e) => e.show(),
+            Self::DecFuncElem(e) => e.show(),
+            Self::DecStructElem(e) => e.show(),
+            Self::DecEnumElem(e) => e.show(),
             // without RecursiveAnalysisElements trait structures
             Self::StringElem(e) => e.show(),
             Self::CommentElem(e) => e.show(),
@@ -244,6 +401,7 @@ impl Token for StmtElem {
             Self::WordElem(e) => e.show(),
             Self::OpeElem(e) => e.show(),
             Self::UnKnownElem(e) => e.show(),
+            Self::ErrorElem(e) => e.show(),
         }
     }
 
@@ -254,56 +412,106 @@ impl Token for StmtElem {
             Self::ParenBlockElem(e) => e.resolve_self(),
             Self::ExprElem(e) => e.resolve_self(),
             Self::Special(e) => e.resolve_self(),
+            Self::DecFuncElem(e) => e.resolve_self(),
             // without RecursiveAnalysisElements trait structures
+            Self::DecStructElem(_) => Ok(()),
+            Self::DecEnumElem(_) => Ok(()),
             Self::StringElem(_) => Ok(()),
             Self::CommentElem(_) => Ok(()),
             Self::WordElem(_) => Ok(()),
             Self::OpeElem(_) => Ok(()),
             Self::UnKnownElem(_) => Ok(()),
+            Self::ErrorElem(_) => Ok(()),
+        }
+    }
+    fn get_span(&self) -> Span {
+        match self {
+            Self::BlockElem(e) => e.span,
+            Self::ListBlockElem(e) => e.span,
+            Self::ParenBlockElem(e) => e.span,
+            Self::ExprElem(e) => e.span,
+            Self::Special(e) => e.span,
+            Self::DecFuncElem(e) => e.span,
+            Self::DecStructElem(e) => e.span,
+            Self::DecEnumElem(e) => e.span,
+            Self::StringElem(e) => e.span,
+            Self::CommentElem(e) => e.span,
+            Self::WordElem(e) => e.span,
+            Self::OpeElem(e) => e.span,
+            Self::UnKnownElem(e) => e.span,
+            Self::ErrorElem(e) => e.span,
+        }
+    }
+
+    fn is_whitespace(&self) -> bool {
+        matches!(self, StmtElem::UnKnownElem(v) if v.contents.is_whitespace())
+    }
+
+    fn resolve_self_recovering(&mut self, errors: &mut Vec<ParserError>) {
+        match self {
+            // `{}`の内側は文ごとに回復する
+            Self::BlockElem(e) => e.resolve_self_recovering(errors),
+            Self::ExprElem(e) => e.resolve_self_recovering(errors),
+            Self::DecFuncElem(e) => e.resolve_self_recovering(errors),
+            _ => {
+                if let Err(e) = self.resolve_self() {
+                    errors.push(e.clone());
+                    *self = Self::ErrorElem(ErrorBranch {
+                        error: e,
+                        depth: 0,
+                        loopdepth: 0,
+                        span: self.get_span(),
+                    });
+                }
+            }
         }
     }
 }
 
 impl ProcToken for StmtElem {
-    fn t_string(contents: String, depth: isize, loopdepth: isize) -> Self {
+    fn t_string(contents: String, depth: isize, loopdepth: isize, span: Span) -> Self {
         Self::StringElem(StringBranch {
             contents,
             depth,
             loopdepth,
+            span,
         })
     }
 
-    fn t_block(contents: Vec<StmtElem>, depth: isize, loopdepth: isize) -> Self {
+    fn t_block(contents: Vec<StmtElem>, depth: isize, loopdepth: isize, span: Span) -> Self {
         Self::BlockElem(BlockBranch {
             contents,
             depth,
             loopdepth,
+            span,
         })
     }
 
-    fn t_parenblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize) -> Self {
+    fn t_parenblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize, span: Span) -> Self {
         Self::ParenBlockElem(ParenBlockBranch {
             contents,
             depth,
             loopdepth,
+            span,
         })
     }
 
-    fn t_listblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize) -> Self {
-        Self::ListBlockElem(ListBlockBranch {
-            contents,
-            depth,
-            loopdepth,
-        })
+    fn t_listblock(contents: Vec<ExprElem>, depth: isize, loopdepth: isize, span: Span) -> Self {
+        Self::ListBlockElem(ASTAreaBranch::new(contents, depth, loopdepth, span))
     }
 
-    fn t_commentblock(contents: String, depth: isize, loopdepth: isize) -> Self {
+    fn t_commentblock(contents: String, depth: isize, loopdepth: isize, span: Span) -> Self {
         Self::CommentElem(CommentBranch {
             contents,
             depth,
             loopdepth,
+            span,
         })
     }
+
+    fn t_error(error: ErrorBranch) -> Self {
+        Self::ErrorElem(error)
+    }
 }
 
 /// #  ASTBranch
@@ -321,13 +529,20 @@ pub trait ASTAreaBranch<T>
 where
     T: Token,
 {
-    fn new(contents: Vec<T>, depth: isize, loopdepth: isize) -> Self;
+    fn new(contents: Vec<T>, depth: isize, loopdepth: isize, span: Span) -> Self;
 }
 
 pub trait TypeAreaBranch {
-    fn new(contents: Vec<TypeElem>, depth: isize) -> Self;
+    fn new(contents: Vec<TypeElem>, depth: isize, span: Span) -> Self;
 }
 
 pub trait RecursiveAnalysisElements {
     fn resolve_self(&mut self) -> Result<(), ParserError>;
+    /// 回復モードで解決する
+    /// 内部に`{}`を持つ要素はブロック内の文ごとに回復するように上書きする
+    fn resolve_self_recovering(&mut self, errors: &mut Vec<ParserError>) {
+        if let Err(e) = self.resolve_self() {
+            errors.push(e);
+        }
+    }
 }
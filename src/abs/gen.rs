@@ -1,5 +1,6 @@
 use crate::errors::generate_errors::GenerateError;
 
+#[allow(non_camel_case_types, dead_code)]
 enum wasm_instructions {
     //
     local_get,
@@ -25,6 +26,7 @@ enum wasm_instructions {
 ///
 /// このトレイトはブランチ構造体に実装します。
 ///
+#[allow(non_camel_case_types)]
 pub trait Wasm_gen {
     fn generate_wasm(&self) -> Result<String, GenerateError>;
 }
@@ -0,0 +1,78 @@
+/// # Span
+/// ソースコード上の位置を表すstruct
+///
+/// - `start` `end` はソースコード先頭からのバイトオフセット(`end`は含まない)
+/// - `line` `column` は`start`の位置を表す(どちらも1から数える)
+///
+/// コンパイラ内部で合成された要素など、ソース上の位置を持たない場合は
+/// `Span::default()`(すべて0)を使用する
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Self {
+        Self {
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+
+    /// ソース上の位置を持たない場合trueを返却する
+    pub fn is_dummy(&self) -> bool {
+        self.line == 0
+    }
+
+    /// 二つのspanを覆うspanを返却する
+    /// どちらかが位置を持たない場合はもう一方を返す
+    pub fn merge(&self, other: &Span) -> Span {
+        if self.is_dummy() {
+            return *other;
+        }
+        if other.is_dummy() {
+            return *self;
+        }
+        let (first, _) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        Span {
+            start: first.start,
+            end: self.end.max(other.end),
+            line: first.line,
+            column: first.column,
+        }
+    }
+
+    /// `end`の位置にある幅0のspanを返却する
+    /// `line` `column`は`self`が一行に収まる場合の位置になる
+    pub fn tail(&self) -> Span {
+        Span {
+            start: self.end,
+            end: self.end,
+            line: self.line,
+            column: self.column + (self.end - self.start),
+        }
+    }
+
+    /// `start`の位置にある幅0のspanを返却する
+    pub fn head(&self) -> Span {
+        Span {
+            end: self.start,
+            ..*self
+        }
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
@@ -0,0 +1,240 @@
+use crate::abs::span::Span;
+
+/// 診断の重要度
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+impl Level {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warning => "warning",
+        }
+    }
+
+    fn color(&self) -> &'static str {
+        match self {
+            Level::Error => ANSI_RED,
+            Level::Warning => ANSI_YELLOW,
+        }
+    }
+}
+
+/// ソース上の位置とそこに添えるメッセージ
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+/// # Diagnostic
+/// コンパイル時に利用者に向けて表示する診断
+///
+/// `primary`はエラーの原因となった位置、`secondary`は補足のための位置
+/// (例えば`BraceNotClosed`に対する"brace opened here")を表す
+/// `render`でrustcのような注釈付きのソースコード片を出力する
+///
+/// ```text
+/// error: this delimiter is never closed
+///  --> 2:11
+///   |
+/// 1 | fn main() {
+///   |           - brace opened here
+/// 2 |     a = 1;
+///   |           ^ expected `}`
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+    pub primary: Label,
+    pub secondary: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_BOLD: &str = "\x1b[1m";
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_BLUE: &str = "\x1b[34m";
+
+impl Diagnostic {
+    pub fn new(level: Level, message: &str, span: Span, label: &str) -> Self {
+        Self {
+            level,
+            message: message.to_string(),
+            primary: Label {
+                span,
+                message: label.to_string(),
+            },
+            secondary: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn error(message: &str, span: Span, label: &str) -> Self {
+        Self::new(Level::Error, message, span, label)
+    }
+
+    pub fn warning(message: &str, span: Span, label: &str) -> Self {
+        Self::new(Level::Warning, message, span, label)
+    }
+
+    /// 補足の位置を追加する
+    pub fn with_label(mut self, span: Span, message: &str) -> Self {
+        self.secondary.push(Label {
+            span,
+            message: message.to_string(),
+        });
+        self
+    }
+
+    /// `= note: ...`として表示する補足を追加する
+    pub fn with_note(mut self, note: &str) -> Self {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    /// 色なしで診断を出力する
+    pub fn render(&self, source: &str) -> String {
+        self.render_impl(source, false)
+    }
+
+    /// ANSIエスケープシーケンスで色付けして診断を出力する
+    pub fn render_colored(&self, source: &str) -> String {
+        self.render_impl(source, true)
+    }
+
+    fn render_impl(&self, source: &str, color: bool) -> String {
+        let paint = |code: &str, text: &str| -> String {
+            if color {
+                format!("{}{}{}{}", ANSI_BOLD, code, text, ANSI_RESET)
+            } else {
+                text.to_string()
+            }
+        };
+
+        let mut rtext = String::new();
+        // header
+        rtext.push_str(&paint(self.level.color(), self.level.as_str()));
+        rtext.push_str(&paint("", &format!(": {}", self.message)));
+        rtext.push('\n');
+
+        // 表示する位置を行ごとにまとめる
+        // 行と列は`start`のオフセットからソースコード上で数え直す
+        // (閉じ括弧が必要な位置など、合成した幅0のspanも正しい行に表示する)
+        let mut labels: Vec<(&Label, bool)> = vec![(&self.primary, true)];
+        labels.extend(self.secondary.iter().map(|l| (l, false)));
+        labels.retain(|(l, _)| !l.span.is_dummy() && l.span.start <= source.len());
+        let line_of = |label: &Label| line_column(source, label.span.start).0;
+
+        let max_line = labels.iter().map(|(l, _)| line_of(l)).max().unwrap_or(0);
+        let gutter = " ".repeat(max_line.to_string().len());
+
+        if !self.primary.span.is_dummy() {
+            let (line, column) = if self.primary.span.start <= source.len() {
+                line_column(source, self.primary.span.start)
+            } else {
+                (self.primary.span.line, self.primary.span.column)
+            };
+            rtext.push_str(&format!(
+                "{}{} {}:{}\n",
+                gutter,
+                paint(ANSI_BLUE, "-->"),
+                line,
+                column
+            ));
+        }
+        if !labels.is_empty() {
+            let mut lines: Vec<usize> = labels.iter().map(|(l, _)| line_of(l)).collect();
+            lines.sort();
+            lines.dedup();
+
+            rtext.push_str(&format!("{} {}\n", gutter, paint(ANSI_BLUE, "|")));
+            let mut prev_line: Option<usize> = None;
+            for line in lines {
+                if let Some(prev) = prev_line {
+                    if prev + 1 < line {
+                        rtext.push_str(&format!("{}\n", paint(ANSI_BLUE, "...")));
+                    }
+                }
+                prev_line = Some(line);
+
+                let mut line_labels: Vec<&(&Label, bool)> =
+                    labels.iter().filter(|(l, _)| line_of(l) == line).collect();
+                line_labels.sort_by_key(|(l, is_primary)| (!*is_primary, l.span.start));
+
+                let (line_start, line_text) = source_line(source, line_labels[0].0.span.start);
+                rtext.push_str(&format!(
+                    "{} {} {}\n",
+                    paint(
+                        ANSI_BLUE,
+                        &format!("{:>width$}", line, width = gutter.len())
+                    ),
+                    paint(ANSI_BLUE, "|"),
+                    line_text
+                ));
+                for (label, is_primary) in line_labels {
+                    let start = label.span.start.max(line_start);
+                    let end = label.span.end.min(line_start + line_text.len()).max(start);
+                    let offset = line_text[..start - line_start].chars().count();
+                    let width = line_text[start - line_start..end - line_start]
+                        .chars()
+                        .count()
+                        .max(1);
+                    let (marker, marker_color) = if *is_primary {
+                        ("^", self.level.color())
+                    } else {
+                        ("-", ANSI_BLUE)
+                    };
+                    let mut mark = marker.repeat(width);
+                    if !label.message.is_empty() {
+                        mark.push(' ');
+                        mark.push_str(&label.message);
+                    }
+                    rtext.push_str(&format!(
+                        "{} {} {}{}\n",
+                        gutter,
+                        paint(ANSI_BLUE, "|"),
+                        " ".repeat(offset),
+                        paint(marker_color, &mark)
+                    ));
+                }
+            }
+        }
+        for note in &self.notes {
+            rtext.push_str(&format!(
+                "{} {} note: {}\n",
+                gutter,
+                paint(ANSI_BLUE, "="),
+                note
+            ));
+        }
+        rtext
+    }
+}
+
+/// `offset`の位置の行と列(どちらも1から数える)
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let (line_start, _) = source_line(source, offset);
+    let line = source[..line_start].matches('\n').count() + 1;
+    let column = source[line_start..offset.min(source.len())].chars().count() + 1;
+    (line, column)
+}
+
+/// `offset`を含む行の先頭のオフセットと、その行の文字列(改行を含まない)を返却する
+fn source_line(source: &str, offset: usize) -> (usize, &str) {
+    let offset = offset.min(source.len());
+    let line_start = source[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line_end = source[offset..]
+        .find('\n')
+        .map(|i| offset + i)
+        .unwrap_or(source.len());
+    (
+        line_start,
+        source[line_start..line_end].trim_end_matches('\r'),
+    )
+}
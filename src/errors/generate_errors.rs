@@ -1,10 +1,557 @@
+use crate::abs::span::Span;
+use crate::errors::diagnostic::Diagnostic;
+use crate::gen::types::MAX_RECORD_SIZE;
+
 /// コードの生成時に発生したエラーについて出力します
 #[derive(Debug)]
 pub enum GenerateError {
-    InvalidNum,       // 不正な数字表現があった場合に発生します
-    InvalidOperation, // 非対応の演算子を使った場合
-    InvalidleftPattern,
-    InvalidTypeError,
+    InvalidNum(Span),         // 不正な数字表現があった場合に発生します
+    InvalidOperation(Span),   // 非対応の演算子を使った場合
+    InvalidleftPattern(Span), // 代入の左辺に代入できない要素が渡された場合
+    InvalidTypeError(Span),
+    EmptyCondition(Span),        // `if` `elif` `while`の条件式が空の場合
+    InvalidCondition(Span),      // 条件式として扱えない要素が渡された場合
+    UnsupportedStatement(Span),  // 生成に対応していない文
+    UnsupportedSyntax(Span),     // 生成に対応していない構文(`for`など)
+    UnsupportedListAccess(Span), // 型検査を経ていないリストや`a[1, 2]`へのアクセス
+    // `let`で宣言された変数への代入
+    AssignToImmutable {
+        name: String,
+        span: Span,     // 代入
+        declared: Span, // 変数の宣言
+    },
+    // 再代入できない変数のフィールドや要素への代入
+    AssignToImmutablePlace {
+        name: String,
+        place: &'static str, // "field" "element"
+        span: Span,          // 代入
+        declared: Span,      // 変数の宣言
+    },
+    // 再代入できない変数を、引数のフィールドや要素に代入する関数に渡している
+    ImmutableArgument {
+        name: String,
+        function: String,
+        span: Span,     // 引数
+        declared: Span, // 変数の宣言
+    },
+    // 宣言されていない変数
+    UndefinedVariable {
+        name: String,
+        span: Span,
+        suggestion: Option<String>, // 似た名前の変数
+    },
+    // 全ての経路で値が代入されていない変数の参照
+    UsedBeforeAssignment {
+        name: String,
+        span: Span,
+        declared: Span, // 変数の宣言
+    },
+    // 宣言されていない関数
+    UndefinedFunction {
+        name: String,
+        span: Span,
+        suggestion: Option<String>, // 似た名前の関数
+    },
+    // 宣言されていない型
+    UndefinedType {
+        name: String,
+        span: Span,
+        suggestion: Option<String>, // 似た名前の型
+    },
+    // 型が持たないフィールド
+    UndefinedField {
+        ty: String,
+        field: String,
+        span: Span,
+        suggestion: Option<String>, // 似た名前のフィールド
+    },
+    // 宣言されていないヴァリアントをパターンに使用した場合
+    UndefinedVariant {
+        name: String,
+        span: Span,
+        suggestion: Option<String>, // 似た名前のヴァリアント
+    },
+    // 構造体のリテラルで値が指定されていないフィールド
+    MissingFields {
+        name: String,
+        missing: Vec<String>,
+        span: Span,
+    },
+    // 構造体の宣言やリテラルで同じフィールドが複数回現れた場合
+    DuplicateField {
+        name: String,
+        span: Span,
+    },
+    // 同じ名前の型が複数回宣言された場合
+    DuplicateDefinition {
+        name: String,
+        span: Span,
+    },
+    // 補助関数のために予約されている名前(`__alloc`など)で関数を宣言した場合
+    ReservedName {
+        name: String,
+        span: Span,
+    },
+    // 型が一致しない場合
+    TypeMismatch {
+        expected: String,
+        found: String,
+        span: Span,
+    },
+    // 数値リテラルの値が型の範囲に収まらない場合
+    LiteralOutOfRange {
+        literal: String,
+        target: String,
+        span: Span,
+    },
+    // 演算子を使用できない型の場合(`f64`の`%`など)
+    InvalidOperandType {
+        ope: String,
+        found: String,
+        span: Span,
+    },
+    // 関数呼び出しの引数の数が一致しない場合
+    ArgumentCountMismatch {
+        name: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    // `match`のパターンとして使用できない式
+    InvalidPattern(Span),
+    // 一つも値を含まない範囲のパターン`5..1` `3..3`
+    EmptyRangePattern {
+        inclusive: bool, // `..=`かどうか
+        span: Span,
+    },
+    // `match`の腕が検査する値の全てを網羅していない場合
+    NonExhaustivePatterns {
+        missing: Vec<String>, // 網羅されていないパターン
+        span: Span,
+    },
+    // ループの外の`break` `continue`
+    BreakOutsideLoop {
+        keyword: String,
+        span: Span,
+    },
+    // `for (x in iterable)`の形式ではない場合
+    InvalidForLoop(Span),
+    // `for`で反復できない値の場合
+    NotIterable {
+        found: String,
+        span: Span,
+    },
+    // 配列ではない値の要素へのアクセス
+    NotIndexable {
+        found: String,
+        span: Span,
+    },
+    // 定数の添字が配列の要素の数を超えている場合
+    IndexOutOfBounds {
+        index: u128,
+        length: u32,
+        span: Span,
+    },
+    // 線形メモリに収まらない大きさの配列
+    ArrayTooLarge {
+        element: String,
+        length: u32,
+        size: u64, // バイト
+        span: Span,
+    },
+    // 要素の型が決まらない配列のリテラル(型注釈のない`[]`など)
+    UnknownArrayType(Span),
+    // 式の中では使用できない演算子(`->`)
+    ReservedOperator(Span),
+    // `@`の実装となる関数(`dot_f64`など)が宣言されていない場合
+    UndefinedOperatorFunction {
+        ope: String,
+        found: String,
+        name: String,
+        span: Span,
+    },
     // developer向けのエラーです。
     Deverror,
 }
+
+impl GenerateError {
+    /// エラーの原因となった位置を返却する
+    pub fn get_span(&self) -> Span {
+        match self {
+            Self::InvalidNum(span)
+            | Self::InvalidOperation(span)
+            | Self::InvalidleftPattern(span)
+            | Self::InvalidTypeError(span)
+            | Self::EmptyCondition(span)
+            | Self::InvalidCondition(span)
+            | Self::UnsupportedStatement(span)
+            | Self::UnsupportedSyntax(span)
+            | Self::UnsupportedListAccess(span)
+            | Self::AssignToImmutable { span, .. }
+            | Self::AssignToImmutablePlace { span, .. }
+            | Self::ImmutableArgument { span, .. }
+            | Self::UndefinedVariable { span, .. }
+            | Self::UsedBeforeAssignment { span, .. }
+            | Self::UndefinedFunction { span, .. }
+            | Self::UndefinedType { span, .. }
+            | Self::UndefinedField { span, .. }
+            | Self::UndefinedVariant { span, .. }
+            | Self::MissingFields { span, .. }
+            | Self::DuplicateField { span, .. }
+            | Self::DuplicateDefinition { span, .. }
+            | Self::ReservedName { span, .. }
+            | Self::TypeMismatch { span, .. }
+            | Self::LiteralOutOfRange { span, .. }
+            | Self::InvalidOperandType { span, .. }
+            | Self::ArgumentCountMismatch { span, .. }
+            | Self::UndefinedOperatorFunction { span, .. }
+            | Self::ReservedOperator(span)
+            | Self::InvalidPattern(span)
+            | Self::EmptyRangePattern { span, .. }
+            | Self::NonExhaustivePatterns { span, .. }
+            | Self::BreakOutsideLoop { span, .. }
+            | Self::InvalidForLoop(span)
+            | Self::NotIterable { span, .. }
+            | Self::NotIndexable { span, .. }
+            | Self::IndexOutOfBounds { span, .. }
+            | Self::ArrayTooLarge { span, .. }
+            | Self::UnknownArrayType(span) => *span,
+            Self::Deverror => Span::default(),
+        }
+    }
+
+    /// 利用者向けの診断に変換する
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let span = self.get_span();
+        match self {
+            Self::InvalidNum(_) => {
+                Diagnostic::error("invalid number literal", span, "not a valid number")
+            }
+            Self::InvalidOperation(_) => Diagnostic::error(
+                "unsupported operator",
+                span,
+                "this operator cannot be compiled yet",
+            ),
+            Self::InvalidleftPattern(_) => Diagnostic::error(
+                "invalid left-hand side of assignment",
+                span,
+                "cannot assign to this expression",
+            ),
+            Self::InvalidTypeError(_) => {
+                Diagnostic::error("invalid type", span, "this type is not supported")
+            }
+            Self::EmptyCondition(_) => Diagnostic::error(
+                "missing condition",
+                span,
+                "expected a condition expression here",
+            ),
+            Self::InvalidCondition(_) => Diagnostic::error(
+                "invalid condition",
+                span,
+                "this cannot be used as a condition",
+            ),
+            Self::UnsupportedStatement(_) => Diagnostic::error(
+                "unsupported statement",
+                span,
+                "this statement cannot be compiled yet",
+            ),
+            Self::UnsupportedSyntax(_) => Diagnostic::error(
+                "unsupported syntax",
+                span,
+                "this syntax cannot be compiled yet",
+            ),
+            Self::UnsupportedListAccess(_) => Diagnostic::error(
+                "unsupported list access",
+                span,
+                "this list access cannot be compiled",
+            ),
+            Self::AssignToImmutable { name, declared, .. } => Diagnostic::error(
+                &format!("cannot assign twice to immutable variable `{}`", name),
+                span,
+                "cannot assign twice to immutable variable",
+            )
+            .with_label(*declared, "declared as immutable here")
+            .with_note(&format!(
+                "declare it with `let_mut {}` to make it mutable",
+                name
+            )),
+            Self::AssignToImmutablePlace {
+                name,
+                place,
+                declared,
+                ..
+            } => Diagnostic::error(
+                &format!("cannot assign to {} of immutable binding `{}`", place, name),
+                span,
+                "cannot assign",
+            )
+            .with_label(*declared, "declared as immutable here")
+            .with_note(&format!(
+                "consider making it mutable: `let_mut {}`",
+                name
+            )),
+            Self::ImmutableArgument {
+                name,
+                function,
+                declared,
+                ..
+            } => Diagnostic::error(
+                &format!(
+                    "cannot pass immutable variable `{}` to `{}`, which modifies it",
+                    name, function
+                ),
+                span,
+                &format!("`{}` assigns to the fields or elements of this argument", function),
+            )
+            .with_label(*declared, "declared as immutable here")
+            .with_note(&format!(
+                "declare it with `let_mut {}` to make it mutable",
+                name
+            )),
+            Self::UndefinedVariable {
+                name, suggestion, ..
+            } => with_suggestion(
+                Diagnostic::error(
+                    &format!("cannot find variable `{}` in this scope", name),
+                    span,
+                    "not found in this scope",
+                ),
+                suggestion,
+            ),
+            Self::UsedBeforeAssignment { name, declared, .. } => Diagnostic::error(
+                &format!("used binding `{}` before assigning a value", name),
+                span,
+                &format!("`{}` used here but it is not assigned on every path", name),
+            )
+            .with_label(*declared, "binding declared here but left uninitialized")
+            .with_note(&format!(
+                "assign a value to `{}` on every path before using it",
+                name
+            )),
+            Self::UndefinedFunction {
+                name, suggestion, ..
+            } => with_suggestion(
+                Diagnostic::error(
+                    &format!("cannot find function `{}` in this scope", name),
+                    span,
+                    "not found in this scope",
+                ),
+                suggestion,
+            ),
+            Self::UndefinedType {
+                name, suggestion, ..
+            } => with_suggestion(
+                Diagnostic::error(
+                    &format!("cannot find type `{}` in this scope", name),
+                    span,
+                    "not found in this scope",
+                ),
+                suggestion,
+            ),
+            Self::UndefinedVariant {
+                name, suggestion, ..
+            } => with_suggestion(
+                Diagnostic::error(
+                    &format!("cannot find variant `{}` in this scope", name),
+                    span,
+                    "not a variant of any enum",
+                ),
+                suggestion,
+            ),
+            Self::UndefinedField {
+                ty,
+                field,
+                suggestion,
+                ..
+            } => with_suggestion(
+                Diagnostic::error(
+                    &format!("no field `{}` on type `{}`", field, ty),
+                    span,
+                    "unknown field",
+                ),
+                suggestion,
+            ),
+            Self::MissingFields { name, missing, .. } => {
+                let fields: Vec<String> = missing.iter().map(|f| format!("`{}`", f)).collect();
+                Diagnostic::error(
+                    &format!(
+                        "missing field(s) {} in initializer of `{}`",
+                        fields.join(", "),
+                        name
+                    ),
+                    span,
+                    "missing field(s)",
+                )
+            }
+            Self::DuplicateField { name, .. } => Diagnostic::error(
+                &format!("field `{}` is specified more than once", name),
+                span,
+                "duplicate field",
+            ),
+            Self::DuplicateDefinition { name, .. } => Diagnostic::error(
+                &format!("the name `{}` is defined multiple times", name),
+                span,
+                "redefined here",
+            ),
+            Self::ReservedName { name, .. } => Diagnostic::error(
+                &format!("the name `{}` is reserved", name),
+                span,
+                "reserved name",
+            )
+            .with_note("names starting with `__` are reserved for compiler helpers"),
+            Self::TypeMismatch {
+                expected, found, ..
+            } => Diagnostic::error(
+                "mismatched types",
+                span,
+                &format!("expected `{}`, found `{}`", expected, found),
+            ),
+            Self::LiteralOutOfRange {
+                literal, target, ..
+            } => Diagnostic::error(
+                &format!("literal out of range for `{}`", target),
+                span,
+                &format!("`{}` does not fit into the type `{}`", literal, target),
+            ),
+            Self::InvalidOperandType { ope, found, .. } => Diagnostic::error(
+                &format!("cannot apply `{}` to `{}`", ope, found),
+                span,
+                &format!("`{}` is not supported for `{}`", ope, found),
+            ),
+            Self::ArgumentCountMismatch {
+                name,
+                expected,
+                found,
+                ..
+            } => Diagnostic::error(
+                &format!(
+                    "function `{}` takes {} argument(s) but {} were supplied",
+                    name, expected, found
+                ),
+                span,
+                &format!("expected {} argument(s)", expected),
+            ),
+            Self::InvalidPattern(_) => {
+                Diagnostic::error("invalid pattern", span, "expected a pattern")
+                    .with_note(
+                        "patterns are number literals `1`, ranges `1..10` `1..=9`, variants `Circle(r)` `Move(Up, _)` or `_`",
+                    )
+            }
+            Self::EmptyRangePattern { inclusive, .. } => Diagnostic::error(
+                if *inclusive {
+                    "lower range bound must be less than or equal to upper"
+                } else {
+                    "lower range bound must be less than upper"
+                },
+                span,
+                "this range pattern matches no values",
+            ),
+            Self::NonExhaustivePatterns { missing, .. } => {
+                let patterns = missing_patterns(missing);
+                Diagnostic::error(
+                    &format!("non-exhaustive patterns: {} not covered", patterns),
+                    span,
+                    &format!("{} not covered", patterns),
+                )
+                .with_note("add arms for the missing values or a wildcard arm `_ => ...`")
+            }
+            Self::InvalidForLoop(_) => Diagnostic::error(
+                "invalid `for` loop",
+                span,
+                "expected `for (name in iterable)`",
+            )
+            .with_note("iterate over a range `0..n`, `0..=n` or an array `[1, 2, 3]`"),
+            Self::NotIterable { found, .. } => Diagnostic::error(
+                &format!("`{}` is not iterable", found),
+                span,
+                "cannot iterate over this value",
+            )
+            .with_note("iterate over a range `0..n`, `0..=n` or an array `[1, 2, 3]`"),
+            Self::NotIndexable { found, .. } => Diagnostic::error(
+                &format!("cannot index into a value of type `{}`", found),
+                span,
+                "this value is not an array",
+            ),
+            Self::IndexOutOfBounds { index, length, .. } => Diagnostic::error(
+                "this index is out of bounds",
+                span,
+                &format!("the length is {} but the index is {}", length, index),
+            ),
+            Self::ArrayTooLarge {
+                element,
+                length,
+                size,
+                ..
+            } => Diagnostic::error(
+                &format!("array `[{}; {}]` is too large", element, length),
+                span,
+                &format!("this array needs {} bytes", size),
+            )
+            .with_note(&format!(
+                "an array must fit in linear memory (at most {} bytes)",
+                MAX_RECORD_SIZE
+            )),
+            Self::UnknownArrayType(_) => Diagnostic::error(
+                "cannot infer the element type of this array",
+                span,
+                "type annotations needed",
+            )
+            .with_note("add a type annotation like `let a: [i32; 0] = [];`"),
+            Self::BreakOutsideLoop { keyword, .. } => Diagnostic::error(
+                &format!("`{}` outside of a loop", keyword),
+                span,
+                &format!("cannot `{}` outside of a loop", keyword),
+            ),
+            Self::ReservedOperator(_) => Diagnostic::error(
+                "reserved operator `->`",
+                span,
+                "cannot be used in an expression",
+            )
+            .with_note("`->` is reserved for function return types and lambda expressions"),
+            Self::UndefinedOperatorFunction {
+                ope, found, name, ..
+            } => Diagnostic::error(
+                &format!("no implementation for `{}` on `{}`", ope, found),
+                span,
+                &format!("`{}` is not defined for `{}`", ope, found),
+            )
+            .with_note(&format!(
+                "declare a function `{}` taking two arguments to define `{}` for `{}`",
+                name, ope, found
+            )),
+            Self::Deverror => Diagnostic::error("internal compiler error", span, "")
+                .with_note("this is a bug in the Lichen compiler"),
+        }
+    }
+}
+
+/// 似た名前の候補があれば診断に追加する
+fn with_suggestion(diagnostic: Diagnostic, suggestion: &Option<String>) -> Diagnostic {
+    match suggestion {
+        Some(name) => diagnostic.with_note(&format!("did you mean `{}`?", name)),
+        None => diagnostic,
+    }
+}
+
+/// 網羅されていないパターンの一覧
+/// 4つ以上の場合は先頭の3つのみを表示する
+/// ```text
+/// pattern `3` not covered
+/// patterns `i32::MIN..=-1` and `3..=i32::MAX` not covered
+/// patterns `1`, `3`, `5` and 2 more not covered
+/// ```
+fn missing_patterns(missing: &[String]) -> String {
+    let quoted: Vec<String> = missing.iter().map(|p| format!("`{}`", p)).collect();
+    match &quoted[..] {
+        [] => String::from("patterns"),
+        [only] => format!("pattern {}", only),
+        [init @ .., last] if quoted.len() <= 3 => {
+            format!("patterns {} and {}", init.join(", "), last)
+        }
+        _ => format!(
+            "patterns {} and {} more",
+            quoted[..3].join(", "),
+            quoted.len() - 3
+        ),
+    }
+}
@@ -0,0 +1,29 @@
+use crate::abs::span::Span;
+use crate::errors::diagnostic::Diagnostic;
+
+/// コードの生成は続けられるが、利用者に知らせるべき問題
+#[derive(Debug)]
+pub enum GenerateWarning {
+    // それより前の腕が全ての値を網羅しているため到達しない`match`の腕
+    UnreachablePattern(Span),
+}
+
+impl GenerateWarning {
+    /// 警告の原因となった位置を返却する
+    pub fn get_span(&self) -> Span {
+        match self {
+            Self::UnreachablePattern(span) => *span,
+        }
+    }
+
+    /// 利用者向けの診断に変換する
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let span = self.get_span();
+        match self {
+            Self::UnreachablePattern(_) => {
+                Diagnostic::warning("unreachable pattern", span, "no value reaches this arm")
+                    .with_note("every value matched by this pattern is matched by an earlier arm")
+            }
+        }
+    }
+}
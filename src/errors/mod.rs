@@ -1,3 +1,5 @@
 // errors パース時に発生したエラー処理
+pub mod diagnostic;
 pub mod generate_errors;
+pub mod generate_warnings;
 pub mod parser_errors;
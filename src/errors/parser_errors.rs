@@ -1,16 +1,174 @@
-#[derive(Debug)]
+use crate::abs::span::Span;
+use crate::errors::diagnostic::Diagnostic;
+
+/// パース時に発生したエラーについて出力します
+/// それぞれのエラーはエラーの原因となったソース上の位置を保持します
+#[derive(Clone, Debug)]
 pub enum ParserError {
-    QuotationNotClosed,
-    BraceNotOpened,
-    BraceNotClosed,
-    GroupingSyntaxBoxError, // please write \"if\",\"while\" or \"for\" statement head
-    OperationError,         // OperationError 見つからない場合
-    CommentBlockNotClosed,
+    // 閉じられていない文字列
+    QuotationNotClosed {
+        open: Span, // 文字列の開始位置
+        end: Span,  // 閉じる`"`が必要な位置
+    },
+    BraceNotOpened(Span), // 対応する開き括弧のない閉じ括弧の位置
+    // 閉じられていない括弧
+    BraceNotClosed {
+        open: Span,  // 閉じられていない括弧の位置
+        end: Span,   // 閉じ括弧が必要な位置
+        close: char, // 必要な閉じ括弧
+    },
+    GroupingSyntaxBoxError(Span), // please write \"if\",\"while\" or \"for\" statement head
+    OperationError(Span),         // OperationError 見つからない場合
+    CommentBlockNotClosed(Span),  // コメントの開始位置
+    UnsupportedSyntax(Span),      // まだ解析に対応していない構文
+    DecFuncError(Span),           // 関数宣言の形式が不正な場合
+    InvalidType(Span),            // 型として解釈できない型注釈
+    InvalidNumLiteral(Span),      // 数値リテラルとして解釈できない数字から始まる単語
+    MatchArmError(Span),          // `match`の腕が`pattern => expr`の形式ではない場合
+    DecStructError(Span),         // 構造体宣言の形式が不正な場合
+    StructLiteralError(Span),     // 構造体のリテラルのフィールドが`name: value`の形式ではない場合
+    DecEnumError(Span),           // 列挙型宣言の形式が不正な場合
+    ArrayLengthError(Span),       // `[0; n]` `[i32; n]`の要素の数が整数のリテラルではない場合
     // for developers
-    UnableToConvertType, // StmtElem をExprElemに変換できないときに発生する
-    UnexpectedTypeStmt,
-    UnexpectedTypeExpr,
-    UnexpectedTypeComma,
+    UnableToConvertType(Span), // StmtElem をExprElemに変換できないときに発生する
+    UnexpectedTypeStmt(Span),
+    UnexpectedTypeExpr(Span),
+    UnexpectedTypeComma(Span),
     DevError,
     Uncategorized,
 }
+
+impl ParserError {
+    /// エラーの原因となった位置を返却する
+    /// 閉じられていない文字列や括弧は開始位置を返却する
+    pub fn get_span(&self) -> Span {
+        match self {
+            Self::QuotationNotClosed { open, .. } | Self::BraceNotClosed { open, .. } => *open,
+            Self::BraceNotOpened(span)
+            | Self::GroupingSyntaxBoxError(span)
+            | Self::OperationError(span)
+            | Self::CommentBlockNotClosed(span)
+            | Self::UnsupportedSyntax(span)
+            | Self::DecFuncError(span)
+            | Self::InvalidType(span)
+            | Self::InvalidNumLiteral(span)
+            | Self::MatchArmError(span)
+            | Self::DecStructError(span)
+            | Self::StructLiteralError(span)
+            | Self::DecEnumError(span)
+            | Self::ArrayLengthError(span)
+            | Self::UnableToConvertType(span)
+            | Self::UnexpectedTypeStmt(span)
+            | Self::UnexpectedTypeExpr(span)
+            | Self::UnexpectedTypeComma(span) => *span,
+            Self::DevError | Self::Uncategorized => Span::default(),
+        }
+    }
+
+    /// 利用者向けの診断に変換する
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let span = self.get_span();
+        match self {
+            // 閉じる位置を主な位置とし、開始位置を補足として示す
+            Self::QuotationNotClosed { open, end } => {
+                Diagnostic::error("unterminated string literal", *end, "expected `\"`")
+                    .with_label(*open, "string starts here")
+                    .with_note("add a closing `\"` to end the string")
+            }
+            Self::BraceNotOpened(_) => Diagnostic::error(
+                "unexpected closing delimiter",
+                span,
+                "this delimiter has no matching opening delimiter",
+            ),
+            Self::BraceNotClosed { open, end, close } => Diagnostic::error(
+                "this delimiter is never closed",
+                *end,
+                &format!("expected `{}`", close),
+            )
+            .with_label(*open, "brace opened here"),
+            Self::GroupingSyntaxBoxError(_) => Diagnostic::error(
+                "`elif` or `else` without a preceding `if`, `while` or `for`",
+                span,
+                "this block has no head",
+            )
+            .with_note("please write \"if\", \"while\" or \"for\" statement head"),
+            Self::OperationError(_) => {
+                Diagnostic::error("unknown operator", span, "this operator is not defined")
+            }
+            Self::CommentBlockNotClosed(_) => {
+                Diagnostic::error("unterminated block comment", span, "comment starts here")
+                    .with_note("add `*/` to end the comment")
+            }
+            Self::UnsupportedSyntax(_) => Diagnostic::error(
+                "unsupported syntax",
+                span,
+                "this syntax is not supported yet",
+            ),
+            Self::DecFuncError(_) => Diagnostic::error(
+                "invalid function declaration",
+                span,
+                "unexpected token in function declaration",
+            )
+            .with_note("function declarations are written as `fn name(a: i32) -> i32 { ... }`"),
+            Self::InvalidType(_) => {
+                Diagnostic::error("invalid type", span, "this is not a known type")
+            }
+            Self::InvalidNumLiteral(_) => Diagnostic::error(
+                "invalid number literal",
+                span,
+                "not a valid number literal",
+            )
+            .with_note("number literals look like `42`, `0x1F`, `0o17`, `0b1010`, `1_000`, `1.5e-3` or `10i64`"),
+            Self::MatchArmError(_) => Diagnostic::error(
+                "invalid match arm",
+                span,
+                "expected `pattern => expression`",
+            )
+            .with_note("match arms are written as `match (x) { 0 => a, 1..10 => b, _ => c }`"),
+            Self::DecStructError(_) => Diagnostic::error(
+                "invalid struct declaration",
+                span,
+                "unexpected token in struct declaration",
+            )
+            .with_note("struct declarations are written as `struct Point { x: f64, y: f64 }`"),
+            Self::StructLiteralError(_) => Diagnostic::error(
+                "invalid struct literal field",
+                span,
+                "expected `name: value`",
+            )
+            .with_note("struct literals are written as `Point { x: 1.0, y: 2.0 }`"),
+            Self::DecEnumError(_) => Diagnostic::error(
+                "invalid enum declaration",
+                span,
+                "unexpected token in enum declaration",
+            )
+            .with_note(
+                "enum declarations are written as `enum Shape { Circle(f64), Rect(f64, f64) }`",
+            ),
+            Self::ArrayLengthError(_) => Diagnostic::error(
+                "invalid array length",
+                span,
+                "expected an integer literal",
+            )
+            .with_note("the length of an array is written as `[0; 10]` or `[i32; 10]`"),
+            Self::UnableToConvertType(_) => Diagnostic::error(
+                "internal compiler error: unable to convert statement into expression",
+                span,
+                "",
+            )
+            .with_note("this is a bug in the Lichen compiler"),
+            Self::UnexpectedTypeStmt(_)
+            | Self::UnexpectedTypeExpr(_)
+            | Self::UnexpectedTypeComma(_) => Diagnostic::error(
+                "internal compiler error: unexpected token",
+                span,
+                "unexpected token",
+            )
+            .with_note("this is a bug in the Lichen compiler"),
+            Self::DevError | Self::Uncategorized => {
+                Diagnostic::error("internal compiler error", span, "")
+                    .with_note("this is a bug in the Lichen compiler")
+            }
+        }
+    }
+}
@@ -0,0 +1,360 @@
+use crate::abs::ast::{ExprElem, StmtElem};
+use crate::abs::span::Span;
+use crate::errors::generate_errors::GenerateError;
+use crate::gen::resolver::{for_parts, is_cast, let_parts};
+use crate::token::decfunc::DecFuncBranch;
+use crate::token::stmt::stmt::StmtBranch;
+use crate::token::syntax::SyntaxBranch;
+
+/// # AssignmentChecker
+/// 初期値を持たずに宣言された変数が、参照される前に必ず代入されていることを検査する
+///
+/// 型注釈の有無によらず、全ての経路で代入されていない変数を参照するとエラーになる
+/// `let`で宣言された変数には一度だけ代入でき、代入されている可能性がある変数への代入はエラーになる
+/// ```lichen
+/// let a;
+/// if (c) { a = 1; } else { a = 2; };
+/// let b: i32;
+/// if (c) { b = 1; };
+/// return a + b; // `b`は代入されていない可能性がある
+/// ```
+/// `Resolver`で名前を解決した後の関数に対して実行する
+pub struct AssignmentChecker<'a> {
+    /// 初期値を持たずに宣言された変数の(一意な名前, 再代入できるか, 宣言された位置)
+    locals: &'a [(String, bool, Span)],
+    /// 実行中のループごとの`break`と`continue`の時点の状態
+    loops: Vec<LoopExits>,
+}
+
+/// ある位置での変数の代入の状態
+#[derive(Clone, Debug, Default)]
+struct State {
+    /// 全ての経路で代入されている変数
+    assigned: Vec<String>,
+    /// いずれかの経路で代入されている変数
+    maybe_assigned: Vec<String>,
+    /// この位置には到達しない(`return` `break` `continue`の後)
+    diverges: bool,
+}
+
+impl State {
+    fn unreachable() -> Self {
+        Self {
+            diverges: true,
+            ..Default::default()
+        }
+    }
+
+    /// 二つの経路が合流した後の状態
+    fn join(self, other: Self) -> Self {
+        if self.diverges {
+            return other;
+        }
+        if other.diverges {
+            return self;
+        }
+        let mut maybe_assigned = self.maybe_assigned;
+        for name in other.maybe_assigned {
+            if !maybe_assigned.contains(&name) {
+                maybe_assigned.push(name);
+            }
+        }
+        Self {
+            assigned: self
+                .assigned
+                .into_iter()
+                .filter(|name| other.assigned.contains(name))
+                .collect(),
+            maybe_assigned,
+            diverges: false,
+        }
+    }
+
+    fn assign(&mut self, name: &str) {
+        for list in [&mut self.assigned, &mut self.maybe_assigned] {
+            if !list.iter().any(|assigned| assigned == name) {
+                list.push(name.to_string());
+            }
+        }
+    }
+}
+
+/// ループの本体から抜ける経路の状態
+#[derive(Debug, Default)]
+struct LoopExits {
+    breaks: Vec<State>,
+    continues: Vec<State>,
+}
+
+impl<'a> AssignmentChecker<'a> {
+    /// `locals`は初期値を持たずに宣言された変数の(一意な名前, 再代入できるか, 宣言された位置)
+    pub fn check_func(
+        dec_func: &DecFuncBranch,
+        locals: &'a [(String, bool, Span)],
+    ) -> Result<(), GenerateError> {
+        if locals.is_empty() {
+            return Ok(());
+        }
+        let mut checker = Self {
+            locals,
+            loops: Vec::new(),
+        };
+        checker.check_stmt_list(&dec_func.contents, State::default())?;
+        Ok(())
+    }
+
+    fn local(&self, name: &str) -> Option<&(String, bool, Span)> {
+        self.locals.iter().find(|(local, _, _)| local == name)
+    }
+
+    fn check_stmt_list(
+        &mut self,
+        stmt_list: &[StmtElem],
+        mut state: State,
+    ) -> Result<State, GenerateError> {
+        for inner in stmt_list {
+            state = match inner {
+                StmtElem::ExprElem(expr_b) => self.check_expr_list(&expr_b.code_list, state)?,
+                StmtElem::Special(control_b) => self.check_control(control_b, state)?,
+                StmtElem::BlockElem(block_b) => self.check_stmt_list(&block_b.contents, state)?,
+                _ => {
+                    // 関数の中で宣言された関数は別の関数として検査する
+                    state
+                }
+            };
+        }
+        Ok(state)
+    }
+
+    fn check_control(
+        &mut self,
+        control_b: &StmtBranch,
+        state: State,
+    ) -> Result<State, GenerateError> {
+        match &*control_b.head {
+            "let" | "let_mut" => {
+                let Some((pattern, init)) = let_parts(&control_b.code_list) else {
+                    return Ok(state);
+                };
+                let mut state = match init {
+                    Some(init) => self.check_expr(init, state)?,
+                    None => state,
+                };
+                // ループの中の宣言は反復ごとに新しい変数になる
+                if let Some(ExprElem::WordElem(word_b)) = pattern.first() {
+                    state.assigned.retain(|name| *name != word_b.contents);
+                    state.maybe_assigned.retain(|name| *name != word_b.contents);
+                }
+                Ok(state)
+            }
+            head @ ("return" | "break" | "continue") => {
+                let state = self.check_expr_list(&control_b.code_list, state)?;
+                if let Some(exits) = self.loops.last_mut() {
+                    match head {
+                        "break" => exits.breaks.push(state),
+                        "continue" => exits.continues.push(state),
+                        _ => {}
+                    }
+                }
+                Ok(State::unreachable())
+            }
+            _ => self.check_expr_list(&control_b.code_list, state),
+        }
+    }
+
+    fn check_expr_list(
+        &mut self,
+        expr_list: &[ExprElem],
+        mut state: State,
+    ) -> Result<State, GenerateError> {
+        for expr in expr_list {
+            state = self.check_expr(expr, state)?;
+        }
+        Ok(state)
+    }
+
+    fn check_expr(&mut self, expr: &ExprElem, state: State) -> Result<State, GenerateError> {
+        match expr {
+            ExprElem::WordElem(word_b) => {
+                let local = self.local(&word_b.contents);
+                if let Some((name, _, declared)) = local {
+                    if !state.diverges && !state.assigned.contains(name) {
+                        return Err(GenerateError::UsedBeforeAssignment {
+                            // 宣言時の名前(`x.1`の`x`)で報告する
+                            name: name.split('.').next().unwrap_or(name).to_string(),
+                            span: word_b.span,
+                            declared: *declared,
+                        });
+                    }
+                }
+                Ok(state)
+            }
+            ExprElem::FuncElem(func_b) => match (&*func_b.name, &func_b.contents[..]) {
+                (ExprElem::OpeElem(ope_b), [l_expr, r_expr]) if ope_b.is_assignment() => {
+                    let target = match l_expr {
+                        ExprElem::ItemElem(item_b) => match &item_b.contents[..] {
+                            [ExprElem::WordElem(word_b)] => Some(word_b),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    let Some(word_b) = target.filter(|_| ope_b.ope == "=") else {
+                        // 複合代入やフィールド・要素への代入は左辺の変数を参照する
+                        let state = self.check_expr(l_expr, state)?;
+                        return self.check_expr(r_expr, state);
+                    };
+                    let mut state = self.check_expr(r_expr, state)?;
+                    if let Some((name, is_mutable, declared)) = self.local(&word_b.contents) {
+                        if !is_mutable && !state.diverges && state.maybe_assigned.contains(name) {
+                            return Err(GenerateError::AssignToImmutable {
+                                name: name.split('.').next().unwrap_or(name).to_string(),
+                                span: func_b.span,
+                                declared: *declared,
+                            });
+                        }
+                        state.assign(name);
+                    }
+                    Ok(state)
+                }
+                (ExprElem::OpeElem(ope_b), [l_expr, r_expr])
+                    if matches!(&*ope_b.ope, "&&" | "||") =>
+                {
+                    // 右辺は評価されない場合がある
+                    let state = self.check_expr(l_expr, state)?;
+                    let r_state = self.check_expr(r_expr, state.clone())?;
+                    Ok(state.join(r_state))
+                }
+                (name, contents) if is_cast(name) => self.check_expr_list(&contents[..1], state),
+                (ExprElem::OpeElem(_) | ExprElem::WordElem(_), contents) => {
+                    self.check_expr_list(contents, state)
+                }
+                (name, contents) => {
+                    let state = self.check_expr(name, state)?;
+                    self.check_expr_list(contents, state)
+                }
+            },
+            ExprElem::ItemElem(item_b) => self.check_expr_list(&item_b.contents, state),
+            ExprElem::ParenBlockElem(paren_b) => self.check_expr_list(&paren_b.contents, state),
+            ExprElem::ListBlockElem(list_block_b) => {
+                self.check_expr_list(&list_block_b.contents, state)
+            }
+            ExprElem::ListElem(list_b) => {
+                let state = if list_b.is_memory() {
+                    state
+                } else {
+                    self.check_expr(&list_b.name, state)?
+                };
+                self.check_expr_list(&list_b.contents, state)
+            }
+            ExprElem::BlockElem(block_b) => self.check_stmt_list(&block_b.contents, state),
+            ExprElem::SyntaxElem(syntax_b) => {
+                self.check_syntax(std::slice::from_ref(syntax_b), state)
+            }
+            ExprElem::SyntaxBoxElem(syntax_box_b) => {
+                self.check_syntax(&syntax_box_b.contents, state)
+            }
+            ExprElem::MatchElem(match_b) => {
+                let state = self.check_expr_list(&match_b.expr, state)?;
+                let mut result = State::unreachable();
+                for arm in &match_b.arms {
+                    let arm_state = self.check_expr_list(&arm.contents, state.clone())?;
+                    result = result.join(arm_state);
+                }
+                Ok(result)
+            }
+            ExprElem::StructElem(struct_b) => {
+                let mut state = state;
+                for field in &struct_b.fields {
+                    state = self.check_expr_list(&field.value, state)?;
+                }
+                Ok(state)
+            }
+            ExprElem::FieldElem(field_b) => self.check_expr(&field_b.base, state),
+            ExprElem::VariantElem(variant_b) => self.check_expr_list(&variant_b.args, state),
+            _ => Ok(state),
+        }
+    }
+
+    /// `if` `elif` `else`の連なりとループ
+    fn check_syntax(
+        &mut self,
+        syntax_list: &[SyntaxBranch],
+        state: State,
+    ) -> Result<State, GenerateError> {
+        let Some(head) = syntax_list.first() else {
+            return Ok(state);
+        };
+        match &*head.name {
+            "if" => {
+                let mut state = state;
+                let mut result = State::unreachable();
+                let mut has_else = false;
+                for syntax_b in syntax_list {
+                    // 条件式は前の節の条件が偽の場合に順に評価される
+                    state = self.check_expr_list(&syntax_b.expr, state)?;
+                    has_else |= syntax_b.name == "else";
+                    let branch = self.check_stmt_list(&syntax_b.contents, state.clone())?;
+                    result = result.join(branch);
+                }
+                if !has_else {
+                    result = result.join(state);
+                }
+                Ok(result)
+            }
+            "while" | "loop" | "for" => {
+                let mut state = state;
+                for syntax_b in syntax_list {
+                    state = self.check_loop(syntax_b, state)?;
+                }
+                Ok(state)
+            }
+            _ => {
+                let mut state = state;
+                for syntax_b in syntax_list {
+                    state = self.check_expr_list(&syntax_b.expr, state)?;
+                    state = self.check_stmt_list(&syntax_b.contents, state)?;
+                }
+                Ok(state)
+            }
+        }
+    }
+
+    /// ループの本体は二度検査する
+    /// 一度目で求めた次の反復の開始時の状態を合流させ、二度目の検査で前の反復での代入を考慮する
+    fn check_loop(
+        &mut self,
+        syntax_b: &SyntaxBranch,
+        state: State,
+    ) -> Result<State, GenerateError> {
+        // `for`の反復の対象は一度だけ評価する
+        let (state, condition) = match &*syntax_b.name {
+            "for" => match for_parts(&syntax_b.expr) {
+                Some((_, iterable)) => (self.check_expr(iterable, state)?, &[][..]),
+                None => (state, &[][..]),
+            },
+            _ => (state, &syntax_b.expr[..]),
+        };
+        let mut entry = state.clone();
+        let mut exits = LoopExits::default();
+        for _ in 0..2 {
+            let checked = self.check_expr_list(condition, entry.clone())?;
+            self.loops.push(LoopExits::default());
+            let body = self.check_stmt_list(&syntax_b.contents, checked.clone());
+            exits = self.loops.pop().unwrap_or_default();
+            let next = exits
+                .continues
+                .drain(..)
+                .fold(body?, |next, continued| next.join(continued));
+            entry = state.clone().join(next);
+            exits.breaks.push(checked);
+        }
+        if syntax_b.name == "loop" {
+            // 条件のない`loop`は`break`でのみ抜ける
+            exits.breaks.pop();
+        }
+        Ok(exits
+            .breaks
+            .into_iter()
+            .fold(State::unreachable(), |exit, state| exit.join(state)))
+    }
+}
@@ -1 +1,7 @@
+pub mod assignment;
+pub mod module;
+pub mod pattern;
+pub mod resolver;
+pub mod type_checker;
+pub mod types;
 pub mod wasm;
@@ -0,0 +1,536 @@
+use crate::abs::ast::{ExprElem, StmtElem, Token, TypeElem};
+use crate::abs::gen::Wasm_gen;
+use crate::errors::generate_errors::GenerateError;
+use crate::errors::generate_warnings::GenerateWarning;
+use crate::gen::assignment::AssignmentChecker;
+use crate::gen::resolver::Resolver;
+use crate::gen::type_checker::{FuncEffects, FuncSignature, TypeChecker};
+use crate::gen::types::{value_type, EnumLayout, ModuleTypes, StructLayout, TypeNames, ValueType};
+use crate::gen::wasm::{
+    alloc_helper_wasm, copy_helper_wasm, pow_helper_wasm, wasm_primitive_type, wasm_stmt_gen,
+    ALLOC_FUNC_NAME, MEMORY_SPACE_NAME, POW_FUNC_PREFIX, RESERVED_PREFIX,
+};
+
+use crate::parser::type_parser::TypeParser;
+use crate::token::decenum::DecEnumBranch;
+use crate::token::decfunc::DecFuncBranch;
+use crate::token::decstruct::DecStructBranch;
+use crate::token::ttype::primitive::PrimitiveType;
+
+/// # generate_wasm_module
+/// 関数宣言と型宣言の集まりから`(module ...)`全体を生成する
+///
+/// - 構造体宣言と列挙型宣言から線形メモリ上の配置を計算する
+/// - 関数のシグネチャごとに`(type ...)`を一つ宣言する
+/// - 関数宣言ごとに`(func ...)`を生成する(関数の中で宣言された関数は`$outer.helper`とする)
+/// - `pub_fn`で宣言された関数は`(export ...)`する
+/// - `**`やレコードの確保・複製を使用している場合は補助関数を追加する
+///
+/// ```lichen
+/// pub_fn add(a: i32, b: i32) -> i32 {
+///     return a + b;
+/// }
+/// ```
+/// ```wat
+/// (module
+/// (type $t0 (func (param i32) (param i32) (result i32)))
+/// (memory $__mem 1)
+/// (func $add (type $t0) (param $a i32) (param $b i32) (result i32)
+/// ...
+/// )
+/// (export "add" (func $add))
+/// )
+/// ```
+pub fn generate_wasm_module(code_list: &[StmtElem]) -> Result<String, GenerateError> {
+    generate_wasm_module_with_warnings(code_list).map(|(assembly_text, _)| assembly_text)
+}
+
+/// `generate_wasm_module`と同様にモジュールを生成し、生成中に見つかった警告も返却する
+pub fn generate_wasm_module_with_warnings(
+    code_list: &[StmtElem],
+) -> Result<(String, Vec<GenerateWarning>), GenerateError> {
+    let mut top_level: Vec<&DecFuncBranch> = Vec::new();
+    let mut dec_structs: Vec<&DecStructBranch> = Vec::new();
+    let mut dec_enums: Vec<&DecEnumBranch> = Vec::new();
+    for inner in code_list {
+        match inner {
+            StmtElem::DecFuncElem(dec_func) => top_level.push(dec_func),
+            StmtElem::DecStructElem(dec_struct) => dec_structs.push(dec_struct),
+            StmtElem::DecEnumElem(dec_enum) => dec_enums.push(dec_enum),
+            StmtElem::CommentElem(_) => {
+                // pass
+            }
+            _ => {
+                // トップレベルには関数宣言と型宣言のみを記述できる
+                return Err(GenerateError::UnsupportedStatement(inner.get_span()));
+            }
+        }
+    }
+
+    // 型は宣言の順序によらず、互いのフィールドの型として使用できる
+    let types = module_types(&dec_structs, &dec_enums)?;
+
+    // トップレベルの関数はどこからでも呼び出せる
+    check_func_names(&top_level)?;
+    let visible: Vec<(String, String)> = top_level
+        .iter()
+        .map(|f| (f.name.clone(), f.name.clone()))
+        .collect();
+    // ヴァリアントの値`Circle(1.0)`は関数呼び出しと同じ形をしている
+    for dec_enum in &dec_enums {
+        for variant in &dec_enum.variants {
+            if visible.iter().any(|(name, _)| *name == variant.name) {
+                return Err(GenerateError::DuplicateDefinition {
+                    name: variant.name.clone(),
+                    span: variant.span,
+                });
+            }
+        }
+    }
+    let mut funcs: Vec<ModuleFunc> = Vec::new();
+    for dec_func in top_level {
+        collect_dec_funcs(dec_func, dec_func.name.clone(), &visible, &mut funcs)?;
+    }
+
+    // 型検査に使用する関数のシグネチャ
+    let func_signatures = funcs
+        .iter()
+        .map(|func| func.dec_func.func_signature(&func.wasm_name, &types.names))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // (type ...)
+    let mut signatures: Vec<String> = Vec::new();
+    for func_signature in &func_signatures {
+        let signature = func_signature.wasm_signature();
+        if !signatures.contains(&signature) {
+            signatures.push(signature);
+        }
+    }
+
+    // 全ての関数を検査してから、引数を変更する関数の呼び出しを検査する
+    let mut warnings: Vec<GenerateWarning> = Vec::new();
+    let checked_funcs = funcs
+        .iter()
+        .zip(&func_signatures)
+        .map(|(func, func_signature)| {
+            func.dec_func.check_func(
+                func_signature,
+                &func.visible,
+                &func_signatures,
+                &types,
+                &mut warnings,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    check_borrowed_arguments(&checked_funcs, &func_signatures)?;
+
+    // (func ...)
+    let mut funcs_text = String::new();
+    for (checked_func, func_signature) in checked_funcs.iter().zip(&func_signatures) {
+        let signature = func_signature.wasm_signature();
+        let type_index = signatures.iter().position(|s| *s == signature).unwrap_or(0);
+        funcs_text.push_str(&checked_func.generate_wasm_func(type_index, func_signature)?);
+    }
+    // レコードを複製する関数は使用されている型と、そのフィールドや要素の型の分だけ追加する
+    let copies: Vec<ValueType> = checked_funcs
+        .iter()
+        .flat_map(|checked_func| checked_func.effects.copies.iter().cloned())
+        .collect();
+    for t in copy_types(&copies, &types) {
+        funcs_text.push_str(&copy_helper_wasm(&t, &types)?);
+    }
+    // レコードを確保する関数は使用されている場合のみ追加する
+    if funcs_text.contains(&format!("call ${}\n", ALLOC_FUNC_NAME)) {
+        funcs_text.push_str(&alloc_helper_wasm());
+    }
+    // `**`の補助関数は使用されている場合のみ追加する
+    // インポートは他の定義よりも前に記述する必要がある
+    let mut imports_text = String::new();
+    for t in [PrimitiveType::I32, PrimitiveType::I64, PrimitiveType::F64] {
+        let name = format!("call ${}{}\n", POW_FUNC_PREFIX, wasm_primitive_type(t));
+        if !funcs_text.contains(&name) {
+            continue;
+        }
+        if t.is_float() {
+            imports_text.push_str(&pow_helper_wasm(t));
+        } else {
+            funcs_text.push_str(&pow_helper_wasm(t));
+        }
+    }
+
+    let mut assembly_text = String::from("(module\n");
+    for (index, signature) in signatures.iter().enumerate() {
+        assembly_text.push_str(&format!("(type $t{} (func{}))\n", index, signature));
+    }
+    assembly_text.push_str(&imports_text);
+    assembly_text.push_str(&format!("(memory ${} 1)\n", MEMORY_SPACE_NAME));
+    assembly_text.push_str(&funcs_text);
+    // (export ...)
+    for func in &funcs {
+        if func.dec_func.is_public {
+            assembly_text.push_str(&format!(
+                "(export \"{}\" (func ${}))\n",
+                func.wasm_name, func.wasm_name
+            ));
+        }
+    }
+    assembly_text.push_str(")\n");
+    Ok((assembly_text, warnings))
+}
+
+/// 型の名前を集め、配置を計算する
+/// 構造体と列挙型とヴァリアントの名前は重複してはならない
+fn module_types(
+    dec_structs: &[&DecStructBranch],
+    dec_enums: &[&DecEnumBranch],
+) -> Result<ModuleTypes, GenerateError> {
+    let mut names = TypeNames::default();
+    let mut variants: Vec<String> = Vec::new();
+    let is_defined = |names: &TypeNames, name: &str| {
+        names.all().iter().any(|defined| defined == name)
+            || PrimitiveType::from_name(name).is_some()
+    };
+    for dec_struct in dec_structs {
+        if is_defined(&names, &dec_struct.name) {
+            return Err(GenerateError::DuplicateDefinition {
+                name: dec_struct.name.clone(),
+                span: dec_struct.span,
+            });
+        }
+        names.structs.push(dec_struct.name.clone());
+    }
+    for dec_enum in dec_enums {
+        if is_defined(&names, &dec_enum.name) {
+            return Err(GenerateError::DuplicateDefinition {
+                name: dec_enum.name.clone(),
+                span: dec_enum.span,
+            });
+        }
+        names.enums.push(dec_enum.name.clone());
+    }
+    for variant in dec_enums.iter().flat_map(|dec_enum| &dec_enum.variants) {
+        if variants.contains(&variant.name) {
+            return Err(GenerateError::DuplicateDefinition {
+                name: variant.name.clone(),
+                span: variant.span,
+            });
+        }
+        variants.push(variant.name.clone());
+    }
+    let structs = dec_structs
+        .iter()
+        .map(|dec_struct| StructLayout::new(dec_struct, &names))
+        .collect::<Result<Vec<_>, _>>()?;
+    let enums = dec_enums
+        .iter()
+        .map(|dec_enum| EnumLayout::new(dec_enum, &names))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ModuleTypes {
+        names,
+        structs,
+        enums,
+    })
+}
+
+/// モジュールに含まれる関数
+struct ModuleFunc<'a> {
+    dec_func: &'a DecFuncBranch,
+    /// wasm上の関数名
+    /// 関数の中で宣言された関数は外側の関数の名前を付ける(`$outer.helper`)
+    wasm_name: String,
+    /// この関数から呼び出すことのできる関数の(名前, wasm上の関数名)
+    visible: Vec<(String, String)>,
+}
+
+/// 名前を解決し、型検査を終えた関数
+struct CheckedFunc {
+    dec_func: DecFuncBranch,
+    /// ローカル変数のwasm上の型
+    locals: Vec<(String, PrimitiveType)>,
+    effects: FuncEffects,
+}
+
+/// 引数のフィールドや要素に代入する関数に、再代入できない変数を渡していないかを検査する
+/// 引数をそのまま別の関数に渡す関数は、渡した先の関数が変更する場合にその引数を変更する
+fn check_borrowed_arguments(
+    checked_funcs: &[CheckedFunc],
+    func_signatures: &[FuncSignature],
+) -> Result<(), GenerateError> {
+    let position = |name: &str| func_signatures.iter().position(|f| f.name == name);
+    let mut mutated: Vec<Vec<bool>> = func_signatures
+        .iter()
+        .map(|f| vec![false; f.params.len()])
+        .collect();
+    for (i, checked_func) in checked_funcs.iter().enumerate() {
+        for param in &checked_func.effects.mutated {
+            mutated[i][*param] = true;
+        }
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (i, checked_func) in checked_funcs.iter().enumerate() {
+            for (param, function, index) in &checked_func.effects.forwarded {
+                let Some(f) = position(function) else {
+                    continue;
+                };
+                if mutated[f][*index] && !mutated[i][*param] {
+                    mutated[i][*param] = true;
+                    changed = true;
+                }
+            }
+        }
+    }
+    for checked_func in checked_funcs {
+        for borrowed in &checked_func.effects.borrowed {
+            if position(&borrowed.function).is_some_and(|f| mutated[f][borrowed.index]) {
+                return Err(GenerateError::ImmutableArgument {
+                    name: borrowed.name.clone(),
+                    // 関数の中で宣言された関数は宣言時の名前(`outer.helper`の`helper`)で報告する
+                    function: borrowed
+                        .function
+                        .rsplit('.')
+                        .next()
+                        .unwrap_or(&borrowed.function)
+                        .to_string(),
+                    span: borrowed.span,
+                    declared: borrowed.declared,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 複製するレコードの型と、そのフィールドや要素として再帰的に複製するレコードの型
+fn copy_types(copies: &[ValueType], types: &ModuleTypes) -> Vec<ValueType> {
+    let mut all: Vec<ValueType> = Vec::new();
+    let mut pending = copies.to_vec();
+    while let Some(t) = pending.pop() {
+        if all.contains(&t) {
+            continue;
+        }
+        match &t {
+            ValueType::Struct(name) => {
+                if let Some(layout) = types.struct_layout(name) {
+                    pending.extend(layout.fields.iter().map(|f| f.field_type.clone()));
+                }
+            }
+            ValueType::Array(element, _) => pending.push((**element).clone()),
+            ValueType::Primitive(_) | ValueType::Enum(_) => continue,
+        }
+        all.push(t);
+    }
+    all
+}
+
+/// 同じスコープで宣言された関数の名前は重複してはならない
+/// `__`から始まる名前は補助関数(`__alloc` `__pow_i32`など)のために予約されている
+fn check_func_names(dec_funcs: &[&DecFuncBranch]) -> Result<(), GenerateError> {
+    for (i, dec_func) in dec_funcs.iter().enumerate() {
+        if dec_func.name.starts_with(RESERVED_PREFIX) {
+            return Err(GenerateError::ReservedName {
+                name: dec_func.name.clone(),
+                span: dec_func.span,
+            });
+        }
+        if dec_funcs[..i].iter().any(|f| f.name == dec_func.name) {
+            return Err(GenerateError::DuplicateDefinition {
+                name: dec_func.name.clone(),
+                span: dec_func.span,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 同じ関数の引数の名前は重複してはならない
+fn check_param_names(dec_func: &DecFuncBranch) -> Result<(), GenerateError> {
+    for (i, arg) in dec_func.args.iter().enumerate() {
+        if dec_func.args[..i].iter().any(|a| a.name == arg.name) {
+            return Err(GenerateError::DuplicateDefinition {
+                name: arg.name.clone(),
+                span: arg.span,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 関数の中で宣言された関数も含めて関数宣言を集める
+/// 関数の中で宣言された関数は、その関数と内側の関数からのみ呼び出せる
+/// 内側で宣言された関数は外側の同じ名前の関数よりも優先される
+fn collect_dec_funcs<'a>(
+    dec_func: &'a DecFuncBranch,
+    wasm_name: String,
+    visible: &[(String, String)],
+    funcs: &mut Vec<ModuleFunc<'a>>,
+) -> Result<(), GenerateError> {
+    let nested_funcs: Vec<&DecFuncBranch> = dec_func
+        .contents
+        .iter()
+        .filter_map(|inner| match inner {
+            StmtElem::DecFuncElem(nested) => Some(nested),
+            _ => None,
+        })
+        .collect();
+    check_param_names(dec_func)?;
+    check_func_names(&nested_funcs)?;
+    let nested_names: Vec<(String, String)> = nested_funcs
+        .iter()
+        .map(|nested| {
+            (
+                nested.name.clone(),
+                format!("{}.{}", wasm_name, nested.name),
+            )
+        })
+        .collect();
+    let mut visible: Vec<(String, String)> = visible
+        .iter()
+        .filter(|(name, _)| !nested_names.iter().any(|(nested, _)| nested == name))
+        .cloned()
+        .collect();
+    visible.extend(nested_names.iter().cloned());
+    funcs.push(ModuleFunc {
+        dec_func,
+        wasm_name,
+        visible: visible.clone(),
+    });
+    for (nested, (_, nested_wasm_name)) in nested_funcs.into_iter().zip(nested_names) {
+        collect_dec_funcs(nested, nested_wasm_name, &visible, funcs)?;
+    }
+    Ok(())
+}
+
+/// 型注釈をプリミティブ型に変換する
+pub fn primitive_type(t: &TypeElem) -> Result<PrimitiveType, GenerateError> {
+    match t {
+        TypeElem::PrimitiveElem(primitive_b) => Ok(primitive_b.primitive_type),
+        _ => Err(GenerateError::InvalidTypeError(t.get_span())),
+    }
+}
+
+/// `a as i64`の右辺の型
+pub fn cast_type(expr: &ExprElem) -> Result<PrimitiveType, GenerateError> {
+    let ExprElem::ItemElem(item_b) = expr else {
+        return Err(GenerateError::InvalidTypeError(expr.get_span()));
+    };
+    let type_elem =
+        TypeParser::resolve_annotation(&item_b.contents, item_b.depth, item_b.loopdepth)
+            .map_err(|e| GenerateError::InvalidTypeError(e.get_span()))?;
+    primitive_type(&type_elem)
+}
+
+/// 値の型をwasmの型に変換する
+fn wasm_value_type(t: &ValueType) -> &'static str {
+    wasm_primitive_type(t.wasm_type())
+}
+
+impl FuncSignature {
+    /// `(type ...)`に記述する` (param i32) (result i32)`の部分
+    fn wasm_signature(&self) -> String {
+        let mut signature = String::new();
+        for param in &self.params {
+            signature.push_str(&format!(" (param {})", wasm_value_type(param)));
+        }
+        if let Some(result) = &self.result {
+            signature.push_str(&format!(" (result {})", wasm_value_type(result)));
+        }
+        signature
+    }
+}
+
+impl DecFuncBranch {
+    /// 型検査に使用するシグネチャ
+    fn func_signature(
+        &self,
+        wasm_name: &str,
+        names: &TypeNames,
+    ) -> Result<FuncSignature, GenerateError> {
+        Ok(FuncSignature {
+            name: wasm_name.to_string(),
+            params: self
+                .args
+                .iter()
+                .map(|arg| value_type(&arg.arg_type, names))
+                .collect::<Result<_, _>>()?,
+            result: self
+                .return_type
+                .as_ref()
+                .map(|t| value_type(t, names))
+                .transpose()?,
+        })
+    }
+
+    /// 名前を解決し、`let`で宣言された変数を一意な名前に付け替えてから
+    /// 変数が参照の前に代入されていることと型を検査する
+    fn check_func(
+        &self,
+        signature: &FuncSignature,
+        functions: &[(String, String)],
+        func_signatures: &[FuncSignature],
+        types: &ModuleTypes,
+        warnings: &mut Vec<GenerateWarning>,
+    ) -> Result<CheckedFunc, GenerateError> {
+        let mut dec_func = self.clone();
+        let resolver = Resolver::resolve_func(&mut dec_func, functions, types)?;
+        AssignmentChecker::check_func(&dec_func, &resolver.uninitialized)?;
+        let (locals, effects) = TypeChecker::check_func(
+            &mut dec_func,
+            &signature.name,
+            &resolver,
+            func_signatures,
+            types,
+            warnings,
+        )?;
+        Ok(CheckedFunc {
+            dec_func,
+            locals,
+            effects,
+        })
+    }
+}
+
+impl CheckedFunc {
+    /// `(func ...)`を生成する
+    fn generate_wasm_func(
+        &self,
+        type_index: usize,
+        signature: &FuncSignature,
+    ) -> Result<String, GenerateError> {
+        let mut assembly_text = format!("(func ${} (type $t{})", signature.name, type_index);
+        for (arg, param) in self.dec_func.args.iter().zip(&signature.params) {
+            assembly_text.push_str(&format!(
+                " (param ${} {})",
+                arg.name,
+                wasm_value_type(param)
+            ));
+        }
+        if let Some(result) = &signature.result {
+            assembly_text.push_str(&format!(" (result {})", wasm_value_type(result)));
+        }
+        assembly_text.push('\n');
+        for (local, local_type) in &self.locals {
+            assembly_text.push_str(&format!(
+                "(local ${} {})\n",
+                local,
+                wasm_primitive_type(*local_type)
+            ));
+        }
+        assembly_text.push_str(&self.dec_func.generate_wasm()?);
+        assembly_text.push_str(")\n");
+        Ok(assembly_text)
+    }
+}
+
+impl Wasm_gen for DecFuncBranch {
+    /// 関数の本体を生成する
+    fn generate_wasm(&self) -> Result<String, GenerateError> {
+        let mut assembly_text = wasm_stmt_gen(&self.contents)?;
+        if self.return_type.is_some() {
+            // `return`せずに本体の終わりに到達した場合
+            assembly_text.push_str("unreachable\n");
+        }
+        Ok(assembly_text)
+    }
+}
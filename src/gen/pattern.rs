@@ -0,0 +1,649 @@
+use std::cmp::Ordering;
+
+use crate::abs::ast::{ExprElem, Token};
+use crate::errors::generate_errors::GenerateError;
+use crate::gen::types::{EnumLayout, ModuleTypes, ValueType, VariantLayout};
+use crate::token::num::NumValue;
+use crate::token::operator::OperatorBranch;
+use crate::token::ttype::primitive::PrimitiveType;
+use crate::token::word::WordBranch;
+
+/// `match`の腕のパターン
+pub enum Pattern<'a> {
+    /// `_`
+    Wildcard,
+    /// `1` `-1`
+    Literal(&'a ExprElem),
+    /// `1..10` `1..=9`
+    /// 両端は数値リテラルでなければならない
+    Range(&'a OperatorBranch, &'a ExprElem, &'a ExprElem),
+    /// `Red` `Circle(r)` `Rect(w, _)` `Move(Up, 1..10)`
+    /// ヴァリアントが持つ値はそれぞれパターンで検査する
+    Variant(&'a WordBranch, Vec<Pattern<'a>>),
+    /// ヴァリアントが持つ値のパターンに書かれた名前`r` `Up`
+    /// ヴァリアントの名前の場合は値を持たないヴァリアントのパターン、それ以外は変数の束縛
+    Name(&'a WordBranch),
+}
+
+/// `_`
+pub const WILDCARD: &str = "_";
+
+impl<'a> Pattern<'a> {
+    /// 腕のパターンを分類する
+    /// パターンとして使えない式の場合は`None`を返却する
+    pub fn classify(pattern: &'a [ExprElem]) -> Option<Self> {
+        let [inner] = pattern else {
+            return None;
+        };
+        match inner {
+            ExprElem::WordElem(word_b) if word_b.contents == WILDCARD => Some(Self::Wildcard),
+            ExprElem::WordElem(word_b) => Some(Self::Variant(word_b, Vec::new())),
+            ExprElem::FuncElem(func_b) => match &*func_b.name {
+                ExprElem::WordElem(word_b) => {
+                    let fields = func_b
+                        .contents
+                        .iter()
+                        .map(Self::classify_field)
+                        .collect::<Option<Vec<_>>>()?;
+                    Some(Self::Variant(word_b, fields))
+                }
+                _ => Self::classify_range(inner),
+            },
+            _ => Self::classify_range(inner),
+        }
+    }
+
+    /// ヴァリアントが持つ値のパターンを分類する
+    fn classify_field(arg: &'a ExprElem) -> Option<Self> {
+        let ExprElem::ItemElem(item_b) = arg else {
+            return None;
+        };
+        match &item_b.contents[..] {
+            [ExprElem::WordElem(word_b)] if word_b.contents != WILDCARD => Some(Self::Name(word_b)),
+            contents => Self::classify(contents),
+        }
+    }
+
+    /// リテラルもしくは範囲のパターン
+    fn classify_range(inner: &'a ExprElem) -> Option<Self> {
+        if is_literal(inner) {
+            return Some(Self::Literal(inner));
+        }
+        match range(inner) {
+            Some((ope_b, start, end)) if is_literal(start) && is_literal(end) => {
+                Some(Self::Range(ope_b, start, end))
+            }
+            _ => None,
+        }
+    }
+
+    /// 範囲のパターンは少なくとも一つの値を含まなければならない
+    /// `5..1` `3..3`はエラーになる
+    pub fn check_range(&self) -> Result<(), GenerateError> {
+        let Self::Range(ope_b, start, end) = self else {
+            return Ok(());
+        };
+        let inclusive = ope_b.ope == "..=";
+        let is_empty = match (int_value(start), int_value(end)) {
+            (Some(start), Some(end)) => match start.cmp(&end) {
+                Ordering::Greater => true,
+                Ordering::Equal => !inclusive,
+                Ordering::Less => false,
+            },
+            _ => false,
+        };
+        if is_empty {
+            return Err(GenerateError::EmptyRangePattern {
+                inclusive,
+                span: start.get_span().merge(&end.get_span()),
+            });
+        }
+        Ok(())
+    }
+
+    /// パターンが含む整数の範囲(両端を含む)
+    /// `_`とヴァリアントと浮動小数点数のパターンの場合は`None`を返却する
+    pub fn int_range(&self) -> Option<(i128, i128)> {
+        match self {
+            Self::Wildcard | Self::Variant(..) | Self::Name(_) => None,
+            Self::Literal(literal) => int_value(literal).map(|value| (value, value)),
+            Self::Range(ope_b, start, end) => {
+                let (start, end) = (int_value(start)?, int_value(end)?);
+                if ope_b.ope == "..=" {
+                    Some((start, end))
+                } else {
+                    Some((start, end - 1))
+                }
+            }
+        }
+    }
+}
+
+/// ヴァリアントのパターン`Circle(r)` `Move(Some(n), _)`で束縛する変数
+/// ヴァリアントの名前は束縛しない
+/// 名前を付け替えるために使用する
+pub fn bindings_mut<'a>(
+    pattern: &'a mut [ExprElem],
+    types: &ModuleTypes,
+) -> Vec<&'a mut WordBranch> {
+    let [ExprElem::FuncElem(func_b)] = pattern else {
+        return Vec::new();
+    };
+    if !matches!(&*func_b.name, ExprElem::WordElem(_)) {
+        return Vec::new();
+    }
+    let mut bindings = Vec::new();
+    for arg in &mut func_b.contents {
+        let ExprElem::ItemElem(item_b) = arg else {
+            continue;
+        };
+        let is_binding = matches!(
+            &item_b.contents[..],
+            [ExprElem::WordElem(word_b)]
+                if word_b.contents != WILDCARD && types.variant(&word_b.contents).is_none()
+        );
+        if !is_binding {
+            bindings.extend(bindings_mut(&mut item_b.contents, types));
+        } else if let [ExprElem::WordElem(binding)] = &mut item_b.contents[..] {
+            bindings.push(binding);
+        }
+    }
+    bindings
+}
+
+/// `1..10` `1..=9`の演算子と両端
+fn range(expr: &ExprElem) -> Option<(&OperatorBranch, &ExprElem, &ExprElem)> {
+    match expr {
+        ExprElem::ItemElem(item_b) if item_b.contents.len() == 1 => range(&item_b.contents[0]),
+        ExprElem::FuncElem(func_b) => match (&*func_b.name, &func_b.contents[..]) {
+            (ExprElem::OpeElem(ope_b), [start, end]) if matches!(&*ope_b.ope, ".." | "..=") => {
+                Some((ope_b, start, end))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 数値リテラル`1`または符号を反転した数値リテラル`-1`
+fn is_literal(expr: &ExprElem) -> bool {
+    literal_value(expr).is_some()
+}
+
+/// パターンに書かれた整数の値
+pub fn int_value(expr: &ExprElem) -> Option<i128> {
+    match literal_value(expr)? {
+        (NumValue::Int(value), negative) => {
+            let value = i128::try_from(value).ok()?;
+            Some(if negative { -value } else { value })
+        }
+        (NumValue::Float(_), _) => None,
+    }
+}
+
+/// 数値リテラルの値と符号が反転されているかどうか
+fn literal_value(expr: &ExprElem) -> Option<(NumValue, bool)> {
+    match expr {
+        ExprElem::NumElem(num_b) => Some((num_b.value, false)),
+        ExprElem::ItemElem(item_b) if item_b.contents.len() == 1 => {
+            literal_value(&item_b.contents[0])
+        }
+        ExprElem::FuncElem(func_b) => match (&*func_b.name, &func_b.contents[..]) {
+            (ExprElem::OpeElem(ope_b), [ExprElem::ItemElem(l_item), r_expr])
+                if ope_b.ope == "-" && l_item.has_no_elem() =>
+            {
+                match literal_value(r_expr)? {
+                    (value, false) => Some((value, true)),
+                    (_, true) => None,
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// # Coverage
+/// `match`の腕が網羅する値の検査の結果
+/// - `unreachable`: それより前の腕が全ての値を網羅しているため到達しない腕の番号
+/// - `missing`: どの腕にも一致しない値のパターン(`3..=i32::MAX`など)
+///
+/// 整数は腕ごとに一致する値の範囲を積み上げて、型の最小値から最大値までを覆うかを調べる
+/// 浮動小数点数は`_`でのみ網羅できる
+pub struct Coverage {
+    pub unreachable: Vec<usize>,
+    pub missing: Vec<String>,
+}
+
+impl Coverage {
+    pub fn check(patterns: &[Pattern], t: PrimitiveType) -> Self {
+        let mut unreachable = Vec::new();
+        let mut covered: Vec<(i128, i128)> = Vec::new();
+        let mut floats: Vec<u64> = Vec::new();
+        let mut has_wildcard = false;
+        for (index, pattern) in patterns.iter().enumerate() {
+            if has_wildcard {
+                unreachable.push(index);
+                continue;
+            }
+            match pattern {
+                Pattern::Wildcard => {
+                    if int_bounds(t).is_some_and(|bounds| is_covered(&covered, bounds)) {
+                        unreachable.push(index);
+                    }
+                    has_wildcard = true;
+                }
+                Pattern::Literal(literal) if t.is_float() => {
+                    // 同じ値のリテラル
+                    let bits = match literal_value(literal) {
+                        Some((NumValue::Float(v), negative)) => {
+                            (if negative { -v } else { v }).to_bits()
+                        }
+                        _ => continue,
+                    };
+                    if floats.contains(&bits) {
+                        unreachable.push(index);
+                    } else {
+                        floats.push(bits);
+                    }
+                }
+                _ => {
+                    let Some((low, high)) = pattern.int_range() else {
+                        continue;
+                    };
+                    if low > high || is_covered(&covered, (low, high)) {
+                        unreachable.push(index);
+                    } else {
+                        covered.push((low, high));
+                        covered = merge_ranges(covered);
+                    }
+                }
+            }
+        }
+
+        let missing = match int_bounds(t) {
+            _ if has_wildcard => Vec::new(),
+            Some((min, max)) => {
+                let mut missing = Vec::new();
+                let mut next = min;
+                for &(low, high) in &covered {
+                    if next < low {
+                        missing.push(range_as_string(next, low - 1, t));
+                    }
+                    next = next.max(high + 1);
+                }
+                if next <= max {
+                    missing.push(range_as_string(next, max, t));
+                }
+                missing
+            }
+            None => vec![String::from(WILDCARD)],
+        };
+        Self {
+            unreachable,
+            missing,
+        }
+    }
+}
+
+impl Coverage {
+    /// 列挙型の値に対する腕を検査する
+    /// ヴァリアントが持つ値のパターンも含めて、腕の前までの腕が一致しない値に一致するかを調べる
+    /// 網羅していない値は`Move(Down, _)`のようにヴァリアントごとに列挙する
+    pub fn check_variants(patterns: &[Pattern], layout: &EnumLayout, types: &ModuleTypes) -> Self {
+        let columns = [Column::Enum(layout)];
+        let mut unreachable = Vec::new();
+        let mut rows: Vec<Vec<Space>> = Vec::new();
+        for (index, pattern) in patterns.iter().enumerate() {
+            let row = vec![Space::new(pattern, &columns[0], types)];
+            if !is_useful(&rows, &row, &columns, types) {
+                unreachable.push(index);
+            }
+            rows.push(row);
+        }
+        let missing = witnesses(&rows, &columns, types)
+            .into_iter()
+            .filter_map(|witness| witness.into_iter().next())
+            .collect();
+        Self {
+            unreachable,
+            missing,
+        }
+    }
+}
+
+/// 網羅性の検査で扱うパターン
+/// ヴァリアントはタグではなく宣言の順の番号で表す
+#[derive(Clone, Debug)]
+enum Space {
+    /// `_`と変数の束縛
+    Wild,
+    /// 整数の範囲(両端を含む)
+    Int(i128, i128),
+    /// 浮動小数点数のリテラル
+    Float(u64),
+    Variant(usize, Vec<Space>),
+}
+
+/// 網羅性の検査で扱う値の型
+#[derive(Clone, Copy)]
+enum Column<'a> {
+    Int(PrimitiveType),
+    Float,
+    Enum(&'a EnumLayout),
+    /// 構造体と配列は`_`と変数の束縛でのみ検査する
+    Other,
+}
+
+impl<'a> Column<'a> {
+    fn new(value_type: &ValueType, types: &'a ModuleTypes) -> Self {
+        match value_type {
+            ValueType::Primitive(t) if t.is_float() => Self::Float,
+            ValueType::Primitive(t) => Self::Int(*t),
+            ValueType::Enum(name) => types.enum_layout(name).map_or(Self::Other, Self::Enum),
+            ValueType::Struct(_) | ValueType::Array(..) => Self::Other,
+        }
+    }
+
+    /// ヴァリアントが持つ値の型
+    fn fields(variant: &VariantLayout, types: &'a ModuleTypes) -> Vec<Self> {
+        variant
+            .fields
+            .iter()
+            .map(|field| Self::new(&field.field_type, types))
+            .collect()
+    }
+}
+
+impl Space {
+    /// 型検査を通ったパターンを変換する
+    fn new(pattern: &Pattern, column: &Column, types: &ModuleTypes) -> Self {
+        match (pattern, column) {
+            (Pattern::Variant(word_b, fields), Column::Enum(layout)) => {
+                let Some(index) = variant_index(layout, &word_b.contents) else {
+                    return Self::Wild;
+                };
+                let columns = Column::fields(&layout.variants[index], types);
+                let fields = columns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, column)| {
+                        fields
+                            .get(i)
+                            .map_or(Self::Wild, |field| Self::new(field, column, types))
+                    })
+                    .collect();
+                Self::Variant(index, fields)
+            }
+            (Pattern::Name(word_b), Column::Enum(layout)) => {
+                match variant_index(layout, &word_b.contents) {
+                    Some(index) => Self::Variant(index, Vec::new()),
+                    None => Self::Wild,
+                }
+            }
+            (Pattern::Literal(literal), Column::Float) => match literal_value(literal) {
+                Some((NumValue::Float(v), negative)) => {
+                    Self::Float((if negative { -v } else { v }).to_bits())
+                }
+                _ => Self::Wild,
+            },
+            (Pattern::Literal(_) | Pattern::Range(..), Column::Int(_)) => pattern
+                .int_range()
+                .map_or(Self::Wild, |(low, high)| Self::Int(low, high)),
+            _ => Self::Wild,
+        }
+    }
+}
+
+fn variant_index(layout: &EnumLayout, name: &str) -> Option<usize> {
+    layout
+        .variants
+        .iter()
+        .position(|variant| variant.name == name)
+}
+
+/// 先頭の列が`head`の値に一致する行の、先頭の列を除いた残り
+/// ヴァリアントの場合は先頭の列をヴァリアントが持つ値の列に置き換える
+/// `head`が`_`の場合は先頭の列が`_`の行のみ残す
+fn specialize(rows: &[Vec<Space>], head: &Space, arity: usize) -> Vec<Vec<Space>> {
+    rows.iter()
+        .filter_map(|row| {
+            let (first, rest) = row.split_first()?;
+            let mut fields = match (first, head) {
+                (Space::Wild, Space::Variant(..)) => vec![Space::Wild; arity],
+                (Space::Wild, _) => Vec::new(),
+                (Space::Variant(index, fields), Space::Variant(head, _)) if index == head => {
+                    fields.clone()
+                }
+                (Space::Int(low, high), Space::Int(head_low, head_high))
+                    if low <= head_low && head_high <= high =>
+                {
+                    Vec::new()
+                }
+                (Space::Float(bits), Space::Float(head)) if bits == head => Vec::new(),
+                _ => return None,
+            };
+            fields.extend(rest.iter().cloned());
+            Some(fields)
+        })
+        .collect()
+}
+
+/// 先頭の列に現れるヴァリアントの番号(宣言の順)
+fn variants_in(rows: &[Vec<Space>]) -> Vec<usize> {
+    let mut indices: Vec<usize> = rows
+        .iter()
+        .filter_map(|row| match row.first() {
+            Some(Space::Variant(index, _)) => Some(*index),
+            _ => None,
+        })
+        .collect();
+    indices.sort();
+    indices.dedup();
+    indices
+}
+
+/// 先頭の列に現れる整数の範囲の境界で、型の最小値から最大値までを分割する
+/// 分割したそれぞれの範囲では、どの行も全ての値に一致するか、どの値にも一致しない
+fn segments(rows: &[Vec<Space>], extra: Option<&Space>, t: PrimitiveType) -> Vec<(i128, i128)> {
+    let Some((min, max)) = int_bounds(t) else {
+        return Vec::new();
+    };
+    let mut cuts = vec![min];
+    for space in rows.iter().filter_map(|row| row.first()).chain(extra) {
+        if let Space::Int(low, high) = space {
+            if low <= high {
+                cuts.push((*low).clamp(min, max));
+                cuts.push(high.saturating_add(1).clamp(min, max + 1));
+            }
+        }
+    }
+    cuts.sort();
+    cuts.dedup();
+    cuts.iter()
+        .enumerate()
+        .filter(|(_, &low)| low <= max)
+        .map(|(i, &low)| (low, cuts.get(i + 1).map_or(max, |next| next - 1)))
+        .collect()
+}
+
+/// `row`が`rows`のどの行にも一致しない値に一致するかどうか
+fn is_useful(rows: &[Vec<Space>], row: &[Space], columns: &[Column], types: &ModuleTypes) -> bool {
+    let (Some((first, rest)), Some((column, rest_columns))) =
+        (row.split_first(), columns.split_first())
+    else {
+        return rows.is_empty();
+    };
+    match (first, column) {
+        (Space::Variant(index, fields), Column::Enum(layout)) => {
+            let mut field_columns = Column::fields(&layout.variants[*index], types);
+            let rows = specialize(rows, first, field_columns.len());
+            let mut row = fields.clone();
+            row.extend(rest.iter().cloned());
+            field_columns.extend(rest_columns.iter().copied());
+            is_useful(&rows, &row, &field_columns, types)
+        }
+        (Space::Wild, Column::Enum(layout)) if variants_in(rows).len() == layout.variants.len() => {
+            (0..layout.variants.len()).any(|index| {
+                let arity = layout.variants[index].fields.len();
+                let mut row = vec![Space::Variant(index, vec![Space::Wild; arity])];
+                row.extend(rest.iter().cloned());
+                is_useful(rows, &row, columns, types)
+            })
+        }
+        (Space::Int(..) | Space::Wild, Column::Int(t)) => segments(rows, Some(first), *t)
+            .into_iter()
+            .filter(|&(low, high)| match first {
+                Space::Int(first_low, first_high) => *first_low <= low && high <= *first_high,
+                _ => true,
+            })
+            .any(|(low, high)| {
+                is_useful(
+                    &specialize(rows, &Space::Int(low, high), 0),
+                    rest,
+                    rest_columns,
+                    types,
+                )
+            }),
+        (Space::Float(_), _) => is_useful(&specialize(rows, first, 0), rest, rest_columns, types),
+        _ => is_useful(
+            &specialize(rows, &Space::Wild, 0),
+            rest,
+            rest_columns,
+            types,
+        ),
+    }
+}
+/// どの行にも一致しない値のパターンの列
+/// ヴァリアントは現れるものを値のパターンごとに、現れないものを`Empty` `Rect(_, _)`として列挙する
+/// 整数は行の範囲の境界で分割し、結果が同じ隣接する範囲をまとめる
+fn witnesses(rows: &[Vec<Space>], columns: &[Column], types: &ModuleTypes) -> Vec<Vec<String>> {
+    let Some((column, rest_columns)) = columns.split_first() else {
+        return if rows.is_empty() {
+            vec![Vec::new()]
+        } else {
+            Vec::new()
+        };
+    };
+    if rows.is_empty() {
+        return vec![vec![String::from(WILDCARD); columns.len()]];
+    }
+    let prefixed = |head: String, witnesses: Vec<Vec<String>>| {
+        witnesses
+            .into_iter()
+            .map(|rest| std::iter::once(head.clone()).chain(rest).collect())
+            .collect::<Vec<Vec<String>>>()
+    };
+    let default = || witnesses(&specialize(rows, &Space::Wild, 0), rest_columns, types);
+    match column {
+        Column::Enum(layout) => {
+            let present = variants_in(rows);
+            if present.is_empty() {
+                return prefixed(String::from(WILDCARD), default());
+            }
+            let mut result = Vec::new();
+            for (index, variant) in layout.variants.iter().enumerate() {
+                let arity = variant.fields.len();
+                if !present.contains(&index) {
+                    let fields = vec![String::from(WILDCARD); arity];
+                    result.extend(prefixed(variant_as_string(variant, &fields), default()));
+                    continue;
+                }
+                let mut field_columns = Column::fields(variant, types);
+                field_columns.extend(rest_columns.iter().copied());
+                let head = Space::Variant(index, Vec::new());
+                let rows = specialize(rows, &head, arity);
+                for mut witness in witnesses(&rows, &field_columns, types) {
+                    let rest = witness.split_off(arity);
+                    let head = variant_as_string(variant, &witness);
+                    result.push(std::iter::once(head).chain(rest).collect());
+                }
+            }
+            result
+        }
+        Column::Int(t) => {
+            let bounds = int_bounds(*t).unwrap_or_default();
+            let mut merged: Vec<(i128, i128, Vec<Vec<String>>)> = Vec::new();
+            for (low, high) in segments(rows, None, *t) {
+                let rows = specialize(rows, &Space::Int(low, high), 0);
+                let witnesses = witnesses(&rows, rest_columns, types);
+                match merged.last_mut() {
+                    Some((_, last_high, last)) if *last == witnesses => *last_high = high,
+                    _ => merged.push((low, high, witnesses)),
+                }
+            }
+            merged
+                .into_iter()
+                .flat_map(|(low, high, witnesses)| {
+                    let head = if (low, high) == bounds {
+                        String::from(WILDCARD)
+                    } else {
+                        range_as_string(low, high, *t)
+                    };
+                    prefixed(head, witnesses)
+                })
+                .collect()
+        }
+        Column::Float | Column::Other => prefixed(String::from(WILDCARD), default()),
+    }
+}
+
+/// ヴァリアントのパターンの表記`Red` `Rect(_, _)`
+fn variant_as_string(variant: &VariantLayout, fields: &[String]) -> String {
+    if variant.fields.is_empty() {
+        variant.name.clone()
+    } else {
+        format!("{}({})", variant.name, fields.join(", "))
+    }
+}
+
+/// 整数型の最小値と最大値
+fn int_bounds(t: PrimitiveType) -> Option<(i128, i128)> {
+    match t {
+        PrimitiveType::I32 => Some((i32::MIN as i128, i32::MAX as i128)),
+        PrimitiveType::I64 => Some((i64::MIN as i128, i64::MAX as i128)),
+        PrimitiveType::U32 => Some((0, u32::MAX as i128)),
+        PrimitiveType::U64 => Some((0, u64::MAX as i128)),
+        PrimitiveType::F32 | PrimitiveType::F64 => None,
+    }
+}
+
+/// 範囲が既に網羅されているかどうか
+/// `covered`は重ならず隣接しない範囲の列
+fn is_covered(covered: &[(i128, i128)], (low, high): (i128, i128)) -> bool {
+    covered.iter().any(|&(l, h)| l <= low && high <= h)
+}
+
+/// 範囲を昇順に並べ、重なるか隣接する範囲を一つにまとめる
+fn merge_ranges(mut ranges: Vec<(i128, i128)>) -> Vec<(i128, i128)> {
+    ranges.sort();
+    let mut merged: Vec<(i128, i128)> = Vec::new();
+    for (low, high) in ranges {
+        match merged.last_mut() {
+            Some((_, last_high)) if low <= *last_high + 1 => *last_high = (*last_high).max(high),
+            _ => merged.push((low, high)),
+        }
+    }
+    merged
+}
+
+/// 網羅されていない範囲の表記
+/// 型の最小値と最大値は`i32::MIN` `i32::MAX`と表記する
+fn range_as_string(low: i128, high: i128, t: PrimitiveType) -> String {
+    let name = t.get_show_as_string();
+    let (min, max) = int_bounds(t).unwrap_or_default();
+    let bound = |value: i128| {
+        if value == max {
+            format!("{}::MAX", name)
+        } else if value == min && !t.is_unsigned() {
+            format!("{}::MIN", name)
+        } else {
+            value.to_string()
+        }
+    };
+    if low == high {
+        bound(low)
+    } else {
+        format!("{}..={}", bound(low), bound(high))
+    }
+}
@@ -0,0 +1,721 @@
+use crate::abs::ast::{merge_span, ExprElem, StmtElem};
+use crate::abs::span::Span;
+use crate::errors::generate_errors::GenerateError;
+use crate::gen::pattern::{bindings_mut, Pattern};
+use crate::gen::types::{value_type, ModuleTypes, ValueType};
+use crate::parser::type_parser::TypeParser;
+
+use crate::token::decfunc::DecFuncBranch;
+use crate::token::match_syntax::MatchBranch;
+use crate::token::operator::OperatorBranch;
+use crate::token::stmt::stmt::StmtBranch;
+use crate::token::syntax::SyntaxBranch;
+use crate::token::ttype::primitive::PrimitiveType;
+use crate::token::variant::VariantBranch;
+use crate::token::word::WordBranch;
+
+/// `for`の範囲の終わりの値を保持する変数の接尾辞(`i.end`)
+pub const FOR_END_SUFFIX: &str = ".end";
+/// `for`で配列の何番目の要素かを保持する変数の接尾辞(`x.index`)
+pub const FOR_INDEX_SUFFIX: &str = ".index";
+/// `for`で反復する配列のレコードのアドレスを保持する変数の接尾辞(`x.array`)
+pub const FOR_ARRAY_SUFFIX: &str = ".array";
+
+/// `match`で検査する値を保持する隠れた変数の名前
+pub const MATCH_LOCAL: &str = "#match";
+/// 構造体のリテラルのレコードのアドレスを保持する隠れた変数の名前
+pub const STRUCT_LOCAL: &str = "#struct";
+/// ヴァリアントの値のレコードのアドレスを保持する隠れた変数の名前
+pub const ENUM_LOCAL: &str = "#enum";
+/// 配列の要素へのアクセス`a[i]`の添字を保持する隠れた変数の名前
+pub const INDEX_LOCAL: &str = "#index";
+/// 配列のリテラルのレコードのアドレスを保持する隠れた変数の名前
+/// `[0; 10]`では何番目の要素かを`#array.index`に保持する
+pub const ARRAY_LOCAL: &str = "#array";
+
+/// 二項演算子と同じ命令に展開される組み込み関数
+/// ```lichen
+/// rotl(a, 3) // -> i32.rotl
+/// ```
+pub const BUILTIN_OPERATORS: [&str; 2] = ["rotl", "rotr"];
+
+/// # Resolver
+/// 関数内の名前を解決する
+///
+/// 変数の参照は引数もしくは`let` `let_mut`で宣言されたローカル変数に、
+/// 関数呼び出しは関数宣言に対応付ける
+/// 見つからなかった場合は似た名前を候補として提示する
+/// 変数でも関数でもないヴァリアントの名前`Red` `Circle(1.0)`はヴァリアントの値に置き換える
+///
+/// wasmのローカル変数は関数全体で一つの名前空間を共有するため、
+/// 同じ名前の変数を再び宣言した場合(シャドーイング)は一意な名前に付け替える
+/// ```lichen
+/// let a = 1;
+/// if (a > 0) {
+///     let a = 2; // -> a.1
+///     a;         // -> a.1
+/// };
+/// a;             // -> a
+/// ```
+///
+/// また、代入の対象が`let_mut`で宣言された変数(もしくは引数)であることを検査する
+#[derive(Debug, Default)]
+pub struct Resolver {
+    /// スコープごとの変数の束縛
+    scopes: Vec<Vec<Binding>>,
+    /// 呼び出すことのできる関数の(名前, wasm上の関数名)
+    functions: Vec<(String, String)>,
+    /// モジュールで宣言されている型
+    types: ModuleTypes,
+    /// 宣言されたローカル変数の(一意な名前, 型注釈)
+    pub locals: Vec<(String, Option<ValueType>)>,
+    /// 再代入できない変数の(一意な名前, 宣言された位置)
+    /// `TypeChecker`でレコードを変更する関数に渡していないかを検査する
+    pub immutables: Vec<(String, Span)>,
+    /// 初期値を持たずに宣言された変数の(一意な名前, 再代入できるか, 宣言された位置)
+    /// `AssignmentChecker`で参照の前に代入されていることを検査する
+    pub uninitialized: Vec<(String, bool, Span)>,
+}
+
+/// 名前と変数の対応
+#[derive(Debug)]
+struct Binding {
+    name: String,
+    unique: String,
+    is_mutable: bool,
+    span: Span, // 宣言された位置
+}
+
+impl Resolver {
+    /// 関数の本体の名前を解決し、変数の参照を一意な名前に付け替える
+    /// `functions`はこの関数から呼び出すことのできる関数の(名前, wasm上の関数名)
+    pub fn resolve_func(
+        dec_func: &mut DecFuncBranch,
+        functions: &[(String, String)],
+        types: &ModuleTypes,
+    ) -> Result<Self, GenerateError> {
+        let mut table = Self {
+            functions: functions.to_vec(),
+            types: types.clone(),
+            ..Default::default()
+        };
+        // 引数は関数のスコープで宣言されたものとして扱う
+        // 引数には再代入できる
+        let params = dec_func
+            .args
+            .iter()
+            .map(|arg| Binding {
+                name: arg.name.clone(),
+                unique: arg.name.clone(),
+                is_mutable: true,
+                span: arg.span,
+            })
+            .collect();
+        table.scopes.push(params);
+        table.resolve_stmt_list(&mut dec_func.contents)?;
+        table.scopes.pop();
+        Ok(table)
+    }
+
+    /// 関数のwasm上の関数名
+    fn function(&self, name: &str) -> Option<String> {
+        self.functions
+            .iter()
+            .find(|(function, _)| function == name)
+            .map(|(_, wasm_name)| wasm_name.clone())
+    }
+
+    fn function_names(&self) -> Vec<String> {
+        self.functions
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Binding> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .find(|binding| binding.name == name)
+    }
+
+    /// 関数内で重複しない名前を決定する
+    fn unique_name(&self, name: &str) -> String {
+        let is_used = |candidate: &str| {
+            self.locals.iter().any(|(local, _)| local == candidate)
+                || self.scopes[0].iter().any(|param| param.unique == candidate)
+        };
+        if !is_used(name) {
+            return name.to_string();
+        }
+        let mut count = 1;
+        while is_used(&format!("{}.{}", name, count)) {
+            count += 1;
+        }
+        format!("{}.{}", name, count)
+    }
+
+    fn resolve_scope(&mut self, stmt_list: &mut [StmtElem]) -> Result<(), GenerateError> {
+        self.scopes.push(Vec::new());
+        let result = self.resolve_stmt_list(stmt_list);
+        self.scopes.pop();
+        result
+    }
+
+    fn resolve_stmt_list(&mut self, stmt_list: &mut [StmtElem]) -> Result<(), GenerateError> {
+        for inner in stmt_list {
+            match inner {
+                StmtElem::ExprElem(expr_b) => self.resolve_expr_list(&mut expr_b.code_list)?,
+                StmtElem::Special(control_b) => match &*control_b.head {
+                    "let" | "let_mut" => self.resolve_let(control_b)?,
+                    _ => self.resolve_expr_list(&mut control_b.code_list)?,
+                },
+                StmtElem::BlockElem(block_b) => self.resolve_scope(&mut block_b.contents)?,
+                _ => {
+                    // 関数の中で宣言された関数は別の関数として解決する
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `let a: i32 = expr;`
+    /// 右辺は宣言の前に解決するため、`let a = a + 1;`の右辺の`a`は外側の変数を指す
+    fn resolve_let(&mut self, control_b: &mut StmtBranch) -> Result<(), GenerateError> {
+        let span = control_b.span;
+        let (pattern, init) = let_parts_mut(&mut control_b.code_list)
+            .ok_or(GenerateError::InvalidleftPattern(span))?;
+        let has_init = init.is_some();
+        if let Some(init) = init {
+            self.resolve_expr(init)?;
+        }
+        let (name, annotation) = match pattern {
+            [ExprElem::WordElem(word_b)] => (word_b, None),
+            [ExprElem::WordElem(word_b), ExprElem::UnKnownElem(colon), annotation @ ..]
+                if colon.contents == ':' =>
+            {
+                (word_b, Some(annotation))
+            }
+            _ => return Err(GenerateError::InvalidleftPattern(span)),
+        };
+        // 型注釈のない変数の型は`TypeChecker`で推論する
+        let local_type = match annotation {
+            Some(annotation) => {
+                let type_elem = TypeParser::resolve_annotation(
+                    annotation,
+                    control_b.depth,
+                    control_b.loopdepth,
+                )
+                .map_err(|e| GenerateError::InvalidTypeError(e.get_span()))?;
+                Some(value_type(&type_elem, &self.types.names)?)
+            }
+            None => None,
+        };
+        let unique = self.unique_name(&name.contents);
+        if control_b.head == "let" {
+            self.immutables.push((unique.clone(), name.span));
+        }
+        if !has_init {
+            self.uninitialized
+                .push((unique.clone(), control_b.head == "let_mut", name.span));
+        }
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(Binding {
+                name: name.contents.clone(),
+                unique: unique.clone(),
+                is_mutable: control_b.head == "let_mut",
+                span: name.span,
+            });
+        }
+        self.locals.push((unique.clone(), local_type));
+        name.contents = unique;
+        Ok(())
+    }
+
+    fn resolve_expr_list(&mut self, expr_list: &mut [ExprElem]) -> Result<(), GenerateError> {
+        for inner in expr_list {
+            self.resolve_expr(inner)?;
+        }
+        Ok(())
+    }
+
+    fn resolve_expr(&mut self, expr: &mut ExprElem) -> Result<(), GenerateError> {
+        if let Some(variant_b) = self.as_variant(expr) {
+            *expr = ExprElem::VariantElem(variant_b);
+        }
+        match expr {
+            ExprElem::WordElem(word_b) => match self.lookup(&word_b.contents) {
+                Some(binding) => word_b.contents = binding.unique.clone(),
+                None => {
+                    return Err(GenerateError::UndefinedVariable {
+                        name: word_b.contents.clone(),
+                        span: word_b.span,
+                        suggestion: self.suggest_variable(&word_b.contents),
+                    })
+                }
+            },
+            ExprElem::FuncElem(func_b) => {
+                if is_assignment(&func_b.name) {
+                    self.check_assignment(&func_b.contents, func_b.span)?;
+                }
+                match &mut *func_b.name {
+                    // 関数名はwasm上の関数名(`outer.helper`)に付け替える
+                    ExprElem::WordElem(word_b) => {
+                        if let Some(wasm_name) = self.function(&word_b.contents) {
+                            word_b.contents = wasm_name;
+                        } else if BUILTIN_OPERATORS.contains(&word_b.contents.as_str()) {
+                            if func_b.contents.len() != 2 {
+                                return Err(GenerateError::ArgumentCountMismatch {
+                                    name: word_b.contents.clone(),
+                                    expected: 2,
+                                    found: func_b.contents.len(),
+                                    span: func_b.span,
+                                });
+                            }
+                            *func_b.name = ExprElem::OpeElem(OperatorBranch {
+                                ope: word_b.contents.clone(),
+                                depth: word_b.depth,
+                                span: word_b.span,
+                                ope_type: None,
+                            });
+                        } else {
+                            return Err(GenerateError::UndefinedFunction {
+                                name: word_b.contents.clone(),
+                                span: word_b.span,
+                                suggestion: suggest(&word_b.contents, &self.function_names()),
+                            });
+                        }
+                    }
+                    // `->`は関数の返り値の型とラムダ式のために予約されている
+                    ExprElem::OpeElem(ope_b) if ope_b.ope == "->" => {
+                        return Err(GenerateError::ReservedOperator(ope_b.span));
+                    }
+                    _ => self.resolve_expr(&mut func_b.name)?,
+                }
+                if is_cast(&func_b.name) {
+                    // `a as i64`の右辺は型の名前
+                    self.resolve_expr_list(&mut func_b.contents[..1])?;
+                } else {
+                    self.resolve_expr_list(&mut func_b.contents)?;
+                }
+            }
+            ExprElem::ItemElem(item_b) => self.resolve_expr_list(&mut item_b.contents)?,
+            ExprElem::ParenBlockElem(paren_b) => self.resolve_expr_list(&mut paren_b.contents)?,
+            ExprElem::ListBlockElem(list_block_b) => {
+                list_block_b.local = self.unique_name(ARRAY_LOCAL);
+                self.locals
+                    .push((list_block_b.local.clone(), Some(PrimitiveType::I32.into())));
+                if list_block_b.repeat.is_some() {
+                    self.locals.push((
+                        format!("{}{}", list_block_b.local, FOR_INDEX_SUFFIX),
+                        Some(PrimitiveType::I32.into()),
+                    ));
+                }
+                self.resolve_expr_list(&mut list_block_b.contents)?
+            }
+            ExprElem::ListElem(list_b) => {
+                // `__mem`はモジュール全体で共有されるメモリ
+                if !list_b.is_memory() {
+                    self.resolve_expr(&mut list_b.name)?;
+                    if list_b.const_index().is_none() {
+                        list_b.local = self.unique_name(INDEX_LOCAL);
+                        self.locals
+                            .push((list_b.local.clone(), Some(PrimitiveType::I32.into())));
+                    }
+                }
+                self.resolve_expr_list(&mut list_b.contents)?;
+            }
+            ExprElem::BlockElem(block_b) => self.resolve_scope(&mut block_b.contents)?,
+            ExprElem::SyntaxElem(syntax_b) => self.resolve_syntax(syntax_b)?,
+            ExprElem::SyntaxBoxElem(syntax_box_b) => {
+                for syntax_b in &mut syntax_box_b.contents {
+                    self.resolve_syntax(syntax_b)?;
+                }
+            }
+            ExprElem::MatchElem(match_b) => self.resolve_match(match_b)?,
+            ExprElem::StructElem(struct_b) => {
+                struct_b.local = self.unique_name(STRUCT_LOCAL);
+                self.locals
+                    .push((struct_b.local.clone(), Some(PrimitiveType::I32.into())));
+                for field in &mut struct_b.fields {
+                    self.resolve_expr_list(&mut field.value)?;
+                }
+            }
+            ExprElem::FieldElem(field_b) => self.resolve_expr(&mut field_b.base)?,
+            ExprElem::VariantElem(variant_b) => {
+                // 値を持つ列挙型のヴァリアントはレコードを確保する
+                let is_boxed = self
+                    .types
+                    .variant(&variant_b.name)
+                    .is_some_and(|(layout, _)| layout.size.is_some());
+                if is_boxed {
+                    variant_b.local = self.unique_name(ENUM_LOCAL);
+                    self.locals
+                        .push((variant_b.local.clone(), Some(PrimitiveType::I32.into())));
+                }
+                self.resolve_expr_list(&mut variant_b.args)?;
+            }
+            _ => {
+                // pass
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_syntax(&mut self, syntax_b: &mut SyntaxBranch) -> Result<(), GenerateError> {
+        if syntax_b.name == "for" {
+            return self.resolve_for(syntax_b);
+        }
+        self.resolve_expr_list(&mut syntax_b.expr)?;
+        self.resolve_scope(&mut syntax_b.contents)
+    }
+
+    /// `for (x in iterable) { ... }`
+    /// `x`はループの本体のスコープでのみ参照でき、再代入できない
+    /// 範囲の終わりの値は`x.end`、配列のアドレスと添字は`x.array` `x.index`という隠れた変数に保持する
+    fn resolve_for(&mut self, syntax_b: &mut SyntaxBranch) -> Result<(), GenerateError> {
+        let span = syntax_b.span;
+        let (binding, iterable) =
+            for_parts_mut(&mut syntax_b.expr).ok_or(GenerateError::InvalidForLoop(span))?;
+        self.resolve_expr(iterable)?;
+        let unique = self.unique_name(&binding.contents);
+        self.locals.push((unique.clone(), None));
+        if range_parts(iterable).is_some() {
+            self.locals
+                .push((format!("{}{}", unique, FOR_END_SUFFIX), None));
+        } else {
+            for suffix in [FOR_ARRAY_SUFFIX, FOR_INDEX_SUFFIX] {
+                self.locals.push((
+                    format!("{}{}", unique, suffix),
+                    Some(PrimitiveType::I32.into()),
+                ));
+            }
+        }
+        self.immutables.push((unique.clone(), binding.span));
+        self.scopes.push(vec![Binding {
+            name: binding.contents.clone(),
+            unique: unique.clone(),
+            is_mutable: false,
+            span: binding.span,
+        }]);
+        binding.contents = unique;
+        let result = self.resolve_scope(&mut syntax_b.contents);
+        self.scopes.pop();
+        result
+    }
+
+    /// 変数でも関数でもないヴァリアントの名前の場合はヴァリアントの値を返却する
+    fn as_variant(&self, expr: &ExprElem) -> Option<VariantBranch> {
+        let (word_b, args, span) = match expr {
+            ExprElem::WordElem(word_b) if self.lookup(&word_b.contents).is_none() => {
+                (word_b, Vec::new(), word_b.span)
+            }
+            ExprElem::FuncElem(func_b) => match &*func_b.name {
+                ExprElem::WordElem(word_b) if self.function(&word_b.contents).is_none() => {
+                    (word_b, func_b.contents.clone(), func_b.span)
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+        self.types.variant(&word_b.contents)?;
+        Some(VariantBranch {
+            name: word_b.contents.clone(),
+            args,
+            depth: word_b.depth,
+            loopdepth: word_b.loopdepth,
+            span,
+            local: String::new(),
+            tag: 0,
+            size: None,
+            fields: Vec::new(),
+        })
+    }
+
+    /// `match (x) { pattern => expr, ... }`
+    /// リテラルと範囲のパターンは変数を含まないため名前を解決しない
+    /// ヴァリアントのパターン`Circle(r)`で束縛した変数は、その腕でのみ参照でき、再代入できない
+    fn resolve_match(&mut self, match_b: &mut MatchBranch) -> Result<(), GenerateError> {
+        self.resolve_expr_list(&mut match_b.expr)?;
+        match_b.local = self.unique_name(MATCH_LOCAL);
+        self.locals.push((match_b.local.clone(), None));
+        for arm in &mut match_b.arms {
+            match Pattern::classify(&arm.pattern) {
+                None => return Err(GenerateError::InvalidPattern(merge_span(&arm.pattern))),
+                // 変数はパターンとして使えない
+                Some(Pattern::Variant(word_b, _)) if self.lookup(&word_b.contents).is_some() => {
+                    return Err(GenerateError::InvalidPattern(merge_span(&arm.pattern)));
+                }
+                Some(pattern) => self.check_variant_names(&pattern)?,
+            }
+            let mut scope = Vec::new();
+            for binding in bindings_mut(&mut arm.pattern, &self.types) {
+                let unique = self.unique_name(&binding.contents);
+                self.locals.push((unique.clone(), None));
+                self.immutables.push((unique.clone(), binding.span));
+                scope.push(Binding {
+                    name: binding.contents.clone(),
+                    unique: unique.clone(),
+                    is_mutable: false,
+                    span: binding.span,
+                });
+                binding.contents = unique;
+            }
+            self.scopes.push(scope);
+            let result = self.resolve_expr_list(&mut arm.contents);
+            self.scopes.pop();
+            result?;
+        }
+        Ok(())
+    }
+
+    /// パターンに書かれたヴァリアントが宣言されているかを検査する
+    /// ヴァリアントが持つ値のパターンの名前`r`はヴァリアントでなければ変数の束縛とする
+    fn check_variant_names(&self, pattern: &Pattern) -> Result<(), GenerateError> {
+        let Pattern::Variant(word_b, fields) = pattern else {
+            return Ok(());
+        };
+        if self.types.variant(&word_b.contents).is_none() {
+            return Err(GenerateError::UndefinedVariant {
+                name: word_b.contents.clone(),
+                span: word_b.span,
+                suggestion: suggest(&word_b.contents, &self.types.variant_names()),
+            });
+        }
+        fields
+            .iter()
+            .try_for_each(|field| self.check_variant_names(field))
+    }
+
+    /// 代入の左辺の変数が再代入可能かを検査する
+    /// フィールドや要素への代入`p.x = 1.0` `a[0] = 1`は`p` `a`が再代入可能でなければならない
+    /// 初期値を持たない`let`の変数への最初の代入は`AssignmentChecker`で検査する
+    /// `__mem[...]`などの変数ではない左辺は検査しない
+    fn check_assignment(&self, contents: &[ExprElem], span: Span) -> Result<(), GenerateError> {
+        let Some(ExprElem::ItemElem(left)) = contents.first() else {
+            return Ok(());
+        };
+        let (place, word_b) = match &left.contents[..] {
+            [place] => match place_root(place) {
+                Some(word_b) => (place, word_b),
+                None => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        let is_deferred = |binding: &Binding| {
+            matches!(place, ExprElem::WordElem(_))
+                && self
+                    .uninitialized
+                    .iter()
+                    .any(|(unique, _, _)| *unique == binding.unique)
+        };
+        match self.lookup(&word_b.contents) {
+            Some(binding) if binding.is_mutable || is_deferred(binding) => Ok(()),
+            Some(binding) => Err(match place {
+                ExprElem::WordElem(_) => GenerateError::AssignToImmutable {
+                    name: word_b.contents.clone(),
+                    span,
+                    declared: binding.span,
+                },
+                _ => GenerateError::AssignToImmutablePlace {
+                    name: word_b.contents.clone(),
+                    place: match place {
+                        ExprElem::FieldElem(_) => "field",
+                        _ => "element",
+                    },
+                    span,
+                    declared: binding.span,
+                },
+            }),
+            None => Err(GenerateError::UndefinedVariable {
+                name: word_b.contents.clone(),
+                span: word_b.span,
+                suggestion: self.suggest_variable(&word_b.contents),
+            }),
+        }
+    }
+
+    /// 現在のスコープから見える変数の中で最も似た名前
+    fn suggest_variable(&self, name: &str) -> Option<String> {
+        let candidates: Vec<String> = self
+            .scopes
+            .iter()
+            .flatten()
+            .map(|binding| binding.name.clone())
+            .collect();
+        suggest(name, &candidates)
+    }
+}
+
+/// `p.a.x` `a[0][1]`の`p` `a`
+/// 関数の返り値などのフィールドや`__mem`の要素の場合は`None`を返却する
+pub fn place_root(expr: &ExprElem) -> Option<&WordBranch> {
+    match expr {
+        ExprElem::WordElem(word_b) => Some(word_b),
+        ExprElem::FieldElem(field_b) => place_root(&field_b.base),
+        ExprElem::ListElem(list_b) if !list_b.is_memory() => place_root(&list_b.name),
+        _ => None,
+    }
+}
+
+/// 候補の中から`name`に最も近い名前を返す
+/// 編集距離が名前の長さの1/3(最低1)を超えるものは候補にしない
+/// 1文字の名前はどの1文字の名前とも距離が1になるため、名前の長さ未満に限る
+pub fn suggest(name: &str, candidates: &[String]) -> Option<String> {
+    let length = name.chars().count();
+    let limit = (length / 3).max(1);
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit && *distance < length)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.clone())
+}
+
+/// 制限付きダメラウ・レーベンシュタイン距離(最適文字列アラインメント)
+/// 隣り合う2文字の入れ替え`cuont` -> `count`も1回の編集とする
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // 二つ前の行と一つ前の行
+    let mut prev2: Vec<usize> = Vec::new();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (prev[j] + 1).min(row[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(prev2[j - 2] + 1);
+            }
+        }
+        prev2 = std::mem::replace(&mut prev, row);
+    }
+    prev[b.len()]
+}
+
+/// `let`の`code_list`を左辺のパターンと初期化式に分ける
+/// ```lichen
+/// let a: i32 = 1;
+/// //  ^^^^^^   ^
+/// let b;
+/// //  ^
+/// ```
+pub fn let_parts(code_list: &[ExprElem]) -> Option<(&[ExprElem], Option<&ExprElem>)> {
+    match code_list {
+        [ExprElem::FuncElem(func_b)] if is_initialization(&func_b.name) => {
+            match &func_b.contents[..] {
+                [ExprElem::ItemElem(left), right] => Some((&left.contents, Some(right))),
+                _ => None,
+            }
+        }
+        _ if !code_list.is_empty() => Some((code_list, None)),
+        _ => None,
+    }
+}
+
+/// `let_parts`の可変参照版
+pub fn let_parts_mut(
+    code_list: &mut [ExprElem],
+) -> Option<(&mut [ExprElem], Option<&mut ExprElem>)> {
+    let has_init =
+        matches!(code_list, [ExprElem::FuncElem(func_b)] if is_initialization(&func_b.name));
+    if !has_init {
+        return (!code_list.is_empty()).then_some((code_list, None));
+    }
+    match code_list {
+        [ExprElem::FuncElem(func_b)] => match &mut func_b.contents[..] {
+            [ExprElem::ItemElem(left), right] => Some((&mut left.contents, Some(right))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `for (x in iterable)`の束縛する変数と反復の対象
+pub fn for_parts(expr: &[ExprElem]) -> Option<(&WordBranch, &ExprElem)> {
+    let [ExprElem::FuncElem(func_b)] = expr else {
+        return None;
+    };
+    if !matches!(&*func_b.name, ExprElem::OpeElem(ope_b) if ope_b.ope == "in") {
+        return None;
+    }
+    match &func_b.contents[..] {
+        [ExprElem::ItemElem(left), iterable] => match &left.contents[..] {
+            [ExprElem::WordElem(word_b)] => Some((word_b, iterable)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `for_parts`の可変参照版
+pub fn for_parts_mut(expr: &mut [ExprElem]) -> Option<(&mut WordBranch, &mut ExprElem)> {
+    let [ExprElem::FuncElem(func_b)] = expr else {
+        return None;
+    };
+    if !matches!(&*func_b.name, ExprElem::OpeElem(ope_b) if ope_b.ope == "in") {
+        return None;
+    }
+    match &mut func_b.contents[..] {
+        [ExprElem::ItemElem(left), iterable] => match &mut left.contents[..] {
+            [ExprElem::WordElem(word_b)] => Some((word_b, iterable)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 範囲`a..b` `a..=b`の演算子と両端
+pub fn range_parts(expr: &ExprElem) -> Option<(&OperatorBranch, &ExprElem, &ExprElem)> {
+    match expr {
+        ExprElem::ItemElem(item_b) if item_b.contents.len() == 1 => {
+            range_parts(&item_b.contents[0])
+        }
+        ExprElem::FuncElem(func_b) => match (&*func_b.name, &func_b.contents[..]) {
+            (ExprElem::OpeElem(ope_b), [start, end]) if is_range(ope_b) => {
+                Some((ope_b, start, end))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `range_parts`の可変参照版
+pub fn range_parts_mut(
+    expr: &mut ExprElem,
+) -> Option<(&mut OperatorBranch, &mut ExprElem, &mut ExprElem)> {
+    match expr {
+        ExprElem::ItemElem(item_b) if item_b.contents.len() == 1 => {
+            range_parts_mut(&mut item_b.contents[0])
+        }
+        ExprElem::FuncElem(func_b) => match (&mut *func_b.name, &mut func_b.contents[..]) {
+            (ExprElem::OpeElem(ope_b), [start, end]) if is_range(ope_b) => {
+                Some((ope_b, start, end))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_range(ope_b: &OperatorBranch) -> bool {
+    matches!(&*ope_b.ope, ".." | "..=")
+}
+
+/// `let`の初期化式
+fn is_initialization(name: &ExprElem) -> bool {
+    matches!(name, ExprElem::OpeElem(ope_b) if ope_b.ope == "=")
+}
+
+/// `as`による型変換かどうか
+pub fn is_cast(name: &ExprElem) -> bool {
+    matches!(name, ExprElem::OpeElem(ope_b) if ope_b.ope == "as")
+}
+
+/// 代入と複合代入
+fn is_assignment(name: &ExprElem) -> bool {
+    matches!(name, ExprElem::OpeElem(ope_b) if ope_b.is_assignment())
+}
@@ -0,0 +1,1187 @@
+use crate::abs::ast::{merge_span, ExprElem, StmtElem, Token};
+use crate::abs::span::Span;
+use crate::errors::generate_errors::GenerateError;
+use crate::errors::generate_warnings::GenerateWarning;
+use crate::gen::module::cast_type;
+use crate::gen::pattern::{Coverage, Pattern};
+use crate::gen::resolver::{
+    for_parts_mut, let_parts_mut, place_root, range_parts, suggest, Resolver, FOR_END_SUFFIX,
+};
+use crate::gen::types::{check_array_size, ModuleTypes, ValueType};
+use crate::gen::wasm::{const_value, copy_func_name, pow_const};
+
+use crate::token::decfunc::DecFuncBranch;
+use crate::token::field::FieldBranch;
+use crate::token::func::FuncBranch;
+use crate::token::item::ItemBranch;
+use crate::token::list::ListBranch;
+use crate::token::list_block::ListBlockBranch;
+use crate::token::match_syntax::{FieldTest, FieldTestKind, MatchBranch};
+use crate::token::num::{NumBranch, NumValue};
+use crate::token::operator::OperatorBranch;
+use crate::token::stmt::stmt::StmtBranch;
+use crate::token::structure::StructBranch;
+use crate::token::syntax::SyntaxBranch;
+use crate::token::ttype::primitive::PrimitiveType;
+use crate::token::variant::VariantBranch;
+use crate::token::word::WordBranch;
+
+/// `@`の実装となる関数の名前の接頭辞
+/// 左辺の型が`f64`の場合は`dot_f64`を呼び出す
+pub const DOT_FUNC_PREFIX: &str = "dot_";
+
+/// 関数のシグネチャ
+#[derive(Clone, Debug)]
+pub struct FuncSignature {
+    pub name: String,
+    pub params: Vec<ValueType>,
+    pub result: Option<ValueType>,
+}
+
+/// 関数の検査で集めた、レコードの複製と引数の変更に関する情報
+/// 呼び出した関数が引数を変更するかどうかは、全ての関数を検査した後に決定する
+#[derive(Clone, Debug, Default)]
+pub struct FuncEffects {
+    /// 複製の補助関数を使用するレコードの型
+    pub copies: Vec<ValueType>,
+    /// フィールドや要素に代入する引数の位置
+    pub mutated: Vec<usize>,
+    /// 引数をそのまま別の関数に渡した呼び出しの(引数の位置, wasm上の関数名, 渡した引数の位置)
+    pub forwarded: Vec<(usize, String, usize)>,
+    /// 再代入できない変数をそのまま関数に渡した呼び出し
+    pub borrowed: Vec<BorrowedArgument>,
+}
+
+/// 再代入できない変数(のフィールドや要素)を引数として渡した箇所
+#[derive(Clone, Debug)]
+pub struct BorrowedArgument {
+    pub name: String,
+    /// wasm上の関数名
+    pub function: String,
+    /// 渡した引数の位置
+    pub index: usize,
+    pub span: Span,
+    /// 変数の宣言
+    pub declared: Span,
+}
+
+/// ヴァリアントのパターンで決定した値の検査と変数の束縛の格納先
+type CheckedPattern<'a> = (
+    &'a mut Vec<FieldTest>,
+    &'a mut Vec<(String, Vec<u32>, PrimitiveType)>,
+);
+
+/// # TypeChecker
+/// 関数の本体の式の型を推論し、型の不一致を検査する
+///
+/// 型は`Option<ValueType>`で表し、`None`は値を持たないこと(`()`)を表す
+/// 接尾辞のない数値リテラルは期待される型に合わせて型が決まる
+/// ```lichen
+/// let a: i64 = 1;  // 1: i64
+/// let b = 1;       // 1: i32
+/// let c = 1.5;     // 1.5: f64
+/// let d = a + 2;   // 2: i64
+/// let e = 2i64;    // 2i64: i64
+/// ```
+/// 数値リテラルの値が決定した型の範囲に収まらない場合はエラーになる
+/// `Resolver`で名前を解決した後の関数に対して実行する
+/// 決定した数値リテラルと演算子の型はそれぞれ`NumBranch::num_type`と
+/// `OperatorBranch::ope_type`に書き込まれ、命令の選択に使用される
+///
+/// 構造体と配列の値は束縛・代入・格納・返却のたびに複製し、二つの変数が同じレコードを共有しないようにする
+/// 引数は複製せずに渡すため、呼び出された関数は引数のフィールドや要素を変更できる
+/// ```lichen
+/// let p = Point { x: 0.0, y: 0.0 };
+/// let_mut q = p;  // -> let_mut q = __copy.Point(p);
+/// q.x = 1.0;      // p.xは0.0のまま
+/// move_x(q, 1.0); // q.xを変更する関数には`let_mut`で宣言した変数のみ渡せる
+/// ```
+pub struct TypeChecker<'a> {
+    functions: &'a [FuncSignature],
+    types: &'a ModuleTypes,
+    return_type: Option<ValueType>,
+    /// ローカル変数と引数の型
+    /// 型注釈がなく、まだ値が代入されていない変数は`None`
+    locals: Vec<(String, Option<ValueType>)>,
+    /// 引数の名前
+    params: Vec<String>,
+    /// 再代入できない変数の(一意な名前, 宣言された位置)
+    immutables: &'a [(String, Span)],
+    /// 初期値を持たずに宣言された変数の(一意な名前, 再代入できるか, 宣言された位置)
+    uninitialized: &'a [(String, bool, Span)],
+    effects: FuncEffects,
+    warnings: &'a mut Vec<GenerateWarning>,
+}
+
+impl<'a> TypeChecker<'a> {
+    /// 関数を検査し、ローカル変数のwasm上の型とレコードに関する情報を返却する
+    /// 最後まで型の決まらなかった変数は`i32`とする
+    /// 検査中に見つかった警告は`warnings`に追加する
+    pub fn check_func(
+        dec_func: &mut DecFuncBranch,
+        wasm_name: &str,
+        resolver: &'a Resolver,
+        functions: &'a [FuncSignature],
+        types: &'a ModuleTypes,
+        warnings: &'a mut Vec<GenerateWarning>,
+    ) -> Result<(Vec<(String, PrimitiveType)>, FuncEffects), GenerateError> {
+        let signature = functions
+            .iter()
+            .find(|f| f.name == wasm_name)
+            .ok_or(GenerateError::Deverror)?;
+        let mut checker = Self {
+            functions,
+            types,
+            return_type: signature.result.clone(),
+            locals: Vec::new(),
+            params: dec_func.args.iter().map(|arg| arg.name.clone()).collect(),
+            immutables: &resolver.immutables,
+            uninitialized: &resolver.uninitialized,
+            effects: FuncEffects::default(),
+            warnings,
+        };
+        for (arg, param) in dec_func.args.iter().zip(&signature.params) {
+            checker.locals.push((arg.name.clone(), Some(param.clone())));
+        }
+        checker.locals.extend_from_slice(&resolver.locals);
+        checker.check_stmt_list(&mut dec_func.contents)?;
+        let locals = checker
+            .locals
+            .into_iter()
+            .skip(dec_func.args.len())
+            .map(|(name, t)| (name, t.map_or(PrimitiveType::I32, |t| t.wasm_type())))
+            .collect();
+        Ok((locals, checker.effects))
+    }
+
+    fn local_type(&self, name: &str) -> Option<ValueType> {
+        self.locals
+            .iter()
+            .find(|(local, _)| local == name)
+            .and_then(|(_, t)| t.clone())
+    }
+
+    /// 型注釈がなく、まだ値が代入されていない変数
+    fn is_unassigned(&self, name: &str) -> bool {
+        self.locals
+            .iter()
+            .any(|(local, t)| local == name && t.is_none())
+    }
+
+    /// 型の決まっていない変数の型を決定する
+    fn set_local_type(&mut self, name: &str, t: Option<ValueType>) {
+        if let Some((_, local_type)) = self.locals.iter_mut().find(|(local, _)| local == name) {
+            if local_type.is_none() {
+                *local_type = t;
+            }
+        }
+    }
+
+    fn check_stmt_list(&mut self, stmt_list: &mut [StmtElem]) -> Result<(), GenerateError> {
+        for inner in stmt_list {
+            match inner {
+                StmtElem::ExprElem(expr_b) => {
+                    for e in &mut expr_b.code_list {
+                        self.infer(e, None)?;
+                    }
+                }
+                StmtElem::Special(control_b) => self.check_control(control_b)?,
+                StmtElem::BlockElem(block_b) => self.check_stmt_list(&mut block_b.contents)?,
+                _ => {
+                    // 関数の中で宣言された関数は別の関数として検査する
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_control(&mut self, control_b: &mut StmtBranch) -> Result<(), GenerateError> {
+        match &*control_b.head {
+            "let" | "let_mut" => {
+                let span = control_b.span;
+                let (pattern, init) = let_parts_mut(&mut control_b.code_list)
+                    .ok_or(GenerateError::InvalidleftPattern(span))?;
+                let Some(ExprElem::WordElem(name)) = pattern.first() else {
+                    return Err(GenerateError::InvalidleftPattern(span));
+                };
+                let name = name.contents.clone();
+                if let Some(init) = init {
+                    let annotation = self.local_type(&name);
+                    let init_type = self.infer(init, annotation.clone())?;
+                    if annotation.is_some() {
+                        expect_type(&annotation, &init_type, init.get_span())?;
+                    }
+                    self.copy_record(init, &init_type);
+                    if annotation.is_none() {
+                        self.set_local_type(&name, init_type);
+                    }
+                }
+            }
+            "return" => {
+                let (value_type, span) = match &mut control_b.code_list[..] {
+                    [] => (None, control_b.span),
+                    [value] => {
+                        let t = self.infer(value, self.return_type.clone())?;
+                        self.copy_record(value, &t);
+                        (t, value.get_span())
+                    }
+                    _ => return Err(GenerateError::Deverror),
+                };
+                expect_type(&self.return_type, &value_type, span)?;
+            }
+            _ => {
+                for e in &mut control_b.code_list {
+                    self.infer(e, None)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 条件式は`i32`でなければならない
+    fn check_syntax(&mut self, syntax_b: &mut SyntaxBranch) -> Result<(), GenerateError> {
+        if syntax_b.name == "for" {
+            return self.check_for(syntax_b);
+        }
+        if let [condition] = &mut syntax_b.expr[..] {
+            let i32_type = Some(PrimitiveType::I32.into());
+            let t = self.infer(condition, i32_type.clone())?;
+            expect_type(&i32_type, &t, condition.get_span())?;
+        }
+        self.check_stmt_list(&mut syntax_b.contents)
+    }
+
+    /// 範囲の両端は同じ整数型、配列の要素は同じ型でなければならない
+    /// 束縛する変数の型は範囲の型、または配列の要素の型になる
+    fn check_for(&mut self, syntax_b: &mut SyntaxBranch) -> Result<(), GenerateError> {
+        let span = syntax_b.span;
+        let (binding, iterable) =
+            for_parts_mut(&mut syntax_b.expr).ok_or(GenerateError::InvalidForLoop(span))?;
+        let name = binding.contents.clone();
+        if range_parts(iterable).is_some() {
+            let t = self.infer(iterable, None)?;
+            self.set_local_type(&name, t.clone());
+            self.set_local_type(&format!("{}{}", name, FOR_END_SUFFIX), t);
+        } else {
+            let t = self.infer(iterable, None)?;
+            let Some(ValueType::Array(element_type, length)) = t else {
+                return Err(GenerateError::NotIterable {
+                    found: type_name(&t),
+                    span: iterable.get_span(),
+                });
+            };
+            syntax_b.iter_array = Some((element_type.wasm_type(), element_type.size(), length));
+            self.set_local_type(&name, Some(*element_type));
+        }
+        self.check_stmt_list(&mut syntax_b.contents)
+    }
+
+    /// パターンは検査する値と同じ型でなければならない
+    /// 列挙型の値はその列挙型のヴァリアントのパターンで検査する
+    /// 全ての腕の値の型が`match`全体の型になる
+    /// 腕が検査する値の全てを網羅していない場合はエラー、到達しない腕は警告とする
+    fn check_match(
+        &mut self,
+        match_b: &mut MatchBranch,
+        expected: Option<ValueType>,
+    ) -> Result<Option<ValueType>, GenerateError> {
+        let [expr] = &mut match_b.expr[..] else {
+            return Err(GenerateError::EmptyCondition(match_b.span));
+        };
+        let expr_span = expr.get_span();
+        let expr_type = self.infer(expr, None)?;
+        let types = self.types;
+        let enum_layout = match &expr_type {
+            Some(ValueType::Enum(name)) => types.enum_layout(name),
+            _ => None,
+        };
+        // 列挙型の値はタグで分岐する
+        let t = match (&expr_type, enum_layout) {
+            (_, Some(_)) => PrimitiveType::I32,
+            (Some(ValueType::Primitive(t)), None) => *t,
+            _ => {
+                return Err(GenerateError::InvalidOperandType {
+                    ope: String::from("match"),
+                    found: type_name(&expr_type),
+                    span: expr_span,
+                })
+            }
+        };
+        self.set_local_type(&match_b.local, expr_type.clone());
+
+        let mut result_type = None;
+        for (i, arm) in match_b.arms.iter_mut().enumerate() {
+            match Pattern::classify(&arm.pattern) {
+                Some(Pattern::Wildcard) => {}
+                Some(Pattern::Variant(word_b, fields)) => {
+                    let span = merge_span(&arm.pattern);
+                    let mut tests = Vec::new();
+                    let mut bindings = Vec::new();
+                    let tag = self.check_variant_pattern(
+                        word_b,
+                        &fields,
+                        &expr_type,
+                        &[],
+                        (&mut tests, &mut bindings),
+                        span,
+                    )?;
+                    arm.tag = Some(tag);
+                    arm.tests = tests;
+                    arm.bindings = bindings;
+                }
+                _ => {
+                    let [pattern] = &mut arm.pattern[..] else {
+                        return Err(GenerateError::Deverror);
+                    };
+                    let t = self.infer(pattern, expr_type.clone())?;
+                    expect_type(&expr_type, &t, pattern.get_span())?;
+                    if let Some(pattern) = Pattern::classify(&arm.pattern) {
+                        pattern.check_range()?;
+                    }
+                }
+            }
+            let [contents] = &mut arm.contents[..] else {
+                return Err(GenerateError::Deverror);
+            };
+            let arm_expected = if i == 0 {
+                expected.clone()
+            } else {
+                result_type.clone()
+            };
+            let t = self.infer(contents, arm_expected)?;
+            if i == 0 {
+                result_type = t;
+            } else {
+                expect_type(&result_type, &t, contents.get_span())?;
+            }
+        }
+        let patterns = match_b
+            .arms
+            .iter()
+            .map(|arm| Pattern::classify(&arm.pattern).ok_or(GenerateError::Deverror))
+            .collect::<Result<Vec<_>, _>>()?;
+        let coverage = match enum_layout {
+            Some(layout) => Coverage::check_variants(&patterns, layout, types),
+            None => Coverage::check(&patterns, t),
+        };
+        if !coverage.missing.is_empty() {
+            return Err(GenerateError::NonExhaustivePatterns {
+                missing: coverage.missing,
+                span: expr_span,
+            });
+        }
+        for index in coverage.unreachable {
+            self.warnings.push(GenerateWarning::UnreachablePattern(
+                match_b.arms[index].span,
+            ));
+        }
+        match_b.expr_type = Some(t);
+        match_b.result_type = result_type.as_ref().map(ValueType::wasm_type);
+        match_b.boxed = enum_layout.is_some_and(|layout| layout.size.is_some());
+        Ok(result_type)
+    }
+
+    /// ヴァリアントのパターンは検査する列挙型のヴァリアントでなければならない
+    /// 値のパターンの数はヴァリアントが持つ値の数と一致しなければならない
+    /// `path`は検査する値を読み出すオフセットの列で、`match`で検査する値の場合は空
+    fn check_variant_pattern(
+        &mut self,
+        word_b: &WordBranch,
+        fields: &[Pattern],
+        expected: &Option<ValueType>,
+        path: &[u32],
+        checked: CheckedPattern,
+        span: Span,
+    ) -> Result<u32, GenerateError> {
+        let types = self.types;
+        let layout = match expected {
+            Some(ValueType::Enum(name)) => types.enum_layout(name),
+            _ => None,
+        };
+        let name = &word_b.contents;
+        let Some((layout, variant)) =
+            layout.and_then(|layout| layout.variant(name).map(|variant| (layout, variant)))
+        else {
+            let found = types
+                .variant(name)
+                .map(|(layout, _)| layout.name.clone())
+                .ok_or(GenerateError::Deverror)?;
+            return Err(GenerateError::TypeMismatch {
+                expected: type_name(expected),
+                found,
+                span,
+            });
+        };
+        if fields.len() != variant.fields.len() {
+            return Err(GenerateError::ArgumentCountMismatch {
+                name: name.to_string(),
+                expected: variant.fields.len(),
+                found: fields.len(),
+                span,
+            });
+        }
+        let (tests, bindings) = checked;
+        if !path.is_empty() {
+            // 値を持つ列挙型の値はレコードの先頭のタグを読み出す
+            let mut tag_path = path.to_vec();
+            if layout.size.is_some() {
+                tag_path.push(0);
+            }
+            tests.push(FieldTest {
+                path: tag_path,
+                value_type: PrimitiveType::I32,
+                kind: FieldTestKind::Tag(variant.tag),
+            });
+        }
+        for (pattern, field) in fields.iter().zip(&variant.fields) {
+            let mut field_path = path.to_vec();
+            field_path.push(field.offset);
+            self.check_field_pattern(
+                pattern,
+                &field.field_type,
+                field_path,
+                (&mut *tests, &mut *bindings),
+            )?;
+        }
+        Ok(variant.tag)
+    }
+
+    /// ヴァリアントが持つ値のパターンは値の型に一致しなければならない
+    /// 変数の束縛は値の型の変数になる
+    fn check_field_pattern(
+        &mut self,
+        pattern: &Pattern,
+        field_type: &ValueType,
+        path: Vec<u32>,
+        checked: CheckedPattern,
+    ) -> Result<(), GenerateError> {
+        let expected = Some(field_type.clone());
+        let (tests, bindings) = checked;
+        let kind = match pattern {
+            Pattern::Wildcard => return Ok(()),
+            Pattern::Name(word_b) if self.types.variant(&word_b.contents).is_none() => {
+                self.set_local_type(&word_b.contents, expected);
+                bindings.push((word_b.contents.clone(), path, field_type.wasm_type()));
+                return Ok(());
+            }
+            Pattern::Name(word_b) => {
+                let checked = (tests, bindings);
+                self.check_variant_pattern(word_b, &[], &expected, &path, checked, word_b.span)?;
+                return Ok(());
+            }
+            Pattern::Variant(word_b, fields) => {
+                let checked = (tests, bindings);
+                self.check_variant_pattern(word_b, fields, &expected, &path, checked, word_b.span)?;
+                return Ok(());
+            }
+            Pattern::Literal(literal) => {
+                FieldTestKind::Literal(self.check_literal(literal, &expected)?)
+            }
+            Pattern::Range(ope_b, start, end) => {
+                let kind = FieldTestKind::Range(
+                    ope_b.ope.clone(),
+                    self.check_literal(start, &expected)?,
+                    self.check_literal(end, &expected)?,
+                );
+                pattern.check_range()?;
+                kind
+            }
+        };
+        tests.push(FieldTest {
+            path,
+            value_type: field_type.wasm_type(),
+            kind,
+        });
+        Ok(())
+    }
+
+    /// パターンの数値リテラルは検査する値の型でなければならない
+    /// 型を決定したリテラルを返却する
+    fn check_literal(
+        &mut self,
+        literal: &ExprElem,
+        expected: &Option<ValueType>,
+    ) -> Result<ExprElem, GenerateError> {
+        let mut literal = literal.clone();
+        let t = self.infer(&mut literal, expected.clone())?;
+        expect_type(expected, &t, literal.get_span())?;
+        Ok(literal)
+    }
+
+    /// ヴァリアントの値はヴァリアントが宣言した数と型の値を持たなければならない
+    fn check_variant(
+        &mut self,
+        variant_b: &mut VariantBranch,
+    ) -> Result<Option<ValueType>, GenerateError> {
+        let types = self.types;
+        let (layout, variant) = types
+            .variant(&variant_b.name)
+            .ok_or(GenerateError::Deverror)?;
+        if variant_b.args.len() != variant.fields.len() {
+            return Err(GenerateError::ArgumentCountMismatch {
+                name: variant_b.name.clone(),
+                expected: variant.fields.len(),
+                found: variant_b.args.len(),
+                span: variant_b.span,
+            });
+        }
+        let mut fields = Vec::new();
+        for (arg, field) in variant_b.args.iter_mut().zip(&variant.fields) {
+            let expected = Some(field.field_type.clone());
+            let t = self.infer(arg, expected.clone())?;
+            expect_type(&expected, &t, arg.get_span())?;
+            self.copy_record(arg, &t);
+            fields.push((field.offset, field.field_type.wasm_type()));
+        }
+        variant_b.tag = variant.tag;
+        variant_b.size = layout.size;
+        variant_b.fields = fields;
+        Ok(Some(ValueType::Enum(layout.name.clone())))
+    }
+
+    /// 全てのフィールドに一度ずつ、フィールドの型の値を指定しなければならない
+    fn check_struct(
+        &mut self,
+        struct_b: &mut StructBranch,
+    ) -> Result<Option<ValueType>, GenerateError> {
+        let types = self.types;
+        let layout =
+            types
+                .struct_layout(&struct_b.name)
+                .ok_or_else(|| GenerateError::UndefinedType {
+                    name: struct_b.name.clone(),
+                    span: struct_b.span,
+                    suggestion: suggest(&struct_b.name, &types.names.structs),
+                })?;
+        let mut seen: Vec<String> = Vec::new();
+        for field in &mut struct_b.fields {
+            let Some(field_layout) = layout.field(&field.name) else {
+                return Err(GenerateError::UndefinedField {
+                    ty: layout.name.clone(),
+                    field: field.name.clone(),
+                    span: field.span,
+                    suggestion: layout.suggest_field(&field.name),
+                });
+            };
+            if seen.contains(&field.name) {
+                return Err(GenerateError::DuplicateField {
+                    name: field.name.clone(),
+                    span: field.span,
+                });
+            }
+            seen.push(field.name.clone());
+            let [value] = &mut field.value[..] else {
+                return Err(GenerateError::Deverror);
+            };
+            let expected = Some(field_layout.field_type.clone());
+            let t = self.infer(value, expected.clone())?;
+            expect_type(&expected, &t, value.get_span())?;
+            self.copy_record(value, &t);
+            field.offset = field_layout.offset;
+            field.field_type = Some(field_layout.field_type.wasm_type());
+        }
+        let missing: Vec<String> = layout
+            .fields
+            .iter()
+            .filter(|f| !struct_b.fields.iter().any(|init| init.name == f.name))
+            .map(|f| f.name.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Err(GenerateError::MissingFields {
+                name: layout.name.clone(),
+                missing,
+                span: struct_b.span,
+            });
+        }
+        struct_b.size = layout.size;
+        Ok(Some(ValueType::Struct(layout.name.clone())))
+    }
+
+    /// `p.x`の`p`は構造体でなければならない
+    fn check_field(
+        &mut self,
+        field_b: &mut FieldBranch,
+    ) -> Result<Option<ValueType>, GenerateError> {
+        let base_type = self.infer(&mut field_b.base, None)?;
+        let layout = match &base_type {
+            Some(ValueType::Struct(name)) => self.types.struct_layout(name),
+            _ => None,
+        };
+        let field_layout = layout.and_then(|layout| layout.field(&field_b.name));
+        let Some(field_layout) = field_layout else {
+            return Err(GenerateError::UndefinedField {
+                ty: type_name(&base_type),
+                field: field_b.name.clone(),
+                span: field_b.span,
+                suggestion: layout.and_then(|layout| layout.suggest_field(&field_b.name)),
+            });
+        };
+        field_b.offset = field_layout.offset;
+        field_b.field_type = Some(field_layout.field_type.wasm_type());
+        Ok(Some(field_layout.field_type.clone()))
+    }
+
+    /// `a[i]`の`a`は配列、添字は`i32`でなければならない
+    /// `__mem[i]`は線形メモリの`i`番地の`i32`の値になる
+    fn check_index(&mut self, list_b: &mut ListBranch) -> Result<Option<ValueType>, GenerateError> {
+        let i32_type = Some(PrimitiveType::I32.into());
+        if list_b.is_memory() {
+            for index in &mut list_b.contents {
+                let t = self.infer(index, i32_type.clone())?;
+                expect_type(&i32_type, &t, index.get_span())?;
+            }
+            return Ok(i32_type);
+        }
+        let base_type = self.infer(&mut list_b.name, None)?;
+        let Some(ValueType::Array(element, length)) = base_type else {
+            return Err(GenerateError::NotIndexable {
+                found: type_name(&base_type),
+                span: list_b.name.get_span(),
+            });
+        };
+        let [index] = &mut list_b.contents[..] else {
+            return Err(GenerateError::UnsupportedListAccess(list_b.span));
+        };
+        let t = self.infer(index, i32_type.clone())?;
+        expect_type(&i32_type, &t, index.get_span())?;
+        if let Some(n) = list_b.const_index().filter(|n| *n >= length as u128) {
+            return Err(GenerateError::IndexOutOfBounds {
+                index: n,
+                length,
+                span: list_b.contents[0].get_span(),
+            });
+        }
+        list_b.element_type = Some(element.wasm_type());
+        list_b.element_size = element.size();
+        list_b.length = length;
+        Ok(Some(*element))
+    }
+
+    /// 配列のリテラルの要素は全て同じ型でなければならない
+    /// 要素の型は期待される配列の型、もしくは最初の要素の型になる
+    fn check_array(
+        &mut self,
+        list_block_b: &mut ListBlockBranch,
+        expected: Option<ValueType>,
+    ) -> Result<Option<ValueType>, GenerateError> {
+        let mut element_type = match expected {
+            Some(ValueType::Array(element, _)) => Some(*element),
+            _ => None,
+        };
+        for element in &mut list_block_b.contents {
+            let t = self.infer(element, element_type.clone())?;
+            if element_type.is_none() {
+                element_type = t.clone();
+            } else {
+                expect_type(&element_type, &t, element.get_span())?;
+            }
+            self.copy_record(element, &t);
+        }
+        let Some(element_type) = element_type else {
+            return Err(GenerateError::UnknownArrayType(list_block_b.span));
+        };
+        check_array_size(&element_type, list_block_b.len() as u32, list_block_b.span)?;
+        list_block_b.element_type = Some(element_type.wasm_type());
+        list_block_b.element_size = element_type.size();
+        Ok(Some(ValueType::Array(
+            Box::new(element_type),
+            list_block_b.len() as u32,
+        )))
+    }
+
+    /// 式の型を推論する
+    /// `expected`は数値リテラルの型を決めるためにのみ使用する
+    fn infer(
+        &mut self,
+        expr: &mut ExprElem,
+        expected: Option<ValueType>,
+    ) -> Result<Option<ValueType>, GenerateError> {
+        match expr {
+            ExprElem::WordElem(word_b) => {
+                let name = &word_b.contents;
+                if self.is_unassigned(name) {
+                    // 参照の前に代入されていることは`AssignmentChecker`で検査済みだが、
+                    // 型の決まる代入よりも前に評価される位置で参照している場合
+                    let declared = self
+                        .uninitialized
+                        .iter()
+                        .find(|(local, _, _)| local == name)
+                        .map_or(word_b.span, |(_, _, declared)| *declared);
+                    // 宣言時の名前(`x.1`の`x`)で報告する
+                    return Err(GenerateError::UsedBeforeAssignment {
+                        name: name.split('.').next().unwrap_or(name).to_string(),
+                        span: word_b.span,
+                        declared,
+                    });
+                }
+                Ok(self.local_type(name))
+            }
+            ExprElem::NumElem(num_b) => Ok(Some(check_num(num_b, &expected, false)?.into())),
+            ExprElem::ItemElem(item_b) => match &mut item_b.contents[..] {
+                [] => Ok(None),
+                [inner] => self.infer(inner, expected),
+                _ => Err(GenerateError::Deverror),
+            },
+            ExprElem::ParenBlockElem(paren_b) => match &mut paren_b.contents[..] {
+                [] => Ok(None),
+                [inner] => self.infer(inner, expected),
+                _ => Err(GenerateError::Deverror),
+            },
+            ExprElem::FuncElem(func_b) => match &mut *func_b.name {
+                ExprElem::OpeElem(ope_b) if ope_b.ope == "@" => self.infer_dot(func_b),
+                ExprElem::OpeElem(ope_b) => {
+                    self.infer_operation(&mut func_b.contents, ope_b, func_b.span, expected)
+                }
+                ExprElem::WordElem(word_b) => {
+                    self.infer_call(&mut func_b.contents, word_b, func_b.span)
+                }
+                _ => Err(GenerateError::Deverror),
+            },
+            ExprElem::ListElem(list_b) => self.check_index(list_b),
+            ExprElem::ListBlockElem(list_block_b) => self.check_array(list_block_b, expected),
+            ExprElem::BlockElem(block_b) => {
+                self.check_stmt_list(&mut block_b.contents)?;
+                Ok(None)
+            }
+            ExprElem::SyntaxElem(syntax_b) => {
+                self.check_syntax(syntax_b)?;
+                Ok(None)
+            }
+            ExprElem::SyntaxBoxElem(syntax_box_b) => {
+                for syntax_b in &mut syntax_box_b.contents {
+                    self.check_syntax(syntax_b)?;
+                }
+                Ok(None)
+            }
+            ExprElem::MatchElem(match_b) => self.check_match(match_b, expected),
+            ExprElem::StructElem(struct_b) => self.check_struct(struct_b),
+            ExprElem::FieldElem(field_b) => self.check_field(field_b),
+            ExprElem::VariantElem(variant_b) => self.check_variant(variant_b),
+            _ => Ok(None),
+        }
+    }
+
+    /// 既存のレコードを指す構造体と配列の値を、レコードを複製する補助関数の呼び出しに置き換える
+    /// リテラルや関数の返り値は新しく確保したレコードなので複製しない
+    /// 列挙型の値は変更できないため複製しない
+    fn copy_record(&mut self, expr: &mut ExprElem, t: &Option<ValueType>) {
+        let Some(t @ (ValueType::Struct(_) | ValueType::Array(..))) = t else {
+            return;
+        };
+        let expr = inner_expr_mut(expr);
+        let is_new = match expr {
+            ExprElem::StructElem(_) | ExprElem::ListBlockElem(_) => true,
+            ExprElem::FuncElem(func_b) => matches!(&*func_b.name, ExprElem::WordElem(_)),
+            _ => false,
+        };
+        if is_new {
+            return;
+        }
+        if !self.effects.copies.contains(t) {
+            self.effects.copies.push(t.clone());
+        }
+        let span = expr.get_span();
+        let empty = ExprElem::ItemElem(ItemBranch {
+            contents: Vec::new(),
+            depth: 0,
+            loopdepth: 0,
+            span,
+        });
+        let value = std::mem::replace(expr, empty);
+        *expr = ExprElem::FuncElem(FuncBranch {
+            name: Box::new(ExprElem::WordElem(WordBranch {
+                contents: copy_func_name(t),
+                depth: 0,
+                loopdepth: 0,
+                span,
+            })),
+            contents: vec![ExprElem::ItemElem(ItemBranch {
+                contents: vec![value],
+                depth: 0,
+                loopdepth: 0,
+                span,
+            })],
+            depth: 0,
+            loopdepth: 0,
+            span,
+        });
+    }
+
+    /// 構造体と配列の引数は複製せずにレコードのアドレスを渡す
+    /// 変数(のフィールドや要素)を渡した場合は、呼び出した関数がそれを変更するかを後で検査するために記録する
+    /// それ以外の式は`copy_record`と同様に複製する
+    fn pass_argument(
+        &mut self,
+        arg: &mut ExprElem,
+        t: &Option<ValueType>,
+        function: &str,
+        index: usize,
+    ) {
+        if !matches!(t, Some(ValueType::Struct(_) | ValueType::Array(..))) {
+            return;
+        }
+        let Some(root) = place_root(inner_expr(arg)) else {
+            self.copy_record(arg, t);
+            return;
+        };
+        let name = &root.contents;
+        if let Some(param) = self.params.iter().position(|p| p == name) {
+            self.effects
+                .forwarded
+                .push((param, function.to_string(), index));
+        } else if let Some((_, declared)) = self.immutables.iter().find(|(n, _)| n == name) {
+            self.effects.borrowed.push(BorrowedArgument {
+                // 宣言時の名前(`x.1`の`x`)で報告する
+                name: name.split('.').next().unwrap_or(name).to_string(),
+                function: function.to_string(),
+                index,
+                span: arg.get_span(),
+                declared: *declared,
+            });
+        }
+    }
+
+    /// 引数のフィールドや要素への代入`p.x = 1.0` `a[0] = 1`を記録する
+    /// 引数自体への再代入`p = q`は呼び出し元の値を変更しない
+    fn record_mutation(&mut self, target: &ExprElem) {
+        let target = inner_expr(target);
+        if matches!(target, ExprElem::WordElem(_)) {
+            return;
+        }
+        let Some(root) = place_root(target) else {
+            return;
+        };
+        if let Some(param) = self.params.iter().position(|p| *p == root.contents) {
+            if !self.effects.mutated.contains(&param) {
+                self.effects.mutated.push(param);
+            }
+        }
+    }
+
+    fn infer_call(
+        &mut self,
+        args: &mut [ExprElem],
+        name: &WordBranch,
+        span: Span,
+    ) -> Result<Option<ValueType>, GenerateError> {
+        let signature = self
+            .functions
+            .iter()
+            .find(|f| f.name == name.contents)
+            .ok_or(GenerateError::Deverror)?;
+        if signature.params.len() != args.len() {
+            return Err(GenerateError::ArgumentCountMismatch {
+                // 関数の中で宣言された関数は宣言時の名前(`outer.helper`の`helper`)で報告する
+                name: name
+                    .contents
+                    .rsplit('.')
+                    .next()
+                    .unwrap_or(&name.contents)
+                    .to_string(),
+                expected: signature.params.len(),
+                found: args.len(),
+                span,
+            });
+        }
+        let (params, result) = (signature.params.clone(), signature.result.clone());
+        for (index, (arg, param)) in args.iter_mut().zip(params).enumerate() {
+            let param = Some(param);
+            let t = self.infer(arg, param.clone())?;
+            expect_type(&param, &t, arg.get_span())?;
+            self.pass_argument(arg, &t, &name.contents, index);
+        }
+        Ok(result)
+    }
+
+    /// `a @ b`を左辺の型に対応する関数の呼び出しに置き換える
+    /// ```lichen
+    /// fn dot_f64(a: f64, b: f64) -> f64 { ... }
+    /// x @ y // -> dot_f64(x, y)
+    /// ```
+    fn infer_dot(&mut self, func_b: &mut FuncBranch) -> Result<Option<ValueType>, GenerateError> {
+        let ExprElem::OpeElem(ope_b) = &*func_b.name else {
+            return Err(GenerateError::Deverror);
+        };
+        let ope_b = ope_b.clone();
+        let [l_expr, r_expr] = &mut func_b.contents[..] else {
+            return Err(GenerateError::Deverror);
+        };
+        let l_type = self.infer(l_expr, None)?;
+        let name = format!("{}{}", DOT_FUNC_PREFIX, type_name(&l_type));
+        let signature = self
+            .functions
+            .iter()
+            .find(|f| f.name == name && f.params.len() == 2)
+            .ok_or_else(|| GenerateError::UndefinedOperatorFunction {
+                ope: ope_b.ope.clone(),
+                found: type_name(&l_type),
+                name: name.clone(),
+                span: ope_b.span,
+            })?;
+        let (params, result) = (signature.params.clone(), signature.result.clone());
+        let (l_param, r_param) = (Some(params[0].clone()), Some(params[1].clone()));
+        expect_type(&l_param, &l_type, l_expr.get_span())?;
+        let r_type = self.infer(r_expr, r_param.clone())?;
+        expect_type(&r_param, &r_type, r_expr.get_span())?;
+        self.pass_argument(l_expr, &l_type, &name, 0);
+        self.pass_argument(r_expr, &r_type, &name, 1);
+        *func_b.name = ExprElem::WordElem(WordBranch {
+            contents: name,
+            depth: ope_b.depth,
+            loopdepth: func_b.loopdepth,
+            span: ope_b.span,
+        });
+        Ok(result)
+    }
+
+    fn infer_operation(
+        &mut self,
+        contents: &mut [ExprElem],
+        ope_b: &mut OperatorBranch,
+        span: Span,
+        expected: Option<ValueType>,
+    ) -> Result<Option<ValueType>, GenerateError> {
+        let [l_expr, r_expr] = contents else {
+            return Err(GenerateError::Deverror);
+        };
+        let ope = ope_b.ope.clone();
+        match &*ope {
+            _ if ope_b.is_assignment() => {
+                let target = match l_expr {
+                    ExprElem::ItemElem(item_b) => match &item_b.contents[..] {
+                        [ExprElem::WordElem(word_b)] => Some(word_b.contents.clone()),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                let l_type = match &target {
+                    // 型の決まっていない変数への代入では左辺の値を参照しない
+                    Some(name) if ope == "=" && self.is_unassigned(name) => None,
+                    _ => self.infer(l_expr, None)?,
+                };
+                let r_type = self.infer(r_expr, l_type.clone())?;
+                ope_b.ope_type = r_type.as_ref().map(ValueType::wasm_type);
+                self.copy_record(r_expr, &r_type);
+                self.record_mutation(l_expr);
+                match (target, l_type) {
+                    // 型の決まっていない変数への最初の代入
+                    (Some(name), None) if ope == "=" => self.set_local_type(&name, r_type),
+                    (_, l_type) => {
+                        expect_type(&l_type, &r_type, r_expr.get_span())?;
+                        check_operand(&ope, &l_type, span)?;
+                    }
+                }
+                Ok(None)
+            }
+            "as" => {
+                // 数値型同士であればどの型にも変換できる
+                let to = cast_type(r_expr)?;
+                let from = self.infer(l_expr, None)?;
+                check_operand(&ope, &from, span)?;
+                ope_b.ope_type = from.as_ref().and_then(ValueType::primitive);
+                Ok(Some(to.into()))
+            }
+            "-" | "!" | "~" if is_empty_item(l_expr) => {
+                // 前置記法
+                let t = match num_mut(r_expr) {
+                    // `-2147483648`は`i32`の範囲に収まる
+                    Some(num_b) if ope == "-" => Some(check_num(num_b, &expected, true)?.into()),
+                    _ => self.infer(r_expr, expected)?,
+                };
+                check_operand(&ope, &t, span)?;
+                let primitive = t.as_ref().and_then(ValueType::primitive);
+                if ope == "-" && primitive.is_some_and(|t| t.is_unsigned()) {
+                    // 符号なし整数の符号は反転できない
+                    return Err(GenerateError::InvalidOperandType {
+                        ope,
+                        found: type_name(&t),
+                        span,
+                    });
+                }
+                ope_b.ope_type = primitive;
+                Ok(t)
+            }
+            _ => {
+                let is_comparison = matches!(&*ope, "==" | "!=" | "<" | "<=" | ">" | ">=");
+                let operand_expected = if is_comparison { None } else { expected };
+                // リテラルの型はもう片方の式の型に合わせる
+                let (l_type, r_type) = if is_literal(l_expr) && !is_literal(r_expr) {
+                    let r_type = self.infer(r_expr, operand_expected)?;
+                    (self.infer(l_expr, r_type.clone())?, r_type)
+                } else {
+                    let l_type = self.infer(l_expr, operand_expected)?;
+                    let r_type = self.infer(r_expr, l_type.clone())?;
+                    (l_type, r_type)
+                };
+                expect_type(&l_type, &r_type, r_expr.get_span())?;
+                check_operand(&ope, &l_type, span)?;
+                ope_b.ope_type = l_type.as_ref().and_then(ValueType::primitive);
+                if let (Some(t), "**") = (ope_b.ope_type, &*ope) {
+                    // コンパイル時に計算した値も数値リテラルと同様に型の範囲を検査する
+                    let base = const_value(l_expr, t);
+                    let exp = const_value(r_expr, t);
+                    if let (Some(base), Some(exp)) = (base, exp) {
+                        let value = pow_const(base, exp, t);
+                        if value.is_some_and(|value| !value.fits(t)) {
+                            return Err(GenerateError::LiteralOutOfRange {
+                                literal: format!("{} ** {}", base, exp),
+                                target: t.get_show_as_string(),
+                                span,
+                            });
+                        }
+                    }
+                }
+                if is_comparison {
+                    Ok(Some(PrimitiveType::I32.into()))
+                } else {
+                    Ok(l_type)
+                }
+            }
+        }
+    }
+}
+
+/// 数値リテラルの型を決定し、値が型の範囲に収まるかを検査する
+fn check_num(
+    num_b: &mut NumBranch,
+    expected: &Option<ValueType>,
+    negative: bool,
+) -> Result<PrimitiveType, GenerateError> {
+    let expected = expected.as_ref().and_then(ValueType::primitive);
+    let t = match (num_b.suffix, num_b.value, expected) {
+        (Some(suffix), _, _) => suffix,
+        (None, NumValue::Int(_), Some(t)) if t.is_integer() => t,
+        (None, NumValue::Float(_), Some(t)) if t.is_float() => t,
+        (None, NumValue::Int(_), _) => PrimitiveType::I32,
+        (None, NumValue::Float(_), _) => PrimitiveType::F64,
+    };
+    if !num_b.fits(t, negative) {
+        return Err(GenerateError::LiteralOutOfRange {
+            literal: format!("{}{}", if negative { "-" } else { "" }, num_b.contents),
+            target: t.get_show_as_string(),
+            span: num_b.span,
+        });
+    }
+    num_b.num_type = Some(t);
+    Ok(t)
+}
+
+/// `1` `(1)`の数値リテラル
+fn num_mut(expr: &mut ExprElem) -> Option<&mut NumBranch> {
+    match expr {
+        ExprElem::NumElem(num_b) => Some(num_b),
+        ExprElem::ItemElem(item_b) => match &mut item_b.contents[..] {
+            [inner] => num_mut(inner),
+            _ => None,
+        },
+        ExprElem::ParenBlockElem(paren_b) => match &mut paren_b.contents[..] {
+            [inner] => num_mut(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 型の決まっていない(接尾辞のない)数値リテラルかどうか
+fn is_literal(expr: &ExprElem) -> bool {
+    match expr {
+        ExprElem::NumElem(num_b) => num_b.suffix.is_none(),
+        ExprElem::ItemElem(item_b) => matches!(&item_b.contents[..], [inner] if is_literal(inner)),
+        ExprElem::ParenBlockElem(paren_b) => {
+            matches!(&paren_b.contents[..], [inner] if is_literal(inner))
+        }
+        ExprElem::FuncElem(func_b) => match (&*func_b.name, &func_b.contents[..]) {
+            // `-1`
+            (ExprElem::OpeElem(ope_b), [l_expr, r_expr]) => {
+                ope_b.ope == "-" && is_empty_item(l_expr) && is_literal(r_expr)
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// 一つの式のみを包んでいる要素(引数の`ItemElem`や`(p)`)
+fn is_wrapper(expr: &ExprElem) -> bool {
+    match expr {
+        ExprElem::ItemElem(item_b) => item_b.contents.len() == 1,
+        ExprElem::ParenBlockElem(paren_b) => paren_b.contents.len() == 1,
+        _ => false,
+    }
+}
+
+/// 包んでいる要素を取り除いた式
+fn inner_expr(expr: &ExprElem) -> &ExprElem {
+    match expr {
+        ExprElem::ItemElem(item_b) if is_wrapper(expr) => inner_expr(&item_b.contents[0]),
+        ExprElem::ParenBlockElem(paren_b) if is_wrapper(expr) => inner_expr(&paren_b.contents[0]),
+        _ => expr,
+    }
+}
+
+fn inner_expr_mut(expr: &mut ExprElem) -> &mut ExprElem {
+    if !is_wrapper(expr) {
+        return expr;
+    }
+    match expr {
+        ExprElem::ItemElem(item_b) => inner_expr_mut(&mut item_b.contents[0]),
+        ExprElem::ParenBlockElem(paren_b) => inner_expr_mut(&mut paren_b.contents[0]),
+        _ => expr,
+    }
+}
+
+fn is_empty_item(expr: &ExprElem) -> bool {
+    matches!(expr, ExprElem::ItemElem(item_b) if item_b.has_no_elem())
+}
+
+/// 演算子が型に対して使用できるかを検査する
+/// 構造体、列挙型と配列には`=`による代入のみ使用できる
+fn check_operand(ope: &str, t: &Option<ValueType>, span: Span) -> Result<(), GenerateError> {
+    let is_valid = match t {
+        None => false,
+        Some(ValueType::Struct(_) | ValueType::Enum(_) | ValueType::Array(..)) => ope == "=",
+        Some(ValueType::Primitive(t)) => match ope {
+            "%" | "%=" => t.is_integer(),
+            // ビット演算とシフト
+            "&" | "|" | "^" | "<<" | ">>" | "~" | "&=" | "|=" | "^=" | "<<=" | ">>=" | "rotl"
+            | "rotr" => t.is_integer(),
+            // 範囲
+            ".." | "..=" => t.is_integer(),
+            "&&" | "||" | "!" => *t == PrimitiveType::I32,
+            _ => true,
+        },
+    };
+    if is_valid {
+        Ok(())
+    } else {
+        Err(GenerateError::InvalidOperandType {
+            ope: ope.to_string(),
+            found: type_name(t),
+            span,
+        })
+    }
+}
+
+fn expect_type(
+    expected: &Option<ValueType>,
+    found: &Option<ValueType>,
+    span: Span,
+) -> Result<(), GenerateError> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(GenerateError::TypeMismatch {
+            expected: type_name(expected),
+            found: type_name(found),
+            span,
+        })
+    }
+}
+
+/// エラーに表示する型の名前
+pub fn type_name(t: &Option<ValueType>) -> String {
+    match t {
+        Some(t) => t.get_show_as_string(),
+        None => String::from("()"),
+    }
+}
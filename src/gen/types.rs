@@ -0,0 +1,314 @@
+use crate::abs::ast::{Token, TypeElem};
+use crate::abs::span::Span;
+use crate::errors::generate_errors::GenerateError;
+use crate::gen::resolver::suggest;
+use crate::gen::wasm::HEAP_BASE;
+use crate::token::decenum::DecEnumBranch;
+use crate::token::decstruct::DecStructBranch;
+use crate::token::ttype::primitive::PrimitiveType;
+
+/// 一つのレコードの大きさの上限
+/// 線形メモリ(4GiB)から`__alloc`が確保し始めるアドレスまでの領域を除いた大きさ
+pub const MAX_RECORD_SIZE: u64 = (1 << 32) - HEAP_BASE as u64;
+
+/// # ValueType
+/// 型検査で扱う値の型
+/// 構造体の値は線形メモリ上のレコードの先頭アドレス(`i32`)として扱う
+/// 列挙型の値はタグ(`i32`)もしくはタグと値を格納したレコードのアドレス(`i32`)として扱う
+/// 配列の値は要素を順に並べたレコードのアドレス(`i32`)として扱う
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValueType {
+    Primitive(PrimitiveType),
+    Struct(String),
+    Enum(String),
+    /// 要素の型と要素の数
+    Array(Box<ValueType>, u32),
+}
+
+impl ValueType {
+    /// プリミティブ型の場合はその型を返却する
+    pub fn primitive(&self) -> Option<PrimitiveType> {
+        match self {
+            Self::Primitive(t) => Some(*t),
+            Self::Struct(_) | Self::Enum(_) | Self::Array(..) => None,
+        }
+    }
+
+    /// wasm上で値を表す型
+    pub fn wasm_type(&self) -> PrimitiveType {
+        match self {
+            Self::Primitive(t) => *t,
+            Self::Struct(_) | Self::Enum(_) | Self::Array(..) => PrimitiveType::I32,
+        }
+    }
+
+    /// レコードに格納したときの大きさ(バイト)
+    /// アラインメントも大きさと同じになる
+    pub fn size(&self) -> u32 {
+        match self.wasm_type() {
+            PrimitiveType::I64 | PrimitiveType::U64 | PrimitiveType::F64 => 8,
+            PrimitiveType::I32 | PrimitiveType::U32 | PrimitiveType::F32 => 4,
+        }
+    }
+
+    pub fn get_show_as_string(&self) -> String {
+        match self {
+            Self::Primitive(t) => t.get_show_as_string(),
+            Self::Struct(name) | Self::Enum(name) => name.clone(),
+            Self::Array(element, length) => {
+                format!("[{}; {}]", element.get_show_as_string(), length)
+            }
+        }
+    }
+}
+
+impl From<PrimitiveType> for ValueType {
+    fn from(t: PrimitiveType) -> Self {
+        Self::Primitive(t)
+    }
+}
+
+/// # TypeNames
+/// モジュールで宣言されている構造体と列挙型の名前
+/// 型注釈の解決に使用する
+#[derive(Clone, Debug, Default)]
+pub struct TypeNames {
+    pub structs: Vec<String>,
+    pub enums: Vec<String>,
+}
+
+impl TypeNames {
+    /// 全ての型の名前
+    pub fn all(&self) -> Vec<String> {
+        self.structs.iter().chain(&self.enums).cloned().collect()
+    }
+}
+
+/// # ModuleTypes
+/// モジュールで宣言されている型の名前と配置
+#[derive(Clone, Debug, Default)]
+pub struct ModuleTypes {
+    pub names: TypeNames,
+    pub structs: Vec<StructLayout>,
+    pub enums: Vec<EnumLayout>,
+}
+
+impl ModuleTypes {
+    pub fn struct_layout(&self, name: &str) -> Option<&StructLayout> {
+        self.structs.iter().find(|layout| layout.name == name)
+    }
+
+    pub fn enum_layout(&self, name: &str) -> Option<&EnumLayout> {
+        self.enums.iter().find(|layout| layout.name == name)
+    }
+
+    /// ヴァリアントとそれを持つ列挙型
+    pub fn variant(&self, name: &str) -> Option<(&EnumLayout, &VariantLayout)> {
+        self.enums
+            .iter()
+            .find_map(|layout| layout.variant(name).map(|variant| (layout, variant)))
+    }
+
+    /// 全てのヴァリアントの名前
+    pub fn variant_names(&self) -> Vec<String> {
+        self.enums
+            .iter()
+            .flat_map(|layout| layout.variants.iter().map(|variant| variant.name.clone()))
+            .collect()
+    }
+}
+
+/// # StructLayout
+/// 構造体の線形メモリ上の配置
+///
+/// フィールドは宣言の順に並べ、それぞれの大きさの倍数のオフセットに配置する
+/// 構造体全体の大きさはフィールドの最大のアラインメントの倍数に切り上げる
+/// 構造体の型のフィールドは、そのレコードのアドレスを保持する
+/// ```lichen
+/// struct A { a: i32, b: f64, c: i32 }
+/// // a: offset=0, b: offset=8, c: offset=16
+/// // size=24, align=8
+/// ```
+#[derive(Clone, Debug)]
+pub struct StructLayout {
+    pub name: String,
+    pub fields: Vec<FieldLayout>,
+    pub size: u32,
+    pub align: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct FieldLayout {
+    pub name: String,
+    pub field_type: ValueType,
+    pub offset: u32,
+}
+
+impl StructLayout {
+    /// 構造体宣言から配置を計算する
+    pub fn new(dec_struct: &DecStructBranch, names: &TypeNames) -> Result<Self, GenerateError> {
+        let mut fields: Vec<FieldLayout> = Vec::new();
+        let mut offset = 0;
+        let mut align = 1;
+        for field in &dec_struct.fields {
+            if fields.iter().any(|f| f.name == field.name) {
+                return Err(GenerateError::DuplicateField {
+                    name: field.name.clone(),
+                    span: field.span,
+                });
+            }
+            let field_type = value_type(&field.field_type, names)?;
+            let size = field_type.size();
+            offset = align_to(offset, size);
+            align = align.max(size);
+            fields.push(FieldLayout {
+                name: field.name.clone(),
+                field_type,
+                offset,
+            });
+            offset += size;
+        }
+        Ok(Self {
+            name: dec_struct.name.clone(),
+            fields,
+            size: align_to(offset, align),
+            align,
+        })
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldLayout> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// 似た名前のフィールド
+    pub fn suggest_field(&self, name: &str) -> Option<String> {
+        let names: Vec<String> = self.fields.iter().map(|f| f.name.clone()).collect();
+        suggest(name, &names)
+    }
+}
+
+/// # EnumLayout
+/// 列挙型のタグと線形メモリ上の配置
+///
+/// タグは宣言の順に0から割り当てる
+/// 全てのヴァリアントが値を持たない場合はタグのみで値を表し、レコードを確保しない
+/// 値を持つヴァリアントがある場合は、オフセット0にタグを、その後にヴァリアントの値を構造体のフィールドと同様に配置する
+/// レコードの大きさは最も大きいヴァリアントに合わせる
+/// ```lichen
+/// enum Shape { Circle(f64), Rect(f64, f64) }
+/// // tag: offset=0
+/// // Circle: tag=0, 0: offset=8
+/// // Rect: tag=1, 0: offset=8, 1: offset=16
+/// // size=24
+/// ```
+#[derive(Clone, Debug)]
+pub struct EnumLayout {
+    pub name: String,
+    pub variants: Vec<VariantLayout>,
+    /// レコードの大きさ
+    /// 値を持たない列挙型の場合は`None`
+    pub size: Option<u32>,
+}
+
+/// # VariantLayout
+/// ヴァリアントのタグと値の配置
+/// 値のフィールドの名前は宣言の順の番号(`0` `1`)
+#[derive(Clone, Debug)]
+pub struct VariantLayout {
+    pub name: String,
+    pub tag: u32,
+    pub fields: Vec<FieldLayout>,
+}
+
+/// レコードの先頭に置くタグの大きさ
+const TAG_SIZE: u32 = 4;
+
+impl EnumLayout {
+    /// 列挙型宣言からタグと配置を計算する
+    pub fn new(dec_enum: &DecEnumBranch, names: &TypeNames) -> Result<Self, GenerateError> {
+        let mut variants: Vec<VariantLayout> = Vec::new();
+        let mut size = TAG_SIZE;
+        let mut align = TAG_SIZE;
+        for (tag, variant) in dec_enum.variants.iter().enumerate() {
+            if variants.iter().any(|v| v.name == variant.name) {
+                return Err(GenerateError::DuplicateDefinition {
+                    name: variant.name.clone(),
+                    span: variant.span,
+                });
+            }
+            let mut fields = Vec::new();
+            let mut offset = TAG_SIZE;
+            for (index, field) in variant.fields.iter().enumerate() {
+                let field_type = value_type(field, names)?;
+                let field_size = field_type.size();
+                offset = align_to(offset, field_size);
+                align = align.max(field_size);
+                fields.push(FieldLayout {
+                    name: index.to_string(),
+                    field_type,
+                    offset,
+                });
+                offset += field_size;
+            }
+            size = size.max(offset);
+            variants.push(VariantLayout {
+                name: variant.name.clone(),
+                tag: tag as u32,
+                fields,
+            });
+        }
+        let has_payload = variants.iter().any(|v| !v.fields.is_empty());
+        Ok(Self {
+            name: dec_enum.name.clone(),
+            variants,
+            size: has_payload.then(|| align_to(size, align)),
+        })
+    }
+
+    pub fn variant(&self, name: &str) -> Option<&VariantLayout> {
+        self.variants.iter().find(|v| v.name == name)
+    }
+}
+
+/// `value`を`align`の倍数に切り上げる
+fn align_to(value: u32, align: u32) -> u32 {
+    value.div_ceil(align) * align
+}
+
+/// 型注釈を値の型に変換する
+pub fn value_type(t: &TypeElem, names: &TypeNames) -> Result<ValueType, GenerateError> {
+    match t {
+        TypeElem::PrimitiveElem(primitive_b) => Ok(primitive_b.primitive_type.into()),
+        TypeElem::WordElem(word_b) if names.structs.contains(&word_b.contents) => {
+            Ok(ValueType::Struct(word_b.contents.clone()))
+        }
+        TypeElem::WordElem(word_b) if names.enums.contains(&word_b.contents) => {
+            Ok(ValueType::Enum(word_b.contents.clone()))
+        }
+        TypeElem::ArrayElem(array_b) => {
+            let element = value_type(&array_b.element, names)?;
+            check_array_size(&element, array_b.length, array_b.span)?;
+            Ok(ValueType::Array(Box::new(element), array_b.length))
+        }
+        TypeElem::WordElem(word_b) => Err(GenerateError::UndefinedType {
+            name: word_b.contents.clone(),
+            span: word_b.span,
+            suggestion: suggest(&word_b.contents, &names.all()),
+        }),
+        _ => Err(GenerateError::InvalidTypeError(t.get_span())),
+    }
+}
+
+/// 配列のレコードが線形メモリに収まる大きさかを検査する
+pub fn check_array_size(element: &ValueType, length: u32, span: Span) -> Result<(), GenerateError> {
+    let size = length as u64 * element.size() as u64;
+    if size > MAX_RECORD_SIZE {
+        return Err(GenerateError::ArrayTooLarge {
+            element: element.get_show_as_string(),
+            length,
+            size,
+            span,
+        });
+    }
+    Ok(())
+}
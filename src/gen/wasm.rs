@@ -1,18 +1,63 @@
-use crate::abs::ast::{ExprElem, StmtElem};
+use crate::abs::ast::{ExprElem, StmtElem, Token};
 use crate::abs::gen::Wasm_gen;
 use crate::errors::generate_errors::GenerateError;
+use crate::gen::module::cast_type;
+use crate::gen::pattern::Pattern;
+use crate::gen::resolver::{
+    for_parts, range_parts, FOR_ARRAY_SUFFIX, FOR_END_SUFFIX, FOR_INDEX_SUFFIX,
+};
+use crate::gen::types::{ModuleTypes, ValueType};
 
+use crate::token::field::FieldBranch;
 use crate::token::func::FuncBranch;
 use crate::token::item::ItemBranch;
 use crate::token::list::ListBranch;
+use crate::token::list_block::ListBlockBranch;
+use crate::token::match_syntax::{FieldTest, FieldTestKind, MatchArmBranch, MatchBranch};
+use crate::token::num::{NumBranch, NumValue};
 use crate::token::operator::OperatorBranch;
 use crate::token::paren_block::ParenBlockBranch;
+use crate::token::structure::StructBranch;
 use crate::token::syntax::SyntaxBranch;
 use crate::token::syntax_box::SyntaxBoxBranch;
+use crate::token::ttype::primitive::PrimitiveType;
+use crate::token::variant::VariantBranch;
 
 pub const LOOP_ADDR: &str = "#l";
 pub const BLOCK_ADDR: &str = "#b";
+/// `for`で次の要素に進むための`loop`のラベル
+pub const FOR_ADDR: &str = "#f";
+/// `match`の値を返す`block`のラベル
+pub const MATCH_ADDR: &str = "#m";
+/// `br_table`で分岐する`match`の腕の数の下限
+const BR_TABLE_MIN_ARMS: usize = 3;
+/// `br_table`の表の大きさの上限
+const BR_TABLE_MAX_SIZE: i128 = 256;
 pub const MEMORY_SPACE_NAME: &str = "__mem";
+/// 補助関数などのために予約されている名前の接頭辞
+/// 関数をこの接頭辞から始まる名前で宣言することはできない
+pub const RESERVED_PREFIX: &str = "__";
+/// `**`の補助関数の名前の接頭辞(`__pow_i32`など)
+pub const POW_FUNC_PREFIX: &str = "__pow_";
+/// 構造体のレコードを確保する補助関数の名前
+pub const ALLOC_FUNC_NAME: &str = "__alloc";
+/// レコードを複製する補助関数の名前の接頭辞(`__copy.Point`など)
+pub const COPY_FUNC_PREFIX: &str = "__copy.";
+/// 次に確保するレコードのアドレスを保持するグローバル変数の名前
+pub const HEAP_POINTER_NAME: &str = "__heap";
+/// レコードを確保し始めるアドレス
+/// それより前の領域は`__mem`による直接のアクセスのために空けておく
+pub const HEAP_BASE: u32 = 1024;
+
+/// プリミティブ型をwasmの型に変換する
+pub fn wasm_primitive_type(t: PrimitiveType) -> &'static str {
+    match t {
+        PrimitiveType::I32 | PrimitiveType::U32 => "i32",
+        PrimitiveType::I64 | PrimitiveType::U64 => "i64",
+        PrimitiveType::F32 => "f32",
+        PrimitiveType::F64 => "f64",
+    }
+}
 
 /// function branch
 impl Wasm_gen for FuncBranch {
@@ -25,7 +70,10 @@ impl Wasm_gen for FuncBranch {
             ExprElem::OpeElem(ope_b) => {
                 // 演算子のとき
                 // 必ず２つの引数が渡されるが`-1`などの場合に注意が必要
-                assembly_text.push_str(&ope_b.generate_wasm(&self.contents[0], &self.contents[1])?);
+                let [l_expr, r_expr] = &self.contents[..] else {
+                    return Err(GenerateError::Deverror);
+                };
+                assembly_text.push_str(&ope_b.generate_wasm(l_expr, r_expr)?);
             }
 
             ExprElem::WordElem(word_b) => {
@@ -66,34 +114,7 @@ impl Wasm_gen for ItemBranch {
         } else if self.contents.len() == 1 {
             // Itemの中に要素が一つだけの場合
             // （特別に修飾子が付与されない場合）
-            // TODO
-            // ここでは、変数をi32として扱います
-            match &self.contents[0] {
-                ExprElem::WordElem(word_b) => {
-                    if word_b.self_is_num()? {
-                        // もし数字だった場合
-                        assembly_text.push_str(&format!("i32.const {}\n", word_b.contents));
-                    } else {
-                        // もし何らかの変数だった場合
-                        assembly_text.push_str(&format!("local.get ${}\n", word_b.contents));
-                    }
-                }
-
-                ExprElem::FuncElem(func_b) => {
-                    assembly_text.push_str(&func_b.generate_wasm()?);
-                }
-
-                ExprElem::ParenBlockElem(paren_b) => {
-                    assembly_text.push_str(&paren_b.generate_wasm()?);
-                }
-                ExprElem::ListElem(list_b) => {
-                    assembly_text.push_str(&list_b.generate_name_wasm()?);
-                    assembly_text.push_str("i32.load\n");
-                }
-                _ => {
-                    return Err(GenerateError::Deverror);
-                }
-            }
+            assembly_text.push_str(&elem_gen_wasm(&self.contents[0])?);
         } else {
             // ここは例えば、let mut aなどの場合
             //
@@ -104,12 +125,209 @@ impl Wasm_gen for ItemBranch {
     }
 }
 
+/// 値を一つ積む要素の生成
+fn elem_gen_wasm(expr: &ExprElem) -> Result<String, GenerateError> {
+    let mut assembly_text = String::default();
+    match expr {
+        ExprElem::WordElem(word_b) => {
+            // 変数の場合
+            assembly_text.push_str(&format!("local.get ${}\n", word_b.contents));
+        }
+        ExprElem::NumElem(num_b) => {
+            // 数字の場合
+            assembly_text.push_str(&num_b.generate_const_wasm());
+        }
+
+        ExprElem::FuncElem(func_b) => {
+            assembly_text.push_str(&func_b.generate_wasm()?);
+        }
+
+        ExprElem::ParenBlockElem(paren_b) => {
+            assembly_text.push_str(&paren_b.generate_wasm()?);
+        }
+        ExprElem::ItemElem(item_b) => {
+            assembly_text.push_str(&item_b.generate_wasm()?);
+        }
+        ExprElem::ListElem(list_b) => {
+            assembly_text.push_str(&list_b.generate_name_wasm()?);
+            assembly_text.push_str(&list_b.access_wasm("load")?);
+        }
+        ExprElem::ListBlockElem(list_block_b) => {
+            assembly_text.push_str(&list_block_b.generate_wasm()?);
+        }
+        ExprElem::MatchElem(match_b) => {
+            assembly_text.push_str(&match_b.generate_wasm()?);
+        }
+        ExprElem::StructElem(struct_b) => {
+            assembly_text.push_str(&struct_b.generate_wasm()?);
+        }
+        ExprElem::VariantElem(variant_b) => {
+            assembly_text.push_str(&variant_b.generate_wasm()?);
+        }
+        ExprElem::FieldElem(field_b) => {
+            assembly_text.push_str(&field_b.generate_base_wasm()?);
+            assembly_text.push_str(&format!(
+                "{}.load offset={}\n",
+                field_b.wasm_type()?,
+                field_b.offset
+            ));
+        }
+        _ => {
+            return Err(GenerateError::Deverror);
+        }
+    }
+    Ok(assembly_text)
+}
+
+impl StructBranch {
+    /// レコードを確保し、フィールドの値を書かれた順に格納する
+    /// ```wat
+    /// i32.const 16
+    /// call $__alloc
+    /// local.set $#struct0
+    /// local.get $#struct0
+    /// f64.const 1.0
+    /// f64.store offset=0
+    /// ...
+    /// local.get $#struct0
+    /// ```
+    pub fn generate_wasm(&self) -> Result<String, GenerateError> {
+        let mut assembly_text = format!(
+            "i32.const {}\ncall ${}\nlocal.set ${}\n",
+            self.size, ALLOC_FUNC_NAME, self.local
+        );
+        for field in &self.fields {
+            let (Some(field_type), [value]) = (field.field_type, &field.value[..]) else {
+                return Err(GenerateError::Deverror);
+            };
+            assembly_text.push_str(&format!("local.get ${}\n", self.local));
+            assembly_text.push_str(&elem_gen_wasm(value)?);
+            assembly_text.push_str(&format!(
+                "{}.store offset={}\n",
+                wasm_primitive_type(field_type),
+                field.offset
+            ));
+        }
+        assembly_text.push_str(&format!("local.get ${}\n", self.local));
+        Ok(assembly_text)
+    }
+}
+
+impl VariantBranch {
+    /// 値を持たない列挙型のヴァリアントはタグの定数になる
+    /// それ以外はレコードを確保し、タグと値を格納する
+    /// ```wat
+    /// i32.const 24
+    /// call $__alloc
+    /// local.set $#enum
+    /// local.get $#enum
+    /// i32.const 1 ;; tag
+    /// i32.store offset=0
+    /// local.get $#enum
+    /// f64.const 2.0
+    /// f64.store offset=8
+    /// ...
+    /// local.get $#enum
+    /// ```
+    pub fn generate_wasm(&self) -> Result<String, GenerateError> {
+        let Some(size) = self.size else {
+            return Ok(format!("i32.const {}\n", self.tag));
+        };
+        let mut assembly_text = format!(
+            "i32.const {}\ncall ${}\nlocal.set ${}\n",
+            size, ALLOC_FUNC_NAME, self.local
+        );
+        assembly_text.push_str(&format!(
+            "local.get ${}\ni32.const {}\ni32.store offset=0\n",
+            self.local, self.tag
+        ));
+        for (arg, (offset, field_type)) in self.args.iter().zip(&self.fields) {
+            assembly_text.push_str(&format!("local.get ${}\n", self.local));
+            assembly_text.push_str(&elem_gen_wasm(arg)?);
+            assembly_text.push_str(&format!(
+                "{}.store offset={}\n",
+                wasm_primitive_type(*field_type),
+                offset
+            ));
+        }
+        assembly_text.push_str(&format!("local.get ${}\n", self.local));
+        Ok(assembly_text)
+    }
+}
+
+impl FieldBranch {
+    /// レコードのアドレス
+    pub fn generate_base_wasm(&self) -> Result<String, GenerateError> {
+        elem_gen_wasm(&self.base)
+    }
+
+    fn wasm_type(&self) -> Result<&'static str, GenerateError> {
+        self.field_type
+            .map(wasm_primitive_type)
+            .ok_or(GenerateError::Deverror)
+    }
+
+    /// 代入先として使えるのは変数から辿れるフィールドのみ
+    fn is_place(&self) -> bool {
+        is_place(&self.base)
+    }
+}
+
+/// 変数から辿れるフィールドと、副作用のない添字による要素
+/// 複合代入では読み出しと書き込みのためにアドレスを二度計算する
+fn is_place(expr: &ExprElem) -> bool {
+    match expr {
+        ExprElem::WordElem(_) => true,
+        ExprElem::FieldElem(field_b) => field_b.is_place(),
+        ExprElem::ListElem(list_b) => {
+            (list_b.is_memory() || is_place(&list_b.name)) && list_b.contents.iter().all(is_pure)
+        }
+        _ => false,
+    }
+}
+
+/// 評価しても副作用のない式(変数、数値リテラルとそれらの演算)
+fn is_pure(expr: &ExprElem) -> bool {
+    match expr {
+        ExprElem::WordElem(_) | ExprElem::NumElem(_) => true,
+        ExprElem::ItemElem(item_b) => item_b.contents.iter().all(is_pure),
+        ExprElem::ParenBlockElem(paren_b) => paren_b.contents.iter().all(is_pure),
+        ExprElem::FuncElem(func_b) => match &*func_b.name {
+            ExprElem::OpeElem(ope_b) => {
+                !ope_b.is_assignment() && func_b.contents.iter().all(is_pure)
+            }
+            _ => false,
+        },
+        ExprElem::FieldElem(_) | ExprElem::ListElem(_) => is_place(expr),
+        _ => false,
+    }
+}
+
 impl ListBranch {
+    /// 線形メモリに直接アクセスする`__mem[i]`かどうか
+    pub fn is_memory(&self) -> bool {
+        matches!(&*self.name, ExprElem::WordElem(word_b) if word_b.contents == MEMORY_SPACE_NAME)
+    }
+
+    /// 添字が整数のリテラルの場合はその値
+    pub fn const_index(&self) -> Option<u128> {
+        let [ExprElem::ItemElem(item_b)] = &self.contents[..] else {
+            return None;
+        };
+        match &item_b.contents[..] {
+            [ExprElem::NumElem(num_b)] => match num_b.value {
+                NumValue::Int(n) => Some(n),
+                NumValue::Float(_) => None,
+            },
+            _ => None,
+        }
+    }
+
     /// indexの展開
     pub fn generate_contents_wasm(&self) -> Result<String, GenerateError> {
         let mut assembly_text = String::default();
-        match &self.contents[0] {
-            ExprElem::ItemElem(item_b) => {
+        match self.contents.first() {
+            Some(ExprElem::ItemElem(item_b)) => {
                 assembly_text.push_str(&item_b.generate_wasm()?);
             }
             _ => {
@@ -119,32 +337,170 @@ impl ListBranch {
         Ok(assembly_text)
     }
 
+    /// 要素のアドレスを積む
+    /// 配列の要素のアドレスは`配列のアドレス + 添字 * 要素の大きさ`
+    /// 添字が定数の場合は`access_wasm`の`offset`で表すため、配列のアドレスのみを積む
+    /// 定数でない添字が要素の数以上の場合は`unreachable`で停止する
+    /// ```wat
+    /// ;; a[i] (a: [f64; 3])
+    /// local.get $a
+    /// local.get $i
+    /// local.tee $#index
+    /// i32.const 3
+    /// i32.ge_u
+    /// if
+    /// unreachable
+    /// end
+    /// local.get $#index
+    /// i32.const 8
+    /// i32.mul
+    /// i32.add
+    /// ```
     pub fn generate_name_wasm(&self) -> Result<String, GenerateError> {
-        let mut assembly_text = String::default();
-        if let ExprElem::WordElem(word_b) = &*self.name {
-            if word_b.contents == MEMORY_SPACE_NAME {
-                // 特別なケース、メモリに直接アクセスするための方法を提供する
-                // ```
-                // __mem[0] = 0;
-                // ```
-                assembly_text.push_str(&self.generate_contents_wasm()?);
-            } else {
-                // 通常のケース
-                // ```
-                // a[0] = 0;
-                // ```
-                todo!()
-            }
-        } else {
-            // 呼び出しの対象が名前ではない場合
+        if self.is_memory() {
+            // 特別なケース、メモリに直接アクセスするための方法を提供する
             // ```
-            // lst[0][0]
-            // ^^^^^^
+            // __mem[0] = 0;
             // ```
-            todo!()
+            return self.generate_contents_wasm();
+        }
+        if self.element_type.is_none() {
+            // 型検査を経ていない場合
+            return Err(GenerateError::UnsupportedListAccess(self.span));
+        }
+        let mut assembly_text = elem_gen_wasm(&self.name)?;
+        if self.const_index().is_none() {
+            assembly_text.push_str(&self.generate_contents_wasm()?);
+            assembly_text.push_str(&format!(
+                "local.tee ${local}\ni32.const {}\ni32.ge_u\nif\nunreachable\nend\n\
+                 local.get ${local}\ni32.const {}\ni32.mul\ni32.add\n",
+                self.length,
+                self.element_size,
+                local = self.local,
+            ));
         }
         Ok(assembly_text)
     }
+
+    /// 要素を読み書きする命令(`load` `store`)
+    pub fn access_wasm(&self, instruction: &str) -> Result<String, GenerateError> {
+        if self.is_memory() {
+            return Ok(format!("i32.{}\n", instruction));
+        }
+        let t = self
+            .element_type
+            .ok_or(GenerateError::UnsupportedListAccess(self.span))?;
+        let offset = self.const_index().unwrap_or(0) * self.element_size as u128;
+        Ok(format!(
+            "{}.{} offset={}\n",
+            wasm_primitive_type(t),
+            instruction,
+            offset
+        ))
+    }
+}
+
+impl ListBlockBranch {
+    /// レコードを確保し、要素を順に格納する
+    /// ```wat
+    /// i32.const 12
+    /// call $__alloc
+    /// local.set $#array
+    /// local.get $#array
+    /// i32.const 1
+    /// i32.store offset=0
+    /// ...
+    /// local.get $#array
+    /// ```
+    /// `[value; n]`は要素ごとに`value`を評価するため、`[[0; 3]; 2]`の各行は別のレコードになる
+    pub fn generate_wasm(&self) -> Result<String, GenerateError> {
+        let t = wasm_primitive_type(self.element_type.ok_or(GenerateError::Deverror)?);
+        let size = self.element_size;
+        let mut assembly_text = format!(
+            "i32.const {}\ncall ${}\nlocal.set ${}\n",
+            self.len() as u64 * size as u64,
+            ALLOC_FUNC_NAME,
+            self.local
+        );
+        match self.repeat {
+            Some(0) => {}
+            Some(n) => {
+                let [value] = &self.contents[..] else {
+                    return Err(GenerateError::Deverror);
+                };
+                assembly_text.push_str(&self.fill_wasm(value, n, t)?);
+            }
+            None => {
+                for (i, value) in self.contents.iter().enumerate() {
+                    assembly_text.push_str(&format!("local.get ${}\n", self.local));
+                    assembly_text.push_str(&elem_gen_wasm(value)?);
+                    assembly_text.push_str(&format!(
+                        "{}.store offset={}\n",
+                        t,
+                        i as u64 * size as u64
+                    ));
+                }
+            }
+        }
+        assembly_text.push_str(&format!("local.get ${}\n", self.local));
+        Ok(assembly_text)
+    }
+
+    /// `[value; n]`の全ての要素に`value`を評価して格納する
+    /// ```wat
+    /// i32.const 0
+    /// local.set $#array.index
+    /// block
+    /// loop
+    /// local.get $#array.index
+    /// i32.const 10
+    /// i32.ge_u
+    /// br_if 1
+    /// local.get $#array
+    /// local.get $#array.index
+    /// i32.const 4
+    /// i32.mul
+    /// i32.add
+    /// ;; value
+    /// i32.store offset=0
+    /// ...
+    /// br 0
+    /// end
+    /// end
+    /// ```
+    fn fill_wasm(&self, value: &ExprElem, n: u32, t: &str) -> Result<String, GenerateError> {
+        let index = format!("{}{}", self.local, FOR_INDEX_SUFFIX);
+        Ok(format!(
+            "i32.const 0
+local.set ${index}
+block
+loop
+local.get ${index}
+i32.const {n}
+i32.ge_u
+br_if 1
+local.get ${array}
+local.get ${index}
+i32.const {size}
+i32.mul
+i32.add
+{value}{t}.store offset=0
+local.get ${index}
+i32.const 1
+i32.add
+local.set ${index}
+br 0
+end
+end
+",
+            index = index,
+            n = n,
+            array = self.local,
+            size = self.element_size,
+            value = elem_gen_wasm(value)?,
+            t = t
+        ))
+    }
 }
 
 // Wasm_gen ---
@@ -178,41 +534,135 @@ impl OperatorBranch {
         l_expr: &ExprElem,
         r_expr: &ExprElem,
     ) -> Result<String, GenerateError> {
-        // 最初はi32向のみ対応
-        // ここで送られて来るデータが本当に文字列でいいのか考える
+        // 命令は型検査で決定した`ope_type`によって選択する
         let mut assembly_text = String::default();
         match &*self.ope {
             "=" => assembly_text.push_str(&equal_gen_wasm(l_expr, r_expr)?), // equal
-            "+=" => assembly_text.push_str(&ref_aequal_gen_wasm(l_expr, r_expr, "+=")?),
-            "-=" => assembly_text.push_str(&ref_aequal_gen_wasm(l_expr, r_expr, "-=")?),
-            "*=" => assembly_text.push_str(&ref_aequal_gen_wasm(l_expr, r_expr, "*=")?),
-            "/=" => assembly_text.push_str(&ref_aequal_gen_wasm(l_expr, r_expr, "/=")?),
-            "%=" => assembly_text.push_str(&ref_aequal_gen_wasm(l_expr, r_expr, "%=")?),
-            "+" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.add\n")?),
-            "-" => assembly_text.push_str(&sub_gen_wasm(l_expr, r_expr)?), // subtract
-            "*" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.mul\n")?),
-            "/" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.div\n")?),
-            "%" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.rem_s\n")?),
-            "&&" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.and\n")?),
-            "||" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.or\n")?),
+            _ if self.is_assignment() => {
+                // `=`を取り除いた演算子の命令を使う
+                let instruction = self.wasm_instruction(&self.ope[..self.ope.len() - 1])?;
+                assembly_text.push_str(&ref_aequal_gen_wasm(l_expr, r_expr, &instruction)?)
+            }
+            "-" => assembly_text.push_str(&sub_gen_wasm(l_expr, r_expr, self.value_type())?), // subtract
             "!" => assembly_text.push_str(&not_gen_wasm(l_expr, r_expr)?), // xor を使ってnotを再現している
-            //
-            "==" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.eq\n")?),
-            "!=" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.ne\n")?),
-            // 大小には`signed` `unsigned`がある
-            "<" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.lt_s\n")?),
-            ">" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.gt_s\n")?),
-            "<=" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.le_s\n")?),
-            ">=" => assembly_text.push_str(&normal_ope_gen_wasm(l_expr, r_expr, "i32.ge_s\n")?),
-            _ => return Err(GenerateError::InvalidOperation),
+            "**" => assembly_text.push_str(&pow_gen_wasm(l_expr, r_expr, self.value_type())?),
+            "&&" | "||" => assembly_text.push_str(&self.logical_gen_wasm(l_expr, r_expr)?),
+            "~" => assembly_text.push_str(&bitnot_gen_wasm(r_expr, self.value_type())?), // 全ビットが1の値とのxor
+            "as" => assembly_text.push_str(&cast_gen_wasm(
+                l_expr,
+                self.value_type(),
+                cast_type(r_expr)?,
+            )?),
+            ope => assembly_text.push_str(&normal_ope_gen_wasm(
+                l_expr,
+                r_expr,
+                &self.wasm_instruction(ope)?,
+            )?),
         }
         Ok(assembly_text)
     }
+
+    /// 短絡評価する`&&` `||`
+    /// 右辺は左辺の値で結果が決まらない場合にのみ評価し、結果は0か1に正規化する
+    /// ```wat
+    /// ;; a && b
+    /// a
+    /// if (result i32)
+    /// b
+    /// i32.eqz
+    /// i32.eqz
+    /// else
+    /// i32.const 0
+    /// end
+    /// ```
+    fn logical_gen_wasm(
+        &self,
+        l_expr: &ExprElem,
+        r_expr: &ExprElem,
+    ) -> Result<String, GenerateError> {
+        let (ExprElem::ItemElem(l_item), ExprElem::ItemElem(r_item)) = (l_expr, r_expr) else {
+            return Err(GenerateError::Deverror);
+        };
+        let r_assembly_text = format!("{}i32.eqz\ni32.eqz\n", r_item.generate_wasm()?);
+        let (then_text, else_text) = if self.ope == "&&" {
+            (r_assembly_text, "i32.const 0\n".to_string())
+        } else {
+            ("i32.const 1\n".to_string(), r_assembly_text)
+        };
+        Ok(format!(
+            "{}if (result i32)\n{}else\n{}end\n",
+            l_item.generate_wasm()?,
+            then_text,
+            else_text
+        ))
+    }
+
+    /// 演算の対象となる値の型
+    /// 型検査を経ていない場合は`i32`として扱う
+    fn value_type(&self) -> PrimitiveType {
+        self.ope_type.unwrap_or(PrimitiveType::I32)
+    }
+
+    /// 二項演算子に対応する命令
+    /// 整数の除算、剰余、大小比較と右シフトは型によって符号付きか符号なしかを選択する
+    fn wasm_instruction(&self, ope: &str) -> Result<String, GenerateError> {
+        let value_type = self.value_type();
+        let (instruction, has_sign) = match ope {
+            "+" => ("add", false),
+            "-" => ("sub", false),
+            "*" => ("mul", false),
+            "/" => ("div", true),
+            "%" if value_type.is_integer() => ("rem", true),
+            "&" => ("and", false),
+            "|" => ("or", false),
+            "^" => ("xor", false),
+            "<<" => ("shl", false),
+            ">>" => ("shr", true),
+            "rotl" => ("rotl", false),
+            "rotr" => ("rotr", false),
+            "==" => ("eq", false),
+            "!=" => ("ne", false),
+            "<" => ("lt", true),
+            ">" => ("gt", true),
+            "<=" => ("le", true),
+            ">=" => ("ge", true),
+            _ => return Err(GenerateError::InvalidOperation(self.span)),
+        };
+        let instruction = match (
+            has_sign && value_type.is_integer(),
+            value_type.is_unsigned(),
+        ) {
+            (true, true) => format!("{}_u", instruction),
+            (true, false) => format!("{}_s", instruction),
+            (false, _) => instruction.to_string(),
+        };
+        Ok(format!(
+            "{}.{}\n",
+            wasm_primitive_type(value_type),
+            instruction
+        ))
+    }
+}
+
+impl NumBranch {
+    /// 数値リテラルを型検査で決定した型の定数として展開する
+    /// 型検査を経ていない場合は接尾辞か値の種類から型を決める
+    pub fn generate_const_wasm(&self) -> String {
+        let num_type = self.num_type.or(self.suffix).unwrap_or(match self.value {
+            NumValue::Int(_) => PrimitiveType::I32,
+            NumValue::Float(_) => PrimitiveType::F64,
+        });
+        format!(
+            "{}.const {}\n",
+            wasm_primitive_type(num_type),
+            self.value_as_string()
+        )
+    }
 }
 
 fn equal_gen_wasm(l_expr: &ExprElem, r_expr: &ExprElem) -> Result<String, GenerateError> {
     let mut assembly_text = String::default();
-    let mut r_assembly_text = String::default();
+    let r_assembly_text: String;
 
     if let ExprElem::ItemElem(item_b) = r_expr {
         r_assembly_text = item_b.generate_wasm()?;
@@ -222,28 +672,32 @@ fn equal_gen_wasm(l_expr: &ExprElem, r_expr: &ExprElem) -> Result<String, Genera
     if let ExprElem::ItemElem(item_b) = l_expr {
         // とりあえず、パターンなどを考えず、一つの変数に値を代入する
         // 場合のみの実装
-        if let ExprElem::WordElem(word_b) = &item_b.contents[0] {
+        if let Some(ExprElem::WordElem(word_b)) = item_b.contents.first() {
             // 普通の変数に代入するのと同じ
             // a = 1;
             // のようなケース
             assembly_text.push_str(&r_assembly_text);
             assembly_text.push_str(&format!("local.set ${}\n", word_b.contents));
-        } else if let ExprElem::ListElem(list_b) = &item_b.contents[0] {
-            // pass
-            // TODO
+        } else if let Some(ExprElem::ListElem(list_b)) = item_b.contents.first() {
             // a[0] = 1;のようなケース
-            // ^
-            // 呼び出す対象が名前の場合
             // ```
             // <list elem> = <r_expr>
             // ```
-            //
             assembly_text.push_str(&list_b.generate_name_wasm()?);
             assembly_text.push_str(&r_assembly_text);
-            assembly_text.push_str("i32.store\n");
+            assembly_text.push_str(&list_b.access_wasm("store")?);
+        } else if let [ExprElem::FieldElem(field_b)] = &item_b.contents[..] {
+            // p.x = 1.0;
+            assembly_text.push_str(&field_b.generate_base_wasm()?);
+            assembly_text.push_str(&r_assembly_text);
+            assembly_text.push_str(&format!(
+                "{}.store offset={}\n",
+                field_b.wasm_type()?,
+                field_b.offset
+            ));
         } else {
             // word 以外がパターンに渡された場合
-            return Err(GenerateError::InvalidleftPattern);
+            return Err(GenerateError::InvalidleftPattern(item_b.span));
         }
     } else {
         return Err(GenerateError::Deverror);
@@ -254,7 +708,7 @@ fn equal_gen_wasm(l_expr: &ExprElem, r_expr: &ExprElem) -> Result<String, Genera
 pub fn ref_aequal_gen_wasm(
     l_expr: &ExprElem,
     r_expr: &ExprElem,
-    ope: &str,
+    instruction: &str,
 ) -> Result<String, GenerateError> {
     let mut assembly_text = String::default();
     let r_assembly_text: String;
@@ -271,13 +725,32 @@ pub fn ref_aequal_gen_wasm(
     }
     if let ExprElem::ItemElem(item_b) = l_expr {
         // 左は式ではなくパターンの処理をする必要があります
-        if let ExprElem::WordElem(word_b) = &item_b.contents[0] {
+        if let Some(ExprElem::WordElem(word_b)) = item_b.contents.first() {
             // pass
             setter_assembly_text = format!("local.set ${}\n", word_b.contents); // setter
             getter_assembly_text = format!("local.get ${}\n", word_b.contents); // setter
+        } else if let [ExprElem::FieldElem(field_b)] = &item_b.contents[..] {
+            if !field_b.is_place() {
+                return Err(GenerateError::InvalidleftPattern(item_b.span));
+            }
+            // p.x += 1.0;
+            // 格納先のアドレスと読み出すアドレスの両方を積んでおく
+            let base = field_b.generate_base_wasm()?;
+            let t = field_b.wasm_type()?;
+            setter_assembly_text = format!("{}.store offset={}\n", t, field_b.offset);
+            getter_assembly_text =
+                format!("{}{}{}.load offset={}\n", base, base, t, field_b.offset);
+        } else if let [ExprElem::ListElem(list_b)] = &item_b.contents[..] {
+            if !is_place(&item_b.contents[0]) {
+                return Err(GenerateError::InvalidleftPattern(item_b.span));
+            }
+            // a[i] += 1;
+            let address = list_b.generate_name_wasm()?;
+            setter_assembly_text = list_b.access_wasm("store")?;
+            getter_assembly_text = format!("{}{}{}", address, address, list_b.access_wasm("load")?);
         } else {
-            println!("まだサポートしていない書き方です"); // TODO
-            todo!()
+            // まだサポートしていない書き方です
+            return Err(GenerateError::InvalidleftPattern(item_b.span));
         }
     } else {
         return Err(GenerateError::Deverror);
@@ -291,14 +764,7 @@ pub fn ref_aequal_gen_wasm(
     // ```
     assembly_text.push_str(&getter_assembly_text);
     assembly_text.push_str(&r_assembly_text);
-    match ope {
-        "+=" => assembly_text.push_str("i32.add\n"),
-        "-=" => assembly_text.push_str("i32.sub\n"),
-        "*=" => assembly_text.push_str("i32.mul\n"),
-        "/=" => assembly_text.push_str("i32.div\n"),
-        "%=" => assembly_text.push_str("i32.rem_s\n"),
-        _ => return Err(GenerateError::Deverror),
-    }
+    assembly_text.push_str(instruction);
     assembly_text.push_str(&setter_assembly_text);
     Ok(assembly_text)
 }
@@ -324,12 +790,414 @@ fn normal_ope_gen_wasm(
     Ok(assembly_text)
 }
 
+/// `**`によるべき乗
+/// 数値リテラルと`**`のみからなる式(`2 ** 3 ** 2`)はコンパイル時に計算する
+/// それ以外の場合は補助関数を呼び出す(`f32`は`f64`に変換して計算する)
+fn pow_gen_wasm(
+    l_expr: &ExprElem,
+    r_expr: &ExprElem,
+    value_type: PrimitiveType,
+) -> Result<String, GenerateError> {
+    let (ExprElem::ItemElem(l_item), ExprElem::ItemElem(r_item)) = (l_expr, r_expr) else {
+        return Err(GenerateError::Deverror);
+    };
+    // 値が型の範囲に収まることは`TypeChecker`で検査している
+    if let Some(value) = fold_pow(l_expr, r_expr, value_type) {
+        if value.fits(value_type) {
+            return Ok(format!(
+                "{}.const {}\n",
+                wasm_primitive_type(value_type),
+                value
+            ));
+        }
+    }
+    let (l_text, r_text) = (l_item.generate_wasm()?, r_item.generate_wasm()?);
+    let func_type = pow_func_type(value_type);
+    Ok(if value_type == PrimitiveType::F32 {
+        format!(
+            "{}f64.promote_f32\n{}f64.promote_f32\ncall ${}{}\nf32.demote_f64\n",
+            l_text,
+            r_text,
+            POW_FUNC_PREFIX,
+            wasm_primitive_type(func_type)
+        )
+    } else {
+        format!(
+            "{}{}call ${}{}\n",
+            l_text,
+            r_text,
+            POW_FUNC_PREFIX,
+            wasm_primitive_type(func_type)
+        )
+    })
+}
+
+/// `**`の補助関数が扱う型
+fn pow_func_type(value_type: PrimitiveType) -> PrimitiveType {
+    match value_type {
+        PrimitiveType::F32 => PrimitiveType::F64,
+        t => t,
+    }
+}
+
+/// コンパイル時に計算した`**`の値
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConstValue {
+    Int(i128),
+    Float(f64),
+}
+
+impl ConstValue {
+    /// 値が`t`の範囲に収まるか
+    pub fn fits(&self, t: PrimitiveType) -> bool {
+        match (*self, t) {
+            (Self::Int(v), PrimitiveType::I32) => i32::try_from(v).is_ok(),
+            (Self::Int(v), PrimitiveType::I64) => i64::try_from(v).is_ok(),
+            (Self::Int(v), PrimitiveType::U32) => u32::try_from(v).is_ok(),
+            (Self::Int(v), PrimitiveType::U64) => u64::try_from(v).is_ok(),
+            (Self::Float(v), PrimitiveType::F32 | PrimitiveType::F64) => v.is_finite(),
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for ConstValue {
+    /// wasmの`const`命令に渡す値の表記
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(v) => write!(f, "{}", v),
+            Self::Float(v) => write!(f, "{:?}", v),
+        }
+    }
+}
+
+/// `l_expr ** r_expr`の両辺が数値リテラルと`**`のみからなる場合に値を計算する
+pub fn fold_pow(
+    l_expr: &ExprElem,
+    r_expr: &ExprElem,
+    value_type: PrimitiveType,
+) -> Option<ConstValue> {
+    pow_const(
+        const_value(l_expr, value_type)?,
+        const_value(r_expr, value_type)?,
+        value_type,
+    )
+}
+
+/// `base ** exp`を計算する
+/// 整数の結果が`i128`に収まらない場合は`i128::MAX`とし、どの型の範囲にも収まらない値として扱う
+/// 指数が負の整数の場合や結果が数でない場合は`None`を返却する
+pub fn pow_const(
+    base: ConstValue,
+    exp: ConstValue,
+    value_type: PrimitiveType,
+) -> Option<ConstValue> {
+    match (base, exp) {
+        (ConstValue::Int(base), ConstValue::Int(exp)) if value_type.is_integer() => {
+            if exp < 0 {
+                return None;
+            }
+            let value = if base.abs() <= 1 {
+                // 指数が大きい場合も値は指数の偶奇のみで決まる
+                base.pow(exp.min(2 + exp % 2) as u32)
+            } else {
+                u32::try_from(exp)
+                    .ok()
+                    .and_then(|exp| base.checked_pow(exp))
+                    .unwrap_or(i128::MAX)
+            };
+            Some(ConstValue::Int(value))
+        }
+        (ConstValue::Float(base), ConstValue::Float(exp)) => {
+            let value = match value_type {
+                PrimitiveType::F32 => (base as f32).powf(exp as f32) as f64,
+                PrimitiveType::F64 => base.powf(exp),
+                _ => return None,
+            };
+            (!value.is_nan()).then_some(ConstValue::Float(value))
+        }
+        _ => None,
+    }
+}
+
+/// 数値リテラル、前置の`-`、`**`のみからなる式の値
+pub fn const_value(expr: &ExprElem, value_type: PrimitiveType) -> Option<ConstValue> {
+    match expr {
+        ExprElem::NumElem(num_b) => Some(match num_b.value {
+            NumValue::Int(v) => ConstValue::Int(i128::try_from(v).unwrap_or(i128::MAX)),
+            NumValue::Float(v) => ConstValue::Float(v),
+        }),
+        ExprElem::ItemElem(item_b) => match &item_b.contents[..] {
+            [inner] => const_value(inner, value_type),
+            _ => None,
+        },
+        ExprElem::ParenBlockElem(paren_b) => match &paren_b.contents[..] {
+            [inner] => const_value(inner, value_type),
+            _ => None,
+        },
+        ExprElem::FuncElem(func_b) => match (&*func_b.name, &func_b.contents[..]) {
+            (ExprElem::OpeElem(ope_b), [l_expr, r_expr]) if ope_b.ope == "**" => {
+                fold_pow(l_expr, r_expr, value_type)
+            }
+            (ExprElem::OpeElem(ope_b), [ExprElem::ItemElem(l_item), r_expr])
+                if ope_b.ope == "-" && l_item.has_no_elem() =>
+            {
+                match const_value(r_expr, value_type)? {
+                    ConstValue::Int(v) => Some(ConstValue::Int(-v)),
+                    ConstValue::Float(v) => Some(ConstValue::Float(-v)),
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `**`の補助関数
+/// 整数は二分累乗法で計算する関数を生成し、指数は符号なし整数として扱う
+/// 浮動小数点数は実行環境の`env.pow`をインポートする
+pub fn pow_helper_wasm(value_type: PrimitiveType) -> String {
+    let t = wasm_primitive_type(value_type);
+    if value_type.is_float() {
+        return format!(
+            "(import \"env\" \"pow\" (func ${}{} (param {t}) (param {t}) (result {t})))\n",
+            POW_FUNC_PREFIX, t
+        );
+    }
+    format!(
+        "(func ${prefix}{t} (param $base {t}) (param $exp {t}) (result {t})
+(local $result {t})
+{t}.const 1
+local.set $result
+block ${BLOCK_ADDR}0
+loop ${LOOP_ADDR}0
+local.get $exp
+{t}.eqz
+br_if ${BLOCK_ADDR}0
+local.get $exp
+{t}.const 1
+{t}.and
+{t}.const 0
+{t}.ne
+if
+local.get $result
+local.get $base
+{t}.mul
+local.set $result
+end
+local.get $base
+local.get $base
+{t}.mul
+local.set $base
+local.get $exp
+{t}.const 1
+{t}.shr_u
+local.set $exp
+br ${LOOP_ADDR}0
+end
+end
+local.get $result
+)
+",
+        prefix = POW_FUNC_PREFIX,
+    )
+}
+
+/// レコードを複製する補助関数の名前
+/// 配列は`要素の数x要素の型`で表す(`[[i32; 3]; 2]` -> `__copy.2x3xi32`)
+pub fn copy_func_name(t: &ValueType) -> String {
+    fn type_name(t: &ValueType) -> String {
+        match t {
+            ValueType::Array(element, length) => format!("{}x{}", length, type_name(element)),
+            _ => t.get_show_as_string(),
+        }
+    }
+    format!("{}{}", COPY_FUNC_PREFIX, type_name(t))
+}
+
+/// レコードを複製する補助関数
+/// 新しいレコードを確保し、フィールドや要素を順に格納する
+/// 構造体や配列のフィールド・要素は再帰的に複製し、列挙型の値はアドレスのみを格納する
+/// ```wat
+/// ;; struct Point { x: f64, y: f64 }
+/// (func $__copy.Point (param $src i32) (result i32)
+/// (local $dst i32)
+/// i32.const 16
+/// call $__alloc
+/// local.set $dst
+/// local.get $dst
+/// local.get $src
+/// f64.load offset=0
+/// f64.store offset=0
+/// ...
+/// local.get $dst
+/// )
+/// ```
+pub fn copy_helper_wasm(t: &ValueType, types: &ModuleTypes) -> Result<String, GenerateError> {
+    let copy_value = |t: &ValueType| match t {
+        ValueType::Struct(_) | ValueType::Array(..) => format!("call ${}\n", copy_func_name(t)),
+        _ => String::new(),
+    };
+    let mut locals = String::from("(local $dst i32)\n");
+    let mut assembly_text = String::new();
+    match t {
+        ValueType::Struct(name) => {
+            let layout = types.struct_layout(name).ok_or(GenerateError::Deverror)?;
+            assembly_text.push_str(&format!(
+                "i32.const {}\ncall ${}\nlocal.set $dst\n",
+                layout.size, ALLOC_FUNC_NAME
+            ));
+            for field in &layout.fields {
+                let w = wasm_primitive_type(field.field_type.wasm_type());
+                assembly_text.push_str(&format!(
+                    "local.get $dst\nlocal.get $src\n{w}.load offset={offset}\n{copy}{w}.store offset={offset}\n",
+                    offset = field.offset,
+                    copy = copy_value(&field.field_type),
+                ));
+            }
+        }
+        ValueType::Array(element, length) => {
+            // 要素ごとに`i * size`だけずらして読み書きする
+            let size = element.size();
+            locals.push_str("(local $i i32)\n");
+            assembly_text.push_str(&format!(
+                "i32.const {total}\ncall ${alloc}\nlocal.set $dst\n\
+                 block ${BLOCK_ADDR}0\nloop ${LOOP_ADDR}0\n\
+                 local.get $i\ni32.const {length}\ni32.ge_u\nbr_if ${BLOCK_ADDR}0\n\
+                 local.get $dst\nlocal.get $i\ni32.const {size}\ni32.mul\ni32.add\n\
+                 local.get $src\nlocal.get $i\ni32.const {size}\ni32.mul\ni32.add\n\
+                 {w}.load offset=0\n{copy}{w}.store offset=0\n\
+                 local.get $i\ni32.const 1\ni32.add\nlocal.set $i\n\
+                 br ${LOOP_ADDR}0\nend\nend\n",
+                total = *length as u64 * size as u64,
+                alloc = ALLOC_FUNC_NAME,
+                w = wasm_primitive_type(element.wasm_type()),
+                copy = copy_value(element),
+            ));
+        }
+        _ => return Err(GenerateError::Deverror),
+    }
+    Ok(format!(
+        "(func ${} (param $src i32) (result i32)\n{}{}local.get $dst\n)\n",
+        copy_func_name(t),
+        locals,
+        assembly_text
+    ))
+}
+
+/// 構造体のレコードを確保する補助関数
+/// `__heap`を8バイト境界に揃えてから`size`だけ進め、確保した先頭のアドレスを返却する
+/// メモリが足りない場合はページを追加し、追加できない場合やアドレスが溢れる場合はトラップする
+/// 確保したレコードは解放しない
+pub fn alloc_helper_wasm() -> String {
+    format!(
+        "(global ${heap} (mut i32) (i32.const {base}))
+(func ${alloc} (param $size i32) (result i32)
+(local $ptr i32)
+global.get ${heap}
+i32.const 7
+i32.add
+i32.const -8
+i32.and
+local.tee $ptr
+local.get $size
+i32.add
+global.set ${heap}
+global.get ${heap}
+local.get $ptr
+i32.lt_u
+if
+unreachable
+end
+block ${BLOCK_ADDR}0
+loop ${LOOP_ADDR}0
+global.get ${heap}
+memory.size
+i32.const 16
+i32.shl
+i32.le_u
+br_if ${BLOCK_ADDR}0
+i32.const 1
+memory.grow
+i32.const -1
+i32.eq
+if
+unreachable
+end
+br ${LOOP_ADDR}0
+end
+end
+local.get $ptr
+)
+",
+        heap = HEAP_POINTER_NAME,
+        base = HEAP_BASE,
+        alloc = ALLOC_FUNC_NAME,
+    )
+}
+
+/// `~`によるビット反転
+fn bitnot_gen_wasm(r_expr: &ExprElem, value_type: PrimitiveType) -> Result<String, GenerateError> {
+    let mut assembly_text = format!("{}.const -1\n", wasm_primitive_type(value_type));
+    if let ExprElem::ItemElem(item_b) = r_expr {
+        assembly_text.push_str(&item_b.generate_wasm()?);
+    } else {
+        return Err(GenerateError::Deverror);
+    }
+    assembly_text.push_str(&format!("{}.xor\n", wasm_primitive_type(value_type)));
+    Ok(assembly_text)
+}
+
+/// `as`による型変換
+/// 浮動小数点数から整数への変換は範囲外の値を飽和させる(`NaN`は0になる)
+fn cast_gen_wasm(
+    l_expr: &ExprElem,
+    from: PrimitiveType,
+    to: PrimitiveType,
+) -> Result<String, GenerateError> {
+    let mut assembly_text = String::default();
+    if let ExprElem::ItemElem(item_b) = l_expr {
+        assembly_text.push_str(&item_b.generate_wasm()?);
+    } else {
+        return Err(GenerateError::Deverror);
+    }
+    let (from_name, to_name) = (wasm_primitive_type(from), wasm_primitive_type(to));
+    // 整数から変換する場合は変換元の、整数へ変換する場合は変換先の符号の有無に従う
+    let sign = |t: PrimitiveType| if t.is_unsigned() { "u" } else { "s" };
+    let instruction = match (from_name, to_name) {
+        // `i32`と`u32`などwasmの型が同じ場合は何もしない
+        _ if from_name == to_name => return Ok(assembly_text),
+        ("i32", "i64") => format!("extend_i32_{}", sign(from)),
+        ("i64", "i32") => "wrap_i64".to_string(),
+        ("f32", "f64") => "promote_f32".to_string(),
+        ("f64", "f32") => "demote_f64".to_string(),
+        _ if from.is_integer() => format!("convert_{}_{}", from_name, sign(from)),
+        _ => format!("trunc_sat_{}_{}", from_name, sign(to)),
+    };
+    assembly_text.push_str(&format!("{}.{}\n", to_name, instruction));
+    Ok(assembly_text)
+}
+
 /// 前置記法の場合わけが必要なケース("-"の場合)
-fn sub_gen_wasm(l_expr: &ExprElem, r_expr: &ExprElem) -> Result<String, GenerateError> {
+fn sub_gen_wasm(
+    l_expr: &ExprElem,
+    r_expr: &ExprElem,
+    value_type: PrimitiveType,
+) -> Result<String, GenerateError> {
     let mut assembly_text = String::default();
+    let type_name = wasm_primitive_type(value_type);
     if let ExprElem::ItemElem(item_b) = l_expr {
         if item_b.has_no_elem() {
-            assembly_text.push_str("i32.const 0\n");
+            if value_type.is_float() {
+                // `-0.0`を正しく扱うために`neg`を使う
+                if let ExprElem::ItemElem(r_item_b) = r_expr {
+                    assembly_text.push_str(&r_item_b.generate_wasm()?);
+                    assembly_text.push_str(&format!("{}.neg\n", type_name));
+                    return Ok(assembly_text);
+                }
+                return Err(GenerateError::Deverror);
+            }
+            assembly_text.push_str(&format!("{}.const 0\n", type_name));
         } else {
             assembly_text.push_str(&item_b.generate_wasm()?);
         }
@@ -341,7 +1209,7 @@ fn sub_gen_wasm(l_expr: &ExprElem, r_expr: &ExprElem) -> Result<String, Generate
     } else {
         return Err(GenerateError::Deverror);
     }
-    assembly_text.push_str("i32.sub\n");
+    assembly_text.push_str(&format!("{}.sub\n", type_name));
     Ok(assembly_text)
 }
 
@@ -389,12 +1257,13 @@ impl Wasm_gen for ParenBlockBranch {
 
                     // example `(a)`
                     ExprElem::WordElem(word_b) => {
-                        if word_b.self_is_num()? {
-                            assembly_text.push_str(&format!("i32.const {}\n", word_b.contents));
-                        } else {
-                            // もし何らかの変数だった場合
-                            assembly_text.push_str(&format!("local.get ${}\n", word_b.contents));
-                        }
+                        // 変数の場合
+                        assembly_text.push_str(&format!("local.get ${}\n", word_b.contents));
+                    }
+
+                    // example `(1)`
+                    ExprElem::NumElem(num_b) => {
+                        assembly_text.push_str(&num_b.generate_const_wasm());
                     }
 
                     // `((a + 1))`
@@ -402,9 +1271,9 @@ impl Wasm_gen for ParenBlockBranch {
                         assembly_text.push_str(&paren_b.generate_wasm()?);
                     }
 
-                    _ => {
-                        // pass
-                        return Err(GenerateError::Deverror);
+                    // `(p.x)` `(a[0])` `(f())`など
+                    inner => {
+                        assembly_text.push_str(&elem_gen_wasm(inner)?);
                     }
                 }
             }
@@ -426,7 +1295,7 @@ impl Wasm_gen for SyntaxBoxBranch {
                 for section in &self.contents {
                     assembly_text.push_str(&section.generate_wasm("if")?);
                 }
-                for i in 0..count_if_section(&self.contents) {
+                for _ in 0..count_if_section(&self.contents) {
                     assembly_text.push_str("end\n");
                 }
             }
@@ -435,8 +1304,15 @@ impl Wasm_gen for SyntaxBoxBranch {
                     assembly_text.push_str(&section.generate_wasm("while")?);
                 }
             }
+            "loop" => {
+                for section in &self.contents {
+                    assembly_text.push_str(&section.generate_wasm("loop")?);
+                }
+            }
             "for" => {
-                todo!()
+                for section in &self.contents {
+                    assembly_text.push_str(&section.generate_wasm("for")?);
+                }
             }
             _ => {
                 return Err(GenerateError::Deverror);
@@ -471,8 +1347,11 @@ impl SyntaxBranch {
             "while" => {
                 assembly_text.push_str(&wasm_while_gen(self)?);
             }
+            "loop" => {
+                assembly_text.push_str(&wasm_loop_gen(self)?);
+            }
             "for" => {
-                todo!()
+                assembly_text.push_str(&wasm_for_gen(self)?);
             }
             _ => {
                 return Err(GenerateError::Deverror);
@@ -488,10 +1367,8 @@ fn wasm_if_gen(if_state: &SyntaxBranch) -> Result<String, GenerateError> {
     match &*if_state.name {
         "if" => {
             if if_state.expr.is_empty() {
-                // TODO
-                // if の条件式が空はおかしいというerror
-                // を返す
-                todo!()
+                // if の条件式が空はおかしい
+                return Err(GenerateError::EmptyCondition(if_state.span));
             } else if if_state.expr.len() == 1 {
                 if let ExprElem::FuncElem(func_b) = &if_state.expr[0] {
                     // 式を展開
@@ -506,10 +1383,8 @@ fn wasm_if_gen(if_state: &SyntaxBranch) -> Result<String, GenerateError> {
                     // 文をwasmように展開
                     assembly_text.push_str(&wasm_stmt_gen(&if_state.contents)?);
                 } else {
-                    // TODO
-                    // if の条件式が関数ではないのはおかしいというerror
-                    // を返す
-                    todo!()
+                    // if の条件式が関数ではないのはおかしい
+                    return Err(GenerateError::InvalidCondition(if_state.expr[0].get_span()));
                 }
             } else {
                 // ここで、exprelemが複数あるのはおかしい
@@ -518,10 +1393,8 @@ fn wasm_if_gen(if_state: &SyntaxBranch) -> Result<String, GenerateError> {
         }
         "elif" => {
             if if_state.expr.is_empty() {
-                // TODO
-                // else if の条件式が空はおかしいというerror
-                // を返す
-                todo!()
+                // else if の条件式が空はおかしい
+                return Err(GenerateError::EmptyCondition(if_state.span));
             } else if if_state.expr.len() == 1 {
                 if let ExprElem::FuncElem(func_b) = &if_state.expr[0] {
                     // 式を展開
@@ -538,10 +1411,8 @@ fn wasm_if_gen(if_state: &SyntaxBranch) -> Result<String, GenerateError> {
                     // 文をwasmように展開
                     assembly_text.push_str(&wasm_stmt_gen(&if_state.contents)?);
                 } else {
-                    // TODO
-                    // else if の条件式が関数ではないのはおかしいというerror
-                    // を返す
-                    todo!()
+                    // else if の条件式が関数ではないのはおかしい
+                    return Err(GenerateError::InvalidCondition(if_state.expr[0].get_span()));
                 }
             } else {
                 // ここで、exprelemが複数あるのはおかしい
@@ -553,8 +1424,8 @@ fn wasm_if_gen(if_state: &SyntaxBranch) -> Result<String, GenerateError> {
                 assembly_text.push_str("else\n");
                 assembly_text.push_str(&wasm_stmt_gen(&if_state.contents)?);
             } else {
-                // else 説に条件式を設定しているのはおかしいというerror
-                todo!()
+                // else 節に条件式を設定しているのはおかしい
+                return Err(GenerateError::InvalidCondition(if_state.expr[0].get_span()));
             }
         }
         _ => return Err(GenerateError::Deverror),
@@ -575,10 +1446,8 @@ fn wasm_while_gen(while_state: &SyntaxBranch) -> Result<String, GenerateError> {
             assembly_text.push_str(&format!("loop ${}\n", loop_addr));
             assembly_text.push_str(&format!("block ${}\n", block_addr));
             if while_state.expr.is_empty() {
-                // TODO
-                // if の条件式が空はおかしいというerror
-                // を返す
-                todo!()
+                // while の条件式が空はおかしい
+                return Err(GenerateError::EmptyCondition(while_state.span));
             } else if while_state.expr.len() == 1 {
                 if let ExprElem::FuncElem(func_b) = &while_state.expr[0] {
                     // 式を展開
@@ -587,10 +1456,10 @@ fn wasm_while_gen(while_state: &SyntaxBranch) -> Result<String, GenerateError> {
                     // 式を展開
                     assembly_text.push_str(&paren_b.generate_wasm()?);
                 } else {
-                    // TODO
-                    // if の条件式が関数またはカッコではないのはおかしいというerror
-                    // を返す
-                    todo!()
+                    // while の条件式が関数またはカッコではないのはおかしい
+                    return Err(GenerateError::InvalidCondition(
+                        while_state.expr[0].get_span(),
+                    ));
                 }
             } else {
                 // ここで、exprelemが複数あるのはおかしい
@@ -613,21 +1482,472 @@ fn wasm_while_gen(while_state: &SyntaxBranch) -> Result<String, GenerateError> {
     Ok(assembly_text)
 }
 
-fn wasm_stmt_gen(stmt_list: &[StmtElem]) -> Result<String, GenerateError> {
+/// `loop {}`
+/// `break`で`block`を抜け、`continue`と本体の終わりで`loop`の先頭に戻る
+/// ```wat
+/// loop $#l0
+/// block $#b0
+/// ;; 本体
+/// br $#l0
+/// end
+/// end
+/// ```
+fn wasm_loop_gen(loop_state: &SyntaxBranch) -> Result<String, GenerateError> {
+    if !loop_state.expr.is_empty() {
+        // `loop`は条件式を持たない
+        return Err(GenerateError::UnsupportedSyntax(
+            loop_state.expr[0].get_span(),
+        ));
+    }
+    let loop_addr = format!("{}{}", LOOP_ADDR, loop_state.loopdepth);
+    let block_addr = format!("{}{}", BLOCK_ADDR, loop_state.loopdepth);
+    let mut assembly_text = String::default();
+    assembly_text.push_str(&format!("loop ${}\n", loop_addr));
+    assembly_text.push_str(&format!("block ${}\n", block_addr));
+    assembly_text.push_str(&wasm_stmt_gen(&loop_state.contents)?);
+    assembly_text.push_str(&format!("br ${}\n", loop_addr));
+    assembly_text.push_str("end\n");
+    assembly_text.push_str("end\n");
+    Ok(assembly_text)
+}
+
+/// `for (x in iterable) {}`
+/// `break`で外側の`block`を抜け、`continue`で本体の`block`を抜けて次の要素に進む
+/// 配列はアドレスを一度だけ評価して`x.array`に保持し、各反復の先頭で`x.index`番目の要素を読み出す
+/// ```wat
+/// ;; for (i in 0..n)
+/// block $#b0
+/// loop $#f0
+/// ;; i >= i.end ならば抜ける
+/// block $#l0
+/// ;; 本体
+/// end
+/// ;; i += 1
+/// br $#f0
+/// end
+/// end
+/// ```
+fn wasm_for_gen(for_state: &SyntaxBranch) -> Result<String, GenerateError> {
+    let (binding, iterable) =
+        for_parts(&for_state.expr).ok_or(GenerateError::InvalidForLoop(for_state.span))?;
+    let for_addr = format!("{}{}", FOR_ADDR, for_state.loopdepth);
+    let loop_addr = format!("{}{}", LOOP_ADDR, for_state.loopdepth);
+    let block_addr = format!("{}{}", BLOCK_ADDR, for_state.loopdepth);
+    let name = &binding.contents;
+    let mut assembly_text = String::default();
+
+    // ループの前の初期化と各反復の先頭の処理
+    let (header, next) = if let Some((ope_b, start, end)) = range_parts(iterable) {
+        let value_type = ope_b.ope_type.ok_or(GenerateError::Deverror)?;
+        let t = wasm_primitive_type(value_type);
+        let sign = if value_type.is_unsigned() { "u" } else { "s" };
+        let end_name = format!("{}{}", name, FOR_END_SUFFIX);
+        assembly_text.push_str(&elem_gen_wasm(start)?);
+        assembly_text.push_str(&format!(
+            "local.set ${}
+",
+            name
+        ));
+        assembly_text.push_str(&elem_gen_wasm(end)?);
+        assembly_text.push_str(&format!(
+            "local.set ${}
+",
+            end_name
+        ));
+        assembly_text.push_str(&format!(
+            "block ${}
+",
+            block_addr
+        ));
+        let compare = format!(
+            "local.get ${}
+local.get ${}
+",
+            name, end_name
+        );
+        let increment = format!(
+            "local.get ${name}
+{t}.const 1
+{t}.add
+local.set ${name}
+",
+            name = name
+        );
+        if ope_b.ope == "..=" {
+            // 終わりの値を含む場合は、加算であふれないように本体の後で終わりの値と比較する
+            assembly_text.push_str(&format!(
+                "{}{}.gt_{}
+br_if ${}
+",
+                compare, t, sign, block_addr
+            ));
+            (
+                String::new(),
+                format!(
+                    "{}{}.eq
+br_if ${}
+{}",
+                    compare, t, block_addr, increment
+                ),
+            )
+        } else {
+            (
+                format!(
+                    "{}{}.ge_{}
+br_if ${}
+",
+                    compare, t, sign, block_addr
+                ),
+                increment,
+            )
+        }
+    } else if let Some((element_type, element_size, length)) = for_state.iter_array {
+        let array_name = format!("{}{}", name, FOR_ARRAY_SUFFIX);
+        let index_name = format!("{}{}", name, FOR_INDEX_SUFFIX);
+        assembly_text.push_str(&elem_gen_wasm(iterable)?);
+        assembly_text.push_str(&format!(
+            "local.set ${}\ni32.const 0\nlocal.set ${}\n",
+            array_name, index_name
+        ));
+        assembly_text.push_str(&format!("block ${}\n", block_addr));
+        let header = format!(
+            "local.get ${index}\ni32.const {}\ni32.ge_u\nbr_if ${}\n\
+             local.get ${array}\nlocal.get ${index}\ni32.const {}\ni32.mul\ni32.add\n\
+             {}.load offset=0\nlocal.set ${}\n",
+            length,
+            block_addr,
+            element_size,
+            wasm_primitive_type(element_type),
+            name,
+            index = index_name,
+            array = array_name,
+        );
+        (
+            header,
+            format!(
+                "local.get ${name}\ni32.const 1\ni32.add\nlocal.set ${name}\n",
+                name = index_name
+            ),
+        )
+    } else {
+        return Err(GenerateError::NotIterable {
+            found: String::from("()"),
+            span: iterable.get_span(),
+        });
+    };
+
+    assembly_text.push_str(&format!(
+        "loop ${}
+",
+        for_addr
+    ));
+    assembly_text.push_str(&header);
+    assembly_text.push_str(&format!(
+        "block ${}
+",
+        loop_addr
+    ));
+    assembly_text.push_str(&wasm_stmt_gen(&for_state.contents)?);
+    assembly_text.push_str(
+        "end
+",
+    );
+    assembly_text.push_str(&next);
+    assembly_text.push_str(&format!(
+        "br ${}
+",
+        for_addr
+    ));
+    assembly_text.push_str(
+        "end
+",
+    );
+    assembly_text.push_str(
+        "end
+",
+    );
+    Ok(assembly_text)
+}
+
+impl MatchBranch {
+    /// 検査する値を隠れた変数に保持し、値を返す`block`の中で腕に分岐する
+    /// `i32` `u32`の値を密に網羅する整数のパターンは`br_table`で、それ以外は比較の連鎖で分岐する
+    /// 列挙型の値はタグで分岐し、ヴァリアントの値は腕の先頭で変数に読み出す
+    /// どの腕にも一致しなかった場合、値を持つ`match`は`unreachable`になる
+    pub fn generate_wasm(&self) -> Result<String, GenerateError> {
+        let expr_type = self.expr_type.ok_or(GenerateError::Deverror)?;
+        let [expr] = &self.expr[..] else {
+            return Err(GenerateError::EmptyCondition(self.span));
+        };
+        let match_addr = format!("{}{}", MATCH_ADDR, self.depth);
+        let mut assembly_text = String::default();
+        assembly_text.push_str(&elem_gen_wasm(expr)?);
+        assembly_text.push_str(&format!("local.set ${}\n", self.local));
+        match self.result_type {
+            Some(t) => assembly_text.push_str(&format!(
+                "block ${} (result {})\n",
+                match_addr,
+                wasm_primitive_type(t)
+            )),
+            None => assembly_text.push_str(&format!("block ${}\n", match_addr)),
+        }
+
+        // `_`より後の腕には到達しない
+        let wildcard = self
+            .arms
+            .iter()
+            .position(|arm| matches!(Pattern::classify(&arm.pattern), Some(Pattern::Wildcard)));
+        let arms = &self.arms[..wildcard.unwrap_or(self.arms.len())];
+        match self.br_table(arms, expr_type)? {
+            Some((start, table)) => {
+                assembly_text.push_str(&self.br_table_gen_wasm(arms, start, &table)?)
+            }
+            None => {
+                for arm in arms {
+                    assembly_text.push_str(&self.pattern_gen_wasm(arm, expr_type)?);
+                    assembly_text.push_str("if\n");
+                    assembly_text.push_str(&self.arm_gen_wasm(arm)?);
+                    assembly_text.push_str(&format!("br ${}\n", match_addr));
+                    assembly_text.push_str("end\n");
+                }
+            }
+        }
+        match wildcard {
+            Some(index) => assembly_text.push_str(&self.arm_gen_wasm(&self.arms[index])?),
+            None if self.result_type.is_some() => assembly_text.push_str("unreachable\n"),
+            None => {}
+        }
+        assembly_text.push_str("end\n");
+        Ok(assembly_text)
+    }
+
+    /// `br_table`で分岐できる場合は表の先頭の値と、値ごとに分岐する腕の番号の表を返却する
+    /// どの腕にも一致しない値は`arms.len()`に分岐する
+    fn br_table(
+        &self,
+        arms: &[MatchArmBranch],
+        expr_type: PrimitiveType,
+    ) -> Result<Option<(i128, Vec<usize>)>, GenerateError> {
+        // ヴァリアントが持つ値を検査する腕は、同じタグの後の腕に分岐することがある
+        if !matches!(expr_type, PrimitiveType::I32 | PrimitiveType::U32)
+            || arms.len() < BR_TABLE_MIN_ARMS
+            || arms.iter().any(|arm| !arm.tests.is_empty())
+        {
+            return Ok(None);
+        }
+        let mut ranges = Vec::new();
+        for arm in arms {
+            let range = match arm.tag {
+                Some(tag) => (tag as i128, tag as i128),
+                None => Pattern::classify(&arm.pattern)
+                    .and_then(|pattern| pattern.int_range())
+                    .ok_or(GenerateError::Deverror)?,
+            };
+            ranges.push(range);
+        }
+        let start = ranges.iter().map(|(low, _)| *low).min().unwrap_or_default();
+        let end = ranges
+            .iter()
+            .map(|(_, high)| *high)
+            .max()
+            .unwrap_or_default();
+        let size = end - start + 1;
+        if size > BR_TABLE_MAX_SIZE {
+            return Ok(None);
+        }
+        let table: Vec<usize> = (start..=end)
+            .map(|value| {
+                ranges
+                    .iter()
+                    .position(|(low, high)| (*low..=*high).contains(&value))
+                    .unwrap_or(arms.len())
+            })
+            .collect();
+        // 表の半分以上がどれかの腕に分岐する場合のみ密とみなす
+        let covered = table.iter().filter(|&&arm| arm < arms.len()).count();
+        if covered * 2 < table.len() {
+            return Ok(None);
+        }
+        Ok(Some((start, table)))
+    }
+
+    /// ```wat
+    /// block $#m0.2 ;; どの腕にも一致しない場合
+    /// block $#m0.1
+    /// block $#m0.0
+    /// local.get $#match
+    /// i32.const 1 ;; 表の先頭の値
+    /// i32.sub
+    /// br_table $#m0.0 $#m0.1 $#m0.2
+    /// end
+    /// ;; 腕0
+    /// br $#m0
+    /// end
+    /// ;; 腕1
+    /// br $#m0
+    /// end
+    /// ```
+    fn br_table_gen_wasm(
+        &self,
+        arms: &[MatchArmBranch],
+        start: i128,
+        table: &[usize],
+    ) -> Result<String, GenerateError> {
+        let match_addr = format!("{}{}", MATCH_ADDR, self.depth);
+        let arm_addr = |index: usize| format!("${}.{}", match_addr, index);
+        let mut assembly_text = String::default();
+        for index in (0..=arms.len()).rev() {
+            assembly_text.push_str(&format!("block {}\n", arm_addr(index)));
+        }
+        assembly_text.push_str(&self.value_gen_wasm());
+        assembly_text.push_str(&format!("i32.const {}\n", start as i32));
+        assembly_text.push_str("i32.sub\n");
+        let targets: Vec<String> = table
+            .iter()
+            .chain(std::iter::once(&arms.len()))
+            .map(|&index| arm_addr(index))
+            .collect();
+        assembly_text.push_str(&format!("br_table {}\n", targets.join(" ")));
+        for arm in arms {
+            assembly_text.push_str("end\n");
+            assembly_text.push_str(&self.arm_gen_wasm(arm)?);
+            assembly_text.push_str(&format!("br ${}\n", match_addr));
+        }
+        assembly_text.push_str("end\n");
+        Ok(assembly_text)
+    }
+
+    /// 検査する値が腕のパターンに一致する場合に`1`を積む
+    fn pattern_gen_wasm(
+        &self,
+        arm: &MatchArmBranch,
+        expr_type: PrimitiveType,
+    ) -> Result<String, GenerateError> {
+        let t = wasm_primitive_type(expr_type);
+        let get = self.value_gen_wasm();
+        match Pattern::classify(&arm.pattern).ok_or(GenerateError::Deverror)? {
+            Pattern::Literal(literal) => {
+                Ok(format!("{}{}{}.eq\n", get, elem_gen_wasm(literal)?, t))
+            }
+            Pattern::Range(ope_b, start, end) => {
+                range_pattern_gen_wasm(&get, &ope_b.ope, start, end, expr_type)
+            }
+            Pattern::Variant(..) => {
+                let tag = arm.tag.ok_or(GenerateError::Deverror)?;
+                let mut tests = vec![format!("{}i32.const {}\ni32.eq\n", get, tag)];
+                for test in &arm.tests {
+                    tests.push(self.field_test_gen_wasm(test)?);
+                }
+                // 前の検査に一致した場合のみ次の値を読み出す
+                let last = tests.pop().unwrap_or_default();
+                Ok(tests.into_iter().rev().fold(last, |inner, test| {
+                    format!("{}if (result i32)\n{}else\ni32.const 0\nend\n", test, inner)
+                }))
+            }
+            Pattern::Wildcard | Pattern::Name(_) => Err(GenerateError::Deverror),
+        }
+    }
+
+    /// ヴァリアントが持つ値がパターンに一致する場合に`1`を積む
+    fn field_test_gen_wasm(&self, test: &FieldTest) -> Result<String, GenerateError> {
+        let get = self.field_gen_wasm(&test.path, test.value_type);
+        let t = wasm_primitive_type(test.value_type);
+        match &test.kind {
+            FieldTestKind::Tag(tag) => Ok(format!("{}i32.const {}\ni32.eq\n", get, tag)),
+            FieldTestKind::Literal(literal) => {
+                Ok(format!("{}{}{}.eq\n", get, elem_gen_wasm(literal)?, t))
+            }
+            FieldTestKind::Range(ope, start, end) => {
+                range_pattern_gen_wasm(&get, ope, start, end, test.value_type)
+            }
+        }
+    }
+
+    /// 検査する値のレコードから`path`のオフセットで順にアドレスを読み出し、最後に値を読み出す
+    fn field_gen_wasm(&self, path: &[u32], value_type: PrimitiveType) -> String {
+        let mut assembly_text = format!("local.get ${}\n", self.local);
+        for (i, offset) in path.iter().enumerate() {
+            let t = if i + 1 == path.len() {
+                wasm_primitive_type(value_type)
+            } else {
+                "i32"
+            };
+            assembly_text.push_str(&format!("{}.load offset={}\n", t, offset));
+        }
+        assembly_text
+    }
+
+    /// 分岐に使う値を積む
+    /// 値を持つ列挙型の場合はレコードの先頭のタグを読み出す
+    fn value_gen_wasm(&self) -> String {
+        if self.boxed {
+            format!("local.get ${}\ni32.load offset=0\n", self.local)
+        } else {
+            format!("local.get ${}\n", self.local)
+        }
+    }
+
+    /// 腕の値を積む
+    /// パターンで束縛した変数にヴァリアントの値を読み出してから、腕を展開する
+    /// `{}`の場合は文として展開する
+    fn arm_gen_wasm(&self, arm: &MatchArmBranch) -> Result<String, GenerateError> {
+        let mut assembly_text = String::default();
+        for (name, path, field_type) in &arm.bindings {
+            assembly_text.push_str(&self.field_gen_wasm(path, *field_type));
+            assembly_text.push_str(&format!("local.set ${}\n", name));
+        }
+        assembly_text.push_str(&match &arm.contents[..] {
+            [ExprElem::BlockElem(block_b)] => wasm_stmt_gen(&block_b.contents)?,
+            [ExprElem::SyntaxBoxElem(syntax_box_b)] => syntax_box_b.generate_wasm()?,
+            [inner] => elem_gen_wasm(inner)?,
+            _ => return Err(GenerateError::Deverror),
+        });
+        Ok(assembly_text)
+    }
+}
+
+/// 値が範囲のパターン`1..10` `1..=9`に一致する場合に`1`を積む
+/// `get`は値を積む命令
+fn range_pattern_gen_wasm(
+    get: &str,
+    ope: &str,
+    start: &ExprElem,
+    end: &ExprElem,
+    value_type: PrimitiveType,
+) -> Result<String, GenerateError> {
+    let t = wasm_primitive_type(value_type);
+    let sign = if value_type.is_float() {
+        ""
+    } else if value_type.is_unsigned() {
+        "_u"
+    } else {
+        "_s"
+    };
+    let end_ope = if ope == "..=" { "le" } else { "lt" };
+    Ok(format!(
+        "{get}{}{t}.ge{sign}\n{get}{}{t}.{}{sign}\ni32.and\n",
+        elem_gen_wasm(start)?,
+        elem_gen_wasm(end)?,
+        end_ope,
+    ))
+}
+
+pub fn wasm_stmt_gen(stmt_list: &[StmtElem]) -> Result<String, GenerateError> {
     let mut assembly_text = String::default();
     for s in stmt_list {
         if let StmtElem::ExprElem(expr_b) = s {
             assembly_text.push_str(&expr_b.generate_wasm()?);
         } else if let StmtElem::Special(control_b) = s {
             assembly_text.push_str(&control_b.generate_wasm()?);
-        } else if let StmtElem::CommentElem(comment_b) = s {
+        } else if let StmtElem::CommentElem(_) = s {
             // pass
             assembly_text.push_str("");
+        } else if let StmtElem::DecFuncElem(_) = s {
+            // 関数の中で宣言された関数はモジュールの関数として生成される
+            // pass
         } else {
             // これ以外のわたしが認識していない場合
-            // コメントだった場合について実装する
-            //
-            todo!()
+            return Err(GenerateError::UnsupportedStatement(s.get_span()));
         }
     }
     Ok(assembly_text)
@@ -1,5 +1,7 @@
+use crate::abs::ast::merge_span;
 use crate::abs::ast::ASTAreaBranch;
 use crate::abs::ast::ExprElem;
+use crate::abs::span::Span;
 
 use crate::abs::ast::ProcToken;
 use crate::abs::ast::Token;
@@ -53,6 +55,11 @@ impl CommaParser {
                         contents: group.clone(),
                         depth: self.depth,
                         loopdepth: self.loopdepth,
+                        span: if group.is_empty() {
+                            v.span.head()
+                        } else {
+                            Self::merge_args_span(&group)
+                        },
                     }));
                     group.clear();
                 } else {
@@ -67,17 +74,31 @@ impl CommaParser {
                 contents: group.clone(),
                 depth: self.depth,
                 loopdepth: self.loopdepth,
+                span: Self::merge_args_span(&group),
             }));
         }
         self.code_list = rlist;
         Ok(())
     }
 
+    /// 引数の前後の空白を除いたspanを返却する
+    fn merge_args_span(group: &[ExprElem]) -> Span {
+        let args: Vec<ExprElem> = group
+            .iter()
+            .filter(|inner| {
+                !matches!(inner, ExprElem::UnKnownElem(v) if Self::SPLIT_CHAR.contains(&v.contents))
+            })
+            .cloned()
+            .collect();
+        merge_span(&args)
+    }
+
     fn grouping_quotation(&mut self) -> Result<(), ParserError> {
         let mut open_flag = false;
         let mut escape_flag = false;
         let mut rlist = Vec::new();
         let mut group = String::new();
+        let mut open_span: Span = Span::default();
 
         for inner in &self.code_list {
             if let ExprElem::UnKnownElem(ref v) = inner {
@@ -94,11 +115,13 @@ impl CommaParser {
                             contents: group.clone(),
                             depth: self.depth,
                             loopdepth: self.loopdepth,
+                            span: open_span.merge(&v.span),
                         }));
                         group.clear();
                         open_flag = false;
                     } else {
                         group.push(v.contents);
+                        open_span = v.span;
                         open_flag = true;
                     }
                 } else if open_flag {
@@ -112,7 +135,10 @@ impl CommaParser {
             }
         }
         if open_flag {
-            return Err(ParserError::QuotationNotClosed);
+            return Err(ParserError::QuotationNotClosed {
+                open: open_span,
+                end: Self::closing_span(&self.code_list, open_span),
+            });
         }
         self.code_list = rlist;
         Ok(())
@@ -131,35 +157,40 @@ impl CommaParser {
         let mut rlist: Vec<ExprElem> = Vec::new();
         let mut group: Vec<U> = Vec::new();
         let mut depth: isize = 0;
+        // 開いている括弧の位置(入れ子の場合は内側ほど後ろ)
+        let mut open_spans: Vec<Span> = Vec::new();
 
         for inner in &self.code_list {
             if let ExprElem::UnKnownElem(ref b) = inner {
                 if b.contents == open_char {
+                    open_spans.push(b.span);
                     match depth {
                         0 => { /*pass*/ }
-                        1.. => group.push(Token::set_char_as_unknown(b.contents)),
-                        _ => return Err(ParserError::Uncategorized),
+                        1.. => group.push(Token::set_char_as_unknown(b.contents, b.span)),
+                        _ => return Err(ParserError::BraceNotOpened(b.span)),
                     }
                     depth += 1;
                 } else if b.contents == close_char {
                     depth -= 1;
+                    let open_span = open_spans.pop().unwrap_or_default();
                     match depth {
                         0 => {
                             rlist.push(elemtype(ASTAreaBranch::new(
                                 group.clone(),
                                 self.depth,
                                 self.loopdepth,
+                                open_span.merge(&b.span),
                             )));
                             group.clear();
                         }
-                        1.. => group.push(Token::set_char_as_unknown(b.contents)),
-                        _ => return Err(ParserError::Uncategorized),
+                        1.. => group.push(Token::set_char_as_unknown(b.contents, b.span)),
+                        _ => return Err(ParserError::BraceNotOpened(b.span)),
                     }
                 } else {
                     match depth {
                         0 => rlist.push(inner.clone()),
-                        1.. => group.push(Token::set_char_as_unknown(b.contents)),
-                        _ => return Err(ParserError::Uncategorized),
+                        1.. => group.push(Token::set_char_as_unknown(b.contents, b.span)),
+                        _ => return Err(ParserError::BraceNotOpened(b.span)),
                     }
                 }
             } else {
@@ -172,6 +203,7 @@ impl CommaParser {
                                     s.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    s.span,
                                 ));
                             }
                             ExprElem::BlockElem(bl) => {
@@ -179,6 +211,7 @@ impl CommaParser {
                                     bl.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    bl.span,
                                 ));
                             }
                             ExprElem::ParenBlockElem(pb) => {
@@ -186,6 +219,7 @@ impl CommaParser {
                                     pb.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    pb.span,
                                 ));
                             }
                             ExprElem::ListBlockElem(lb) => {
@@ -193,21 +227,33 @@ impl CommaParser {
                                     lb.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    lb.span,
                                 ));
                             }
-                            // todo
+                            // 回復で生成したエラーの要素はそのまま残し、元のエラーを報告する
+                            ExprElem::ErrorElem(eb) => group.push(ProcToken::t_error(eb.clone())),
                             _ => {
-                                // todo error処理
-                                return Err(ParserError::UnexpectedTypeComma);
+                                return Err(ParserError::UnexpectedTypeComma(inner.get_span()));
                             }
                         }
                     }
-                    _ => return Err(ParserError::BraceNotClosed),
+                    _ => {
+                        return Err(ParserError::BraceNotClosed {
+                            open: inner.get_span(),
+                            end: inner.get_span(),
+                            close: close_char,
+                        })
+                    }
                 }
             }
         }
         if depth != 0 {
-            return Err(ParserError::BraceNotClosed);
+            let open_span = open_spans.last().copied().unwrap_or_default();
+            return Err(ParserError::BraceNotClosed {
+                open: open_span,
+                end: Self::closing_span(&self.code_list, open_span),
+                close: close_char,
+            });
         }
         self.code_list = rlist;
         Ok(())
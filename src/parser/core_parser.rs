@@ -2,6 +2,7 @@ use std::fmt::Debug;
 
 // use crate::parser::token::*;
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 use crate::errors::parser_errors::ParserError;
 
 pub enum Prio {
@@ -31,12 +32,17 @@ pub enum OpeTable {
     ARROW, // 矢印
     OR,
     AND,
+    BITOR,
+    BITXOR,
+    BITAND,
     EQ,
     NE,
     LT,
     LE,
     GT,
     GE,
+    SHL,
+    SHR,
     ADD,
     SUB,
     MUL,
@@ -49,22 +55,37 @@ pub enum OpeTable {
     MULEQ,
     DIVEQ,
     MODEQ,
+    BITANDEQ,
+    BITOREQ,
+    BITXOREQ,
+    SHLEQ,
+    SHREQ,
     POW,
     NOT,
+    BITNOT,
+    AS,
+    IN,
+    RANGE,
+    RANGEINCLUSIVE,
 }
 
 impl OpeTable {
     pub fn set(s: &str) -> Result<Self, &str> {
         match s {
-            "->" => Ok(Self::OR),
+            "->" => Ok(Self::ARROW),
             "||" => Ok(Self::OR),
             "&&" => Ok(Self::AND),
+            "|" => Ok(Self::BITOR),
+            "^" => Ok(Self::BITXOR),
+            "&" => Ok(Self::BITAND),
             "==" => Ok(Self::EQ),
             "!=" => Ok(Self::NE),
             "<" => Ok(Self::LT),
             "<=" => Ok(Self::LE),
             ">" => Ok(Self::GT),
             ">=" => Ok(Self::GE),
+            "<<" => Ok(Self::SHL),
+            ">>" => Ok(Self::SHR),
             "+" => Ok(Self::ADD),
             "-" => Ok(Self::SUB),
             "*" => Ok(Self::MUL),
@@ -77,8 +98,18 @@ impl OpeTable {
             "*=" => Ok(Self::MULEQ),
             "/=" => Ok(Self::DIVEQ),
             "%=" => Ok(Self::MODEQ),
+            "&=" => Ok(Self::BITANDEQ),
+            "|=" => Ok(Self::BITOREQ),
+            "^=" => Ok(Self::BITXOREQ),
+            "<<=" => Ok(Self::SHLEQ),
+            ">>=" => Ok(Self::SHREQ),
             "**" => Ok(Self::POW),
             "!" => Ok(Self::NOT),
+            "~" => Ok(Self::BITNOT),
+            "as" => Ok(Self::AS),
+            "in" => Ok(Self::IN),
+            ".." => Ok(Self::RANGE),
+            "..=" => Ok(Self::RANGEINCLUSIVE),
             _ => Err("Invalid Operation String"),
         }
     }
@@ -88,12 +119,17 @@ impl OpeTable {
 /// パーサのコア実装
 pub trait Parser<'a> {
     // operators
+    // 優先順位はCと同様に
+    // 代入 < `->` < `in` < `..` < `||` < `&&` < `|` < `^` < `&` < 比較 < シフト < `+` < `*` < `**` < `as`
     // - left priority
-    //   - priority -3
-    def_ope!(ARROW, "->", Prio::Right, -4);
-    def_ope!(OR, "||", Prio::Left, -3);
-    //   - priority -2
-    def_ope!(AND, "&&", Prio::Left, -2);
+    //   - priority -6
+    def_ope!(OR, "||", Prio::Left, -6);
+    //   - priority -5
+    def_ope!(AND, "&&", Prio::Left, -5);
+    //   - priority -4 ~ -2
+    def_ope!(BITOR, "|", Prio::Left, -4);
+    def_ope!(BITXOR, "^", Prio::Left, -3);
+    def_ope!(BITAND, "&", Prio::Left, -2);
     //   - priority 0
     def_ope!(EQ, "==", Prio::Left, 0);
     def_ope!(NE, "!=", Prio::Left, 0);
@@ -102,44 +138,77 @@ pub trait Parser<'a> {
     def_ope!(GT, ">", Prio::Left, 0);
     def_ope!(GE, ">=", Prio::Left, 0);
     //   - priority 1
-    def_ope!(ADD, "+", Prio::Left, 1);
-    def_ope!(SUB, "-", Prio::Left, 1);
-    def_ope!(MUL, "*", Prio::Left, 2);
-    def_ope!(DIV, "/", Prio::Left, 2);
-    def_ope!(MOD, "%", Prio::Left, 2);
-    def_ope!(DOT, "@", Prio::Left, 2);
+    def_ope!(SHL, "<<", Prio::Left, 1);
+    def_ope!(SHR, ">>", Prio::Left, 1);
+    //   - priority 2
+    def_ope!(ADD, "+", Prio::Left, 2);
+    def_ope!(SUB, "-", Prio::Left, 2);
+    //   - priority 3
+    def_ope!(MUL, "*", Prio::Left, 3);
+    def_ope!(DIV, "/", Prio::Left, 3);
+    def_ope!(MOD, "%", Prio::Left, 3);
+    def_ope!(DOT, "@", Prio::Left, 3);
+
+    //   - priority -8
+    def_ope!(IN, "in", Prio::Left, -8);
+    //   - priority -7
+    def_ope!(RANGE, "..", Prio::Left, -7);
+    def_ope!(RANGEINCLUSIVE, "..=", Prio::Left, -7);
 
     // - right priority
-    //   - priority -4
-    def_ope!(ASSIGNMENT, "=", Prio::Right, -5);
-    def_ope!(ADDEQ, "+=", Prio::Right, -5);
-    def_ope!(SUBEQ, "-=", Prio::Right, -5);
-    def_ope!(MULEQ, "*=", Prio::Right, -5);
-    def_ope!(DIVEQ, "/=", Prio::Right, -5);
-    def_ope!(MODEQ, "%=", Prio::Right, -5);
-    //   - priority -3
-    def_ope!(POW, "**", Prio::Right, 3);
+    //   - priority -10
+    def_ope!(ASSIGNMENT, "=", Prio::Right, -10);
+    def_ope!(ADDEQ, "+=", Prio::Right, -10);
+    def_ope!(SUBEQ, "-=", Prio::Right, -10);
+    def_ope!(MULEQ, "*=", Prio::Right, -10);
+    def_ope!(DIVEQ, "/=", Prio::Right, -10);
+    def_ope!(MODEQ, "%=", Prio::Right, -10);
+    def_ope!(BITANDEQ, "&=", Prio::Right, -10);
+    def_ope!(BITOREQ, "|=", Prio::Right, -10);
+    def_ope!(BITXOREQ, "^=", Prio::Right, -10);
+    def_ope!(SHLEQ, "<<=", Prio::Right, -10);
+    def_ope!(SHREQ, ">>=", Prio::Right, -10);
+    //   - priority -9
+    def_ope!(ARROW, "->", Prio::Right, -9);
+    //   - priority 4
+    def_ope!(POW, "**", Prio::Right, 4);
 
     // - prefix priority
     //   - priority -1
     def_ope!(NOT, "!", Prio::Prefix, -1);
+    def_ope!(BITNOT, "~", Prio::Prefix, -1);
+
+    // - word operators
+    //   - priority 5 (前置演算子よりも優先される)
+    def_ope!(AS, "as", Prio::Left, 5);
+
+    /// 式の先頭にある前置演算子の優先順位
+    const PREFIX_PRIORITY: i32 = 5;
 
     /// 演算子を文字列として長いものからの順番で並べたもの
-    const LENGTH_ORDER_OPE_LIST: [&'a Ope<'a>; 23] = [
+    const LENGTH_ORDER_OPE_LIST: [&'a Ope<'a>; 34] = [
+        // length 3
+        Self::SHLEQ, // <<=
+        Self::SHREQ, // >>=
         // length 2
         Self::ARROW,
-        Self::OR,    // ||
-        Self::AND,   // &&
-        Self::EQ,    // ==
-        Self::NE,    // !=
-        Self::LE,    // <=
-        Self::GE,    // >=
-        Self::ADDEQ, // +=
-        Self::SUBEQ, // -=
-        Self::MULEQ, // *=
-        Self::DIVEQ, // /=
-        Self::MODEQ, // %=
-        Self::POW,   // **
+        Self::OR,       // ||
+        Self::AND,      // &&
+        Self::EQ,       // ==
+        Self::NE,       // !=
+        Self::LE,       // <=
+        Self::GE,       // >=
+        Self::SHL,      // <<
+        Self::SHR,      // >>
+        Self::ADDEQ,    // +=
+        Self::SUBEQ,    // -=
+        Self::MULEQ,    // *=
+        Self::DIVEQ,    // /=
+        Self::MODEQ,    // %=
+        Self::BITANDEQ, // &=
+        Self::BITOREQ,  // |=
+        Self::BITXOREQ, // ^=
+        Self::POW,      // **
         // length 1
         Self::LT,         // <
         Self::GT,         // >
@@ -149,8 +218,22 @@ pub trait Parser<'a> {
         Self::DIV,        // /
         Self::MOD,        // %
         Self::DOT,        // @
+        Self::BITAND,     // &
+        Self::BITOR,      // |
+        Self::BITXOR,     // ^
         Self::ASSIGNMENT, // =
         Self::NOT,        // !
+        Self::BITNOT,     // ~
+    ];
+
+    /// 単語として現れる演算子
+    /// `LENGTH_ORDER_OPE_LIST`に含めると単語の区切りとして扱われてしまうため分けている
+    /// (`.`を区切りにすると`1.5`が分割されてしまう)
+    const WORD_OPE_LIST: [&'a Ope<'a>; 4] = [
+        Self::AS,             // as
+        Self::IN,             // in
+        Self::RANGE,          // ..
+        Self::RANGEINCLUSIVE, // ..=
     ];
 
     // comment
@@ -210,7 +293,7 @@ pub trait Parser<'a> {
         Self::CONTROL_LETMUT,   // let mut
     ];
 
-    const KEYWORDS: [&'a str; 16] = [
+    const KEYWORDS: [&'a str; 17] = [
         // Syntax
         Self::SYNTAX_IF,    // if
         Self::SYNTAX_ELIF,  // elif
@@ -220,9 +303,10 @@ pub trait Parser<'a> {
         Self::SYNTAX_WHILE, // while
         Self::SYNTAX_MATCH, // match
         // keyword
-        Self::FUNCTION,    // fn
-        Self::STRUCTURE,   // struct
-        Self::ENUMERATION, // enum
+        Self::FUNCTION,     // fn
+        Self::PUB_FUNCTION, // pub_fn
+        Self::STRUCTURE,    // struct
+        Self::ENUMERATION,  // enum
         // control
         Self::CONTROL_RETURN,   // return
         Self::CONTROL_BREAK,    // break
@@ -244,18 +328,48 @@ pub trait Parser<'a> {
 
     fn new(code: String, depth: isize, loopdepth: isize) -> Self;
     fn resolve(&mut self) -> Result<(), ParserError>;
+    /// ソースコードを一文字ずつ未定tokenに変換する
+    /// それぞれのtokenにはソース上の位置(`Span`)を付与する
     fn code2_vec_pre_proc_func<T>(code: &str) -> Vec<T>
     where
         T: Token + Clone + Debug,
     {
-        return code
-            .chars()
-            .map(|c| Token::set_char_as_unknown(c))
-            .collect();
+        let mut line = 1;
+        let mut column = 1;
+        let mut rlist = Vec::new();
+        for (offset, c) in code.char_indices() {
+            rlist.push(Token::set_char_as_unknown(
+                c,
+                Span::new(offset, offset + c.len_utf8(), line, column),
+            ));
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        rlist
     }
 
-    fn find_ope_priority(ope: &'a str) -> Result<&Ope, &'a str> {
-        for i in Self::LENGTH_ORDER_OPE_LIST {
+    /// 閉じ括弧が必要な位置(最後の空白でない要素の直後)
+    fn closing_span<T>(code_list: &[T], open_span: Span) -> Span
+    where
+        T: Token,
+    {
+        code_list
+            .iter()
+            .rev()
+            .find(|inner| !inner.is_whitespace())
+            .map_or(open_span, |inner| inner.get_span())
+            .tail()
+    }
+
+    fn find_ope_priority(ope: &'a str) -> Result<&'a Ope<'a>, &'a str> {
+        for i in Self::LENGTH_ORDER_OPE_LIST
+            .iter()
+            .chain(&Self::WORD_OPE_LIST)
+        {
             if i.opestr == ope {
                 return Ok(i);
             }
@@ -263,3 +377,22 @@ pub trait Parser<'a> {
         Err("ope not exist")
     }
 }
+
+/// 回復可能なエラーを処理する
+/// 回復モード(`sink`が`Some`)のときはエラーを記録して解析を続け、
+/// そうでないときは最初のエラーを返却する
+pub fn report_errors(
+    sink: &mut Option<Vec<ParserError>>,
+    errors: Vec<ParserError>,
+) -> Result<(), ParserError> {
+    match sink {
+        Some(v) => {
+            v.extend(errors);
+            Ok(())
+        }
+        None => match errors.into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        },
+    }
+}
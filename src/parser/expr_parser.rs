@@ -1,17 +1,24 @@
 use std::cmp::Ordering;
 
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 use crate::parser::core_parser::*;
 
 use crate::errors::parser_errors::ParserError;
 
+use crate::parser::stmt_parser::StmtParser;
 use crate::token::comment::CommentBranch;
+use crate::token::error::ErrorBranch;
+use crate::token::field::FieldBranch;
 use crate::token::func::FuncBranch;
 use crate::token::item::ItemBranch;
 use crate::token::list::ListBranch;
+use crate::token::match_syntax::MatchBranch;
+use crate::token::num::NumBranch;
 use crate::token::operator::OperatorBranch;
 use crate::token::paren_block::ParenBlockBranch;
 use crate::token::string::StringBranch;
+use crate::token::structure::StructBranch;
 use crate::token::syntax::SyntaxBranch;
 use crate::token::syntax_box::SyntaxBoxBranch;
 use crate::token::word::WordBranch;
@@ -23,6 +30,9 @@ pub struct ExprParser {
     pub code_list: Vec<ExprElem>,
     pub depth: isize,
     pub loopdepth: isize,
+    /// 回復モードで収集したエラー
+    /// `None`のときは最初のエラーで解析を中断する
+    pub errors: Option<Vec<ParserError>>,
 }
 
 enum StringAreaState {
@@ -53,10 +63,14 @@ impl ExprParser {
         )?;
         // end of grouping_elements
         self.grouping_words()?;
+        self.grouping_ranges();
+        self.grouping_numbers()?;
+        self.grouping_fields();
 
         // grouping syntax
         self.grouping_syntax()?;
         self.grouping_syntaxbox()?;
+        self.grouping_structs()?;
 
         while self.contain_subscriptable() {
             self.grouping_subscription()?;
@@ -69,16 +83,18 @@ impl ExprParser {
     fn grouping_words(&mut self) -> Result<(), ParserError> {
         // macro
         macro_rules! add_rlist {
-            ($rlist:expr,$group:expr) => {
+            ($rlist:expr,$group:expr,$group_span:expr) => {
                 $rlist.push(ExprElem::WordElem(WordBranch {
                     contents: $group.clone(),
                     depth: self.depth,
                     loopdepth: self.loopdepth,
+                    span: $group_span,
                 }));
             };
         }
         let mut rlist: Vec<ExprElem> = Vec::new();
         let mut group: String = String::new();
+        let mut group_span: Span = Span::default();
         let ope_str = Self::LENGTH_ORDER_OPE_LIST.map(|a| a.opestr).join("");
 
         for inner in &self.code_list {
@@ -87,36 +103,212 @@ impl ExprParser {
                 // inner in split
                 {
                     if !group.is_empty() {
-                        add_rlist!(rlist, group);
+                        add_rlist!(rlist, group, group_span);
                         group.clear();
                     }
                 } else if Self::EXCLUDE_WORDS.contains(&e.contents) || ope_str.contains(e.contents)
                 // inner in split
                 {
                     if !group.is_empty() {
-                        add_rlist!(rlist, group);
+                        add_rlist!(rlist, group, group_span);
                         group.clear();
                     }
                     rlist.push(inner.clone());
                 } else {
+                    if group.is_empty() {
+                        group_span = e.span;
+                    } else {
+                        group_span = group_span.merge(&e.span);
+                    }
                     group.push(e.contents);
                 }
             } else {
                 if !group.is_empty() {
-                    add_rlist!(rlist, group);
+                    add_rlist!(rlist, group, group_span);
                     group.clear();
                 }
                 rlist.push(inner.clone());
             }
         }
         if !group.is_empty() {
-            add_rlist!(rlist, group);
+            add_rlist!(rlist, group, group_span);
             group.clear();
         }
         self.code_list = rlist;
         Ok(())
     }
 
+    /// `0..n` `0..=n`は一つの単語としてまとめられるため、`..` `..=`を分割する
+    /// 分割した`..` `..=`は`grouping_word_operator`で演算子になる
+    fn grouping_ranges(&mut self) {
+        let mut rlist: Vec<ExprElem> = Vec::new();
+        let mut index = 0;
+
+        while index < self.code_list.len() {
+            let inner = &self.code_list[index];
+            index += 1;
+            let ExprElem::WordElem(word_b) = inner else {
+                rlist.push(inner.clone());
+                continue;
+            };
+            let Some(position) = word_b.contents.find("..") else {
+                rlist.push(inner.clone());
+                continue;
+            };
+            let sub_word = |from: usize, to: usize| {
+                ExprElem::WordElem(WordBranch {
+                    contents: word_b.contents[from..to].to_string(),
+                    depth: self.depth,
+                    loopdepth: self.loopdepth,
+                    span: Span::new(
+                        word_b.span.start + from,
+                        word_b.span.start + to,
+                        word_b.span.line,
+                        word_b.span.column + from,
+                    ),
+                })
+            };
+            let len = word_b.contents.len();
+            if position > 0 {
+                rlist.push(sub_word(0, position));
+            }
+            let mut range = sub_word(position, position + 2);
+            // `0..` `=` `n`
+            if let (ExprElem::WordElem(range_b), Some(ExprElem::UnKnownElem(equal))) =
+                (&mut range, self.code_list.get(index))
+            {
+                if position + 2 == len
+                    && equal.contents == '='
+                    && word_b.span.end == equal.span.start
+                {
+                    range_b.contents.push('=');
+                    range_b.span = range_b.span.merge(&equal.span);
+                    index += 1;
+                }
+            }
+            rlist.push(range);
+            if position + 2 < len {
+                rlist.push(sub_word(position + 2, len));
+            }
+        }
+        self.code_list = rlist;
+    }
+
+    /// 数字から始まる単語を数値リテラルにする
+    /// `1.5e-3`は`-`で単語が分割されるため、ここで一つにまとめる
+    fn grouping_numbers(&mut self) -> Result<(), ParserError> {
+        let mut rlist: Vec<ExprElem> = Vec::new();
+        let mut errors: Vec<ParserError> = Vec::new();
+        let mut index = 0;
+
+        while index < self.code_list.len() {
+            let ExprElem::WordElem(word_b) = &self.code_list[index] else {
+                rlist.push(self.code_list[index].clone());
+                index += 1;
+                continue;
+            };
+            if !word_b.contents.starts_with(|c: char| c.is_ascii_digit()) {
+                rlist.push(self.code_list[index].clone());
+                index += 1;
+                continue;
+            }
+            let mut contents = word_b.contents.clone();
+            let mut span = word_b.span;
+            index += 1;
+            // 指数部の符号 `1.5e` `-` `3`
+            if let [ExprElem::UnKnownElem(sign), ExprElem::WordElem(exponent), ..] =
+                &self.code_list[index..]
+            {
+                let is_decimal = !matches!(contents.get(..2), Some("0x" | "0o" | "0b"));
+                if is_decimal
+                    && contents.ends_with(['e', 'E'])
+                    && matches!(sign.contents, '+' | '-')
+                    && span.end == sign.span.start
+                    && sign.span.end == exponent.span.start
+                {
+                    contents.push(sign.contents);
+                    contents.push_str(&exponent.contents);
+                    span = span.merge(&exponent.span);
+                    index += 2;
+                }
+            }
+            match NumBranch::parse(&contents) {
+                Some((value, suffix)) => rlist.push(ExprElem::NumElem(NumBranch {
+                    contents,
+                    value,
+                    suffix,
+                    depth: self.depth,
+                    loopdepth: self.loopdepth,
+                    span,
+                    num_type: None,
+                })),
+                None => {
+                    let error = ParserError::InvalidNumLiteral(span);
+                    errors.push(error.clone());
+                    rlist.push(ExprElem::ErrorElem(ErrorBranch {
+                        error,
+                        depth: self.depth,
+                        loopdepth: self.loopdepth,
+                        span,
+                    }));
+                }
+            }
+        }
+        self.code_list = rlist;
+        report_errors(&mut self.errors, errors)
+    }
+
+    /// `p.x`は一つの単語としてまとめられるため、フィールドへのアクセスに分割する
+    /// `p.a.x`は`(p.a).x`になる
+    /// 数値リテラルと`..` `..=`は先に処理されているため対象にならない
+    fn grouping_fields(&mut self) {
+        let mut rlist: Vec<ExprElem> = Vec::new();
+
+        for inner in &self.code_list {
+            let ExprElem::WordElem(word_b) = inner else {
+                rlist.push(inner.clone());
+                continue;
+            };
+            if !word_b.contents.contains('.') || word_b.contents.starts_with('.') {
+                rlist.push(inner.clone());
+                continue;
+            }
+            let mut parts = word_b.contents.split('.');
+            let base = parts.next().unwrap_or_default();
+            let mut end = base.len();
+            let mut elem = ExprElem::WordElem(WordBranch {
+                contents: base.to_string(),
+                depth: self.depth,
+                loopdepth: self.loopdepth,
+                span: Span::new(
+                    word_b.span.start,
+                    word_b.span.start + end,
+                    word_b.span.line,
+                    word_b.span.column,
+                ),
+            });
+            for name in parts {
+                end += name.len() + 1;
+                elem = ExprElem::FieldElem(FieldBranch {
+                    base: Box::new(elem),
+                    name: name.to_string(),
+                    depth: self.depth,
+                    loopdepth: self.loopdepth,
+                    span: Span::new(
+                        word_b.span.start,
+                        word_b.span.start + end,
+                        word_b.span.line,
+                        word_b.span.column,
+                    ),
+                    offset: 0,
+                    field_type: None,
+                });
+            }
+            rlist.push(elem);
+        }
+        self.code_list = rlist;
+    }
+
     fn grouping_string(&mut self) -> Result<(), ParserError> {
         // now this function can group all string in  the program
         let mut group: String = String::new();
@@ -124,6 +316,12 @@ impl ExprParser {
         let mut open_status: StringAreaState = StringAreaState::Closed;
         let mut ignore_flag = false;
         let mut string_escape_flag = false;
+        // 文字列やコメントが開いた位置
+        let mut open_span: Span = Span::default();
+        let mut last_span: Span = Span::default();
+        // 最後の空白でない文字の位置
+        let mut visible_span: Span = Span::default();
+        let mut errors: Vec<ParserError> = Vec::new();
 
         for (count, inner) in self.code_list.iter().enumerate() {
             if ignore_flag {
@@ -132,6 +330,10 @@ impl ExprParser {
                 continue;
             }
             if let ExprElem::UnKnownElem(e) = inner {
+                last_span = e.span;
+                if !e.contents.is_whitespace() {
+                    visible_span = e.span;
+                }
                 match open_status {
                     StringAreaState::CommentStart => {
                         // //が開いているとき
@@ -140,6 +342,7 @@ impl ExprParser {
                                 contents: group.clone(),
                                 depth: self.depth,
                                 loopdepth: self.loopdepth,
+                                span: open_span.merge(&e.span.head()),
                             }));
                             open_status = StringAreaState::Closed;
                             group.clear();
@@ -152,7 +355,7 @@ impl ExprParser {
                         if Self::COMMENT_CLOSE.starts_with(e.contents)
                         // "*" == e.content
                         {
-                            if count < self.code_list.len() {
+                            if count + 1 < self.code_list.len() {
                                 if let ExprElem::UnKnownElem(next_e) = &self.code_list[count + 1] {
                                     if Self::COMMENT_CLOSE.ends_with(next_e.contents)
                                     // "/" == e.content
@@ -161,6 +364,7 @@ impl ExprParser {
                                             contents: group.clone(),
                                             depth: self.depth,
                                             loopdepth: self.loopdepth,
+                                            span: open_span.merge(&next_e.span),
                                         }));
                                         group.clear();
                                         open_status = StringAreaState::Closed;
@@ -170,10 +374,11 @@ impl ExprParser {
                                     }
                                 } else {
                                     // defer type
-                                    return Err(ParserError::UnexpectedTypeExpr);
+                                    return Err(ParserError::UnexpectedTypeExpr(e.span));
                                 }
                             } else {
-                                return Err(ParserError::CommentBlockNotClosed);
+                                // 閉じられないまま終端に達した場合
+                                group.push(e.contents);
                             }
                         } else {
                             group.push(e.contents);
@@ -190,6 +395,7 @@ impl ExprParser {
                                     contents: group.clone(),
                                     depth: self.depth,
                                     loopdepth: self.loopdepth,
+                                    span: open_span.merge(&e.span),
                                 }));
                                 group.clear();
                                 open_status = StringAreaState::Closed;
@@ -210,13 +416,15 @@ impl ExprParser {
                         if Self::COMMENT_OPEN.starts_with(e.contents)
                             || Self::COMMENT_START.starts_with(e.contents)
                         {
-                            if count < self.code_list.len() {
+                            if count + 1 < self.code_list.len() {
                                 if let ExprElem::UnKnownElem(next_e) = &self.code_list[count + 1] {
                                     if Self::COMMENT_OPEN.ends_with(next_e.contents) {
                                         open_status = StringAreaState::CommentOpen;
+                                        open_span = e.span;
                                         ignore_flag = true;
                                     } else if Self::COMMENT_START.ends_with(next_e.contents) {
                                         open_status = StringAreaState::CommentStart;
+                                        open_span = e.span;
                                         ignore_flag = true;
                                     } else {
                                         rlist.push(inner.clone())
@@ -229,6 +437,7 @@ impl ExprParser {
                             }
                         } else if Self::DOUBLE_QUOTATION == e.contents {
                             open_status = StringAreaState::QuotationOpen;
+                            open_span = e.span;
                         } else {
                             rlist.push(inner.clone());
                         }
@@ -243,14 +452,32 @@ impl ExprParser {
                 contents: group.clone(),
                 depth: self.depth,
                 loopdepth: self.loopdepth,
+                span: open_span.merge(&last_span),
             }));
         } else if let StringAreaState::CommentOpen = open_status {
-            return Err(ParserError::CommentBlockNotClosed);
+            // 回復時は終端までをコメントとして扱う
+            errors.push(ParserError::CommentBlockNotClosed(open_span));
+            rlist.push(ExprElem::CommentElem(CommentBranch {
+                contents: group.clone(),
+                depth: self.depth,
+                loopdepth: self.loopdepth,
+                span: open_span.merge(&last_span),
+            }));
         } else if let StringAreaState::QuotationOpen = open_status {
-            return Err(ParserError::QuotationNotClosed);
+            // 回復時は終端までを文字列として扱う
+            errors.push(ParserError::QuotationNotClosed {
+                open: open_span,
+                end: visible_span.tail(),
+            });
+            rlist.push(ExprElem::StringElem(StringBranch {
+                contents: group.clone(),
+                depth: self.depth,
+                loopdepth: self.loopdepth,
+                span: open_span.merge(&last_span),
+            }));
         }
         self.code_list = rlist;
-        Ok(())
+        report_errors(&mut self.errors, errors)
     }
 
     fn grouping_elements<T, U>(
@@ -266,35 +493,52 @@ impl ExprParser {
         let mut rlist: Vec<ExprElem> = Vec::new();
         let mut group: Vec<U> = Vec::new();
         let mut depth: isize = 0;
+        // 開いている括弧の位置(入れ子の場合は内側ほど後ろ)
+        let mut open_spans: Vec<Span> = Vec::new();
+        let mut errors: Vec<ParserError> = Vec::new();
 
         for inner in &self.code_list {
             if let ExprElem::UnKnownElem(ref b) = inner {
                 if b.contents == open_char {
+                    open_spans.push(b.span);
                     match depth {
                         0 => { /*pass*/ }
-                        1.. => group.push(Token::set_char_as_unknown(b.contents)),
-                        _ => return Err(ParserError::BraceNotOpened),
+                        1.. => group.push(Token::set_char_as_unknown(b.contents, b.span)),
+                        _ => return Err(ParserError::BraceNotOpened(b.span)),
                     }
                     depth += 1;
                 } else if b.contents == close_char {
                     depth -= 1;
+                    let open_span = open_spans.pop().unwrap_or_default();
                     match depth {
                         0 => {
                             rlist.push(elemtype(ASTAreaBranch::new(
                                 group.clone(),
                                 self.depth,
                                 self.loopdepth,
+                                open_span.merge(&b.span),
                             )));
                             group.clear();
                         }
-                        1.. => group.push(Token::set_char_as_unknown(b.contents)),
-                        _ => return Err(ParserError::BraceNotOpened),
+                        1.. => group.push(Token::set_char_as_unknown(b.contents, b.span)),
+                        _ => {
+                            // 対応する開き括弧がない場合はエラーの要素として残す
+                            let e = ParserError::BraceNotOpened(b.span);
+                            errors.push(e.clone());
+                            rlist.push(ExprElem::ErrorElem(ErrorBranch {
+                                error: e,
+                                depth: self.depth,
+                                loopdepth: self.loopdepth,
+                                span: b.span,
+                            }));
+                            depth = 0;
+                        }
                     }
                 } else {
                     match depth {
                         0 => rlist.push(inner.clone()),
-                        1.. => group.push(Token::set_char_as_unknown(b.contents)),
-                        _ => return Err(ParserError::BraceNotOpened),
+                        1.. => group.push(Token::set_char_as_unknown(b.contents, b.span)),
+                        _ => return Err(ParserError::BraceNotOpened(b.span)),
                     }
                 }
             } else {
@@ -307,6 +551,7 @@ impl ExprParser {
                                     s.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    s.span,
                                 ));
                             }
                             ExprElem::BlockElem(bl) => {
@@ -314,6 +559,7 @@ impl ExprParser {
                                     bl.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    bl.span,
                                 ));
                             }
                             ExprElem::ParenBlockElem(pb) => {
@@ -321,6 +567,7 @@ impl ExprParser {
                                     pb.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    pb.span,
                                 ));
                             }
                             ExprElem::ListBlockElem(lb) => {
@@ -328,35 +575,85 @@ impl ExprParser {
                                     lb.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    lb.span,
                                 ));
                             }
-                            // todo
+                            // 回復で生成したエラーの要素はそのまま残し、元のエラーを報告する
+                            ExprElem::ErrorElem(eb) => group.push(ProcToken::t_error(eb.clone())),
                             _ => {
-                                // todo error処理
-                                return Err(ParserError::UnexpectedTypeExpr);
+                                return Err(ParserError::UnexpectedTypeExpr(inner.get_span()));
                             }
                         }
                     }
-                    _ => return Err(ParserError::BraceNotClosed),
+                    _ => {
+                        return Err(ParserError::BraceNotClosed {
+                            open: inner.get_span(),
+                            end: inner.get_span(),
+                            close: close_char,
+                        })
+                    }
                 }
             }
         }
         if depth != 0 {
-            return Err(ParserError::BraceNotClosed);
+            // 回復時は終端で閉じられたものとして扱う
+            let open_span = open_spans.first().copied().unwrap_or_default();
+            let last_span = self
+                .code_list
+                .last()
+                .map(|inner| inner.get_span())
+                .unwrap_or_default();
+            errors.push(ParserError::BraceNotClosed {
+                open: open_span,
+                end: Self::closing_span(&self.code_list, open_span),
+                close: close_char,
+            });
+            rlist.push(elemtype(ASTAreaBranch::<U>::new(
+                group,
+                self.depth,
+                self.loopdepth,
+                open_span.merge(&last_span),
+            )));
+            if let Some(ExprElem::ListBlockElem(list_block_b)) = rlist.last_mut() {
+                list_block_b.trim_unclosed();
+            }
         }
         self.code_list = rlist;
-        Ok(())
+        report_errors(&mut self.errors, errors)
     }
 
     fn grouping_operator(&mut self) -> Result<(), ParserError> {
         for ope in Self::LENGTH_ORDER_OPE_LIST {
             self.grouping_operator_unit(ope.opestr.to_string())?;
         }
+        self.grouping_word_operator();
         Ok(())
     }
 
+    /// `as`などの単語として現れる演算子
+    fn grouping_word_operator(&mut self) {
+        for inner in &mut self.code_list {
+            if let ExprElem::WordElem(word_b) = inner {
+                if Self::is_word_operator(&word_b.contents) {
+                    *inner = ExprElem::OpeElem(OperatorBranch {
+                        ope: word_b.contents.clone(),
+                        depth: self.depth,
+                        span: word_b.span,
+                        ope_type: None,
+                    });
+                }
+            }
+        }
+    }
+
+    fn is_word_operator(word: &str) -> bool {
+        Self::WORD_OPE_LIST.iter().any(|ope| ope.opestr == word)
+    }
+
     fn grouping_operator_unit(&mut self, ope: String) -> Result<(), ParserError> {
         let mut group: String = String::new();
+        // `group`を構成している未定token(位置情報を保持するため)
+        let mut group_elems: Vec<ExprElem> = Vec::new();
         let mut rlist: Vec<ExprElem> = Vec::new();
 
         let ope_size: usize = ope.len();
@@ -364,6 +661,7 @@ impl ExprParser {
             if let ExprElem::UnKnownElem(e) = inner {
                 // 未解決の場合
                 group.push(e.contents);
+                group_elems.push(inner.clone());
                 match group.len().cmp(&ope_size) {
                     Ordering::Less => {}
                     Ordering::Equal => {
@@ -371,56 +669,60 @@ impl ExprParser {
                             rlist.push(ExprElem::OpeElem(OperatorBranch {
                                 ope: group.clone(),
                                 depth: self.depth,
+                                span: merge_span(&group_elems),
+                                ope_type: None,
                             }))
                         } else {
-                            let group_tmp = Self::code2_vec_pre_proc_func(&group);
-                            rlist.extend(group_tmp);
+                            rlist.append(&mut group_elems);
                         }
                         group.clear();
+                        group_elems.clear();
                     }
                     Ordering::Greater => {
                         // ope_size < group.len()
                         // rlist += group
-                        let group_tmp = Self::code2_vec_pre_proc_func(&group);
-                        rlist.extend(group_tmp);
+                        rlist.append(&mut group_elems);
                         group.clear();
                     }
                 }
             } else {
                 // 既にtokenが割り当てられているとき
                 match group.len().cmp(&ope_size) {
-                    Ordering::Less => {
-                        let group_tmp = Self::code2_vec_pre_proc_func(&group);
-                        rlist.extend(group_tmp);
-                    }
-                    Ordering::Equal => {
-                        if group == ope {
-                            rlist.push(ExprElem::OpeElem(OperatorBranch {
-                                ope: group.clone(),
-                                depth: self.depth,
-                            }))
-                        } else {
-                            // rlist += group
-                            let group_tmp = Self::code2_vec_pre_proc_func(&group);
-                            rlist.extend(group_tmp);
-                        }
+                    Ordering::Equal if group == ope => {
+                        rlist.push(ExprElem::OpeElem(OperatorBranch {
+                            ope: group.clone(),
+                            depth: self.depth,
+                            span: merge_span(&group_elems),
+                            ope_type: None,
+                        }));
+                        group_elems.clear();
                     }
-                    Ordering::Greater => {
+                    _ => {
                         // rlist += group
-                        let group_tmp = Self::code2_vec_pre_proc_func(&group);
-                        rlist.extend(group_tmp);
+                        rlist.append(&mut group_elems);
                     }
                 }
                 group.clear();
                 rlist.push(inner.clone());
             }
         } //end of "for inner in codelist"
+        if group.len() == ope_size && group == ope {
+            rlist.push(ExprElem::OpeElem(OperatorBranch {
+                ope: group.clone(),
+                depth: self.depth,
+                span: merge_span(&group_elems),
+                ope_type: None,
+            }));
+        } else {
+            rlist.append(&mut group_elems);
+        }
         self.code_list = rlist;
         Ok(())
     }
 
     fn grouping_syntax(&mut self) -> Result<(), ParserError> {
         let mut name: Option<String> = None;
+        let mut name_span: Span = Span::default();
         let mut expr: Option<ParenBlockBranch> = None;
         let mut rlist: Vec<ExprElem> = Vec::new();
 
@@ -428,6 +730,7 @@ impl ExprParser {
             if let ExprElem::WordElem(wd) = inner {
                 if Self::SYNTAX_WORDS.contains(&wd.contents.as_str()) {
                     name = Some(wd.contents.clone());
+                    name_span = wd.span;
                 } else {
                     rlist.push(inner.clone());
                 }
@@ -438,7 +741,24 @@ impl ExprParser {
                     rlist.push(inner.clone());
                 }
             } else if let ExprElem::BlockElem(bl) = inner {
-                if let Some(syntax_name) = name {
+                if name.as_deref() == Some(Self::SYNTAX_MATCH) {
+                    // `match`の`{}`の中身は文ではなく`,`で区切られた腕
+                    rlist.push(ExprElem::MatchElem(MatchBranch {
+                        expr: expr.map(|e| e.contents).unwrap_or_default(),
+                        arms: MatchBranch::split_arms(
+                            StmtParser::stmt2expr(&bl.contents)?,
+                            self.depth,
+                            self.loopdepth,
+                        )?,
+                        depth: self.depth,
+                        loopdepth: self.loopdepth,
+                        span: name_span.merge(&bl.span),
+                        local: String::new(),
+                        expr_type: None,
+                        result_type: None,
+                        boxed: false,
+                    }));
+                } else if let Some(syntax_name) = name {
                     rlist.push(ExprElem::SyntaxElem(SyntaxBranch {
                         name: syntax_name,
                         expr: if let Some(syntax_expr) = expr {
@@ -449,6 +769,8 @@ impl ExprParser {
                         contents: bl.contents.clone(),
                         depth: self.depth,
                         loopdepth: self.loopdepth,
+                        span: name_span.merge(&bl.span),
+                        iter_array: None,
                     }));
                 } else {
                     // TODO
@@ -485,7 +807,7 @@ impl ExprParser {
                     if flag {
                         group.push(e.clone());
                     } else {
-                        return Err(ParserError::GroupingSyntaxBoxError);
+                        return Err(ParserError::GroupingSyntaxBoxError(e.span));
                     }
                 } else if e.name == Self::SYNTAX_ELSE {
                     if flag {
@@ -495,12 +817,13 @@ impl ExprParser {
                             contents: group.clone(),
                             depth: self.depth,
                             loopdepth: self.loopdepth,
+                            span: merge_spans(group.iter().map(|inner| inner.span)),
                         }));
                         group.clear();
                         name = String::from("");
                         flag = false;
                     } else {
-                        return Err(ParserError::GroupingSyntaxBoxError);
+                        return Err(ParserError::GroupingSyntaxBoxError(e.span));
                     }
                 } else {
                     rlist.push(inner.clone());
@@ -513,6 +836,7 @@ impl ExprParser {
                             contents: group.clone(),
                             depth: self.depth,
                             loopdepth: self.loopdepth,
+                            span: merge_spans(group.iter().map(|inner| inner.span)),
                         }));
                         group.clear();
                         name = String::from("");
@@ -532,12 +856,52 @@ impl ExprParser {
                 contents: group.clone(),
                 depth: self.depth,
                 loopdepth: self.loopdepth,
+                span: merge_spans(group.iter().map(|inner| inner.span)),
             }));
         }
         self.code_list = rlist;
         Ok(())
     }
 
+    /// 名前の直後の`{}`を構造体のリテラルにする
+    /// ```lichen
+    /// Point { x: 1.0, y: 2.0 }
+    /// ```
+    /// `if` `loop`などの`{}`は`grouping_syntax`で先にまとめられている
+    fn grouping_structs(&mut self) -> Result<(), ParserError> {
+        let mut rlist: Vec<ExprElem> = Vec::new();
+
+        for inner in &self.code_list {
+            if let ExprElem::BlockElem(bl) = inner {
+                if let Some(ExprElem::WordElem(wd)) = rlist.last() {
+                    if !Self::KEYWORDS.contains(&wd.contents.as_str())
+                        && !Self::is_word_operator(&wd.contents)
+                    {
+                        let (name, span) = (wd.contents.clone(), wd.span.merge(&bl.span));
+                        rlist.pop();
+                        rlist.push(ExprElem::StructElem(StructBranch {
+                            name,
+                            fields: StructBranch::split_fields(
+                                StmtParser::stmt2expr(&bl.contents)?,
+                                self.depth,
+                                self.loopdepth,
+                            )?,
+                            depth: self.depth,
+                            loopdepth: self.loopdepth,
+                            span,
+                            local: String::new(),
+                            size: 0,
+                        }));
+                        continue;
+                    }
+                }
+            }
+            rlist.push(inner.clone());
+        }
+        self.code_list = rlist;
+        Ok(())
+    }
+
     //
     // TODO: Word以外について`()`が付与され呼ばれたときに
     // 関数として認識できるようにする必要がある
@@ -546,19 +910,34 @@ impl ExprParser {
     // funcA()() // 関数を返却するような関数
     // a[]()     // 関数を保持しているリスト
     // ```
+    // `x in [1, 2]`のように演算子として使われる単語の後の`[]` `()`は添字・呼び出しではない
     fn contain_subscriptable(&self) -> bool {
         let mut name_tmp: Option<&ExprElem> = None;
 
+        if self
+            .code_list
+            .windows(2)
+            .any(|pair| Self::is_element_field(Some(&pair[0]), &pair[1]))
+        {
+            return true;
+        }
         for inner in &self.code_list {
             if let ExprElem::WordElem(_)
             | ExprElem::FuncElem(_)
             | ExprElem::ListElem(_)
+            | ExprElem::FieldElem(_)
             | ExprElem::SyntaxBoxElem(_) = inner
             {
                 name_tmp = Some(inner);
             } else if let ExprElem::ListBlockElem(_) | ExprElem::ParenBlockElem(_) = inner {
-                if let Some(ExprElem::WordElem(v)) = name_tmp {
-                    if !Self::KEYWORDS.contains(&v.contents.as_str()) {
+                if let (Some(ExprElem::FieldElem(_)), ExprElem::ListBlockElem(_)) =
+                    (name_tmp, inner)
+                {
+                    return true;
+                } else if let Some(ExprElem::WordElem(v)) = name_tmp {
+                    if !Self::KEYWORDS.contains(&v.contents.as_str())
+                        && !Self::is_word_operator(&v.contents)
+                    {
                         return true;
                     }
                 } else if let Some(
@@ -576,14 +955,55 @@ impl ExprParser {
         false
     }
 
+    /// 式の後のフィールド`a[0].x` `f().x` `(p).x`の`.x`かどうか
+    fn is_element_field(name_tmp: Option<&ExprElem>, inner: &ExprElem) -> bool {
+        matches!(
+            (name_tmp, inner),
+            (
+                Some(ExprElem::ListElem(_) | ExprElem::FuncElem(_) | ExprElem::ParenBlockElem(_)),
+                ExprElem::WordElem(word_b),
+            ) if word_b.contents.starts_with('.')
+        )
+    }
+
     fn grouping_subscription(&mut self) -> Result<(), ParserError> {
         let mut name_tmp: Option<ExprElem> = None;
         let mut rlist: Vec<ExprElem> = Vec::new();
 
         for inner in &self.code_list {
+            // 呼び出しや括弧は既に`rlist`に追加されている
+            let base = if Self::is_element_field(name_tmp.as_ref(), inner) {
+                name_tmp.take()
+            } else if name_tmp.is_none() && Self::is_element_field(rlist.last(), inner) {
+                rlist.pop()
+            } else {
+                None
+            };
+            if let Some(base) = base {
+                // `a[0].x` `f().x` `(p).x`
+                let ExprElem::WordElem(word_b) = inner else {
+                    return Err(ParserError::DevError);
+                };
+                let mut elem = base;
+                for name in word_b.contents[1..].split('.') {
+                    let span = elem.get_span().merge(&word_b.span);
+                    elem = ExprElem::FieldElem(FieldBranch {
+                        base: Box::new(elem),
+                        name: name.to_string(),
+                        depth: self.depth,
+                        loopdepth: self.loopdepth,
+                        span,
+                        offset: 0,
+                        field_type: None,
+                    });
+                }
+                name_tmp = Some(elem);
+                continue;
+            }
             if let ExprElem::WordElem(_)
             | ExprElem::FuncElem(_)
             | ExprElem::ListElem(_)
+            | ExprElem::FieldElem(_)
             | ExprElem::SyntaxBoxElem(_) = inner
             {
                 if let Some(v) = name_tmp {
@@ -594,7 +1014,9 @@ impl ExprParser {
             // [] ()
             else if let Some(v) = &name_tmp {
                 if let ExprElem::WordElem(ref wd) = v {
-                    if !Self::KEYWORDS.contains(&wd.contents.as_str()) {
+                    if !Self::KEYWORDS.contains(&wd.contents.as_str())
+                        && !Self::is_word_operator(&wd.contents)
+                    {
                         // jump to point01
                     } else {
                         // 1
@@ -608,6 +1030,9 @@ impl ExprParser {
                 | ExprElem::SyntaxBoxElem(_) = &v
                 {
                     // jump to point01
+                } else if let (ExprElem::FieldElem(_), ExprElem::ListBlockElem(_)) = (&v, inner) {
+                    // `p.xs[0]` フィールドは添字のみを取る
+                    // jump to point01
                 } else {
                     rlist.push(v.clone());
                     rlist.push(inner.clone());
@@ -621,6 +1046,11 @@ impl ExprParser {
                         contents: vec![inner.clone()],
                         depth: self.depth,
                         loopdepth: self.loopdepth,
+                        span: v.get_span().merge(&inner.get_span()),
+                        element_type: None,
+                        element_size: 0,
+                        length: 0,
+                        local: String::new(),
                     }));
                 } else if let ExprElem::ParenBlockElem(_) = inner {
                     rlist.push(ExprElem::FuncElem(FuncBranch {
@@ -628,6 +1058,7 @@ impl ExprParser {
                         contents: vec![inner.clone()],
                         depth: self.depth,
                         loopdepth: self.loopdepth,
+                        span: v.get_span().merge(&inner.get_span()),
                     }));
                 } else {
                     rlist.push(v.clone());
@@ -656,7 +1087,7 @@ impl ExprParser {
                     // if index == 0:
                     {
                         index_tmp = Some(index);
-                        priority_tmp = 4; // unsafe
+                        priority_tmp = Self::PREFIX_PRIORITY;
                     } else if let ExprElem::OpeElem(_) = &self.code_list[index - 1] {
                         continue;
                     } else if ope_info.priority < priority_tmp {
@@ -674,7 +1105,7 @@ impl ExprParser {
                     } // else pass
                 } else {
                     // error case
-                    return Err(ParserError::OperationError);
+                    return Err(ParserError::OperationError(ope.span));
                 }
             } else {
                 continue;
@@ -688,18 +1119,32 @@ impl ExprParser {
         match operation_index {
             Ok(v) => {
                 if let Some(s) = v {
+                    let name = &self.code_list[s];
+                    let l_contents = self.code_list[..s].to_vec();
+                    let r_contents = self.code_list[s + 1..].to_vec();
+                    // 前置演算子などで要素がない場合は演算子の位置を指すようにする
                     let arg1 = ExprElem::ItemElem(ItemBranch {
-                        contents: self.code_list[..s].to_vec(),
+                        span: if l_contents.is_empty() {
+                            name.get_span().head()
+                        } else {
+                            merge_span(&l_contents)
+                        },
+                        contents: l_contents,
                         depth: self.depth,
                         loopdepth: self.loopdepth,
                     });
-                    let name = &self.code_list[s];
                     let arg2 = ExprElem::ItemElem(ItemBranch {
-                        contents: self.code_list[s + 1..].to_vec(),
+                        span: if r_contents.is_empty() {
+                            name.get_span().head()
+                        } else {
+                            merge_span(&r_contents)
+                        },
+                        contents: r_contents,
                         depth: self.depth,
                         loopdepth: self.loopdepth,
                     });
                     self.code_list = vec![ExprElem::FuncElem(FuncBranch {
+                        span: merge_span(&self.code_list),
                         name: Box::new(name.clone()),
                         contents: vec![arg1, arg2],
                         depth: self.depth,
@@ -712,6 +1157,29 @@ impl ExprParser {
         }
     }
 
+    /// 回復モードで解析する
+    /// `StmtParser::resolve_recovering`を参照
+    pub fn resolve_recovering(&mut self) -> Vec<ParserError> {
+        self.errors = Some(Vec::new());
+        let fatal = self.code2vec().err();
+        let mut errors = self.errors.take().unwrap_or_default();
+        if let Some(e) = fatal {
+            // 回復できないエラーの場合は全体をエラーの要素とする
+            self.code_list = vec![ExprElem::ErrorElem(ErrorBranch {
+                error: e.clone(),
+                depth: self.depth,
+                loopdepth: self.loopdepth,
+                span: merge_span(&self.code_list),
+            })];
+            errors.push(e);
+        }
+        for inner in &mut self.code_list {
+            inner.resolve_self_recovering(&mut errors);
+        }
+        errors.sort_by_key(|e| e.get_span().start);
+        errors
+    }
+
     pub fn create_parser_from_vec(
         code_list: Vec<ExprElem>,
         depth: isize,
@@ -722,6 +1190,7 @@ impl ExprParser {
             code_list,
             depth,
             loopdepth,
+            errors: None,
         }
     }
 }
@@ -733,6 +1202,7 @@ impl Parser<'_> for ExprParser {
             code_list: Self::code2_vec_pre_proc_func(&code),
             depth,
             loopdepth,
+            errors: None,
         }
     }
 
@@ -1,8 +1,16 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 use crate::errors::parser_errors::ParserError;
+use crate::parser::comma_parser::CommaParser;
 use crate::parser::core_parser::*;
+use crate::parser::expr_parser::ExprParser;
+use crate::parser::type_parser::{is_type_name, TypeParser};
 
 use crate::token::comment::CommentBranch;
+use crate::token::decenum::{DecEnumBranch, EnumVariantBranch};
+use crate::token::decfunc::{DecFuncBranch, FuncArgBranch};
+use crate::token::decstruct::{DecStructBranch, StructFieldBranch};
+use crate::token::error::ErrorBranch;
 use crate::token::operator::OperatorBranch;
 use crate::token::stmt::expr::ExprBranch;
 use crate::token::stmt::stmt::StmtBranch;
@@ -15,6 +23,9 @@ pub struct StmtParser {
     pub code_list: Vec<StmtElem>,
     pub depth: isize,
     pub loopdepth: isize,
+    /// 回復モードで収集したエラー
+    /// `None`のときは最初のエラーで解析を中断する
+    pub errors: Option<Vec<ParserError>>,
 }
 
 enum StringAreaState {
@@ -43,6 +54,7 @@ impl StmtParser {
             Self::BLOCK_PAREN_CLOSE, // )
         )?;
         self.grouping_words()?;
+        self.grouping_functions()?;
         self.split_semicolon()?;
         Ok(())
     }
@@ -54,6 +66,12 @@ impl StmtParser {
         let mut open_status: StringAreaState = StringAreaState::Closed;
         let mut ignore_flag = false;
         let mut string_escape_flag = false;
+        // 文字列やコメントが開いた位置
+        let mut open_span: Span = Span::default();
+        let mut last_span: Span = Span::default();
+        // 最後の空白でない文字の位置
+        let mut visible_span: Span = Span::default();
+        let mut errors: Vec<ParserError> = Vec::new();
 
         for (count, inner) in self.code_list.iter().enumerate() {
             if ignore_flag {
@@ -62,6 +80,10 @@ impl StmtParser {
                 continue;
             }
             if let StmtElem::UnKnownElem(e) = inner {
+                last_span = e.span;
+                if !e.contents.is_whitespace() {
+                    visible_span = e.span;
+                }
                 match open_status {
                     StringAreaState::CommentStart => {
                         // //が開いているとき
@@ -70,6 +92,7 @@ impl StmtParser {
                                 contents: group.clone(),
                                 depth: self.depth,
                                 loopdepth: self.loopdepth,
+                                span: open_span.merge(&e.span.head()),
                             }));
                             open_status = StringAreaState::Closed;
                             group.clear();
@@ -82,7 +105,7 @@ impl StmtParser {
                         if Self::COMMENT_CLOSE.starts_with(e.contents)
                         // "*" == e.content
                         {
-                            if count < self.code_list.len() {
+                            if count + 1 < self.code_list.len() {
                                 if let StmtElem::UnKnownElem(next_e) = &self.code_list[count + 1] {
                                     if Self::COMMENT_CLOSE.ends_with(next_e.contents)
                                     // "/" == e.content
@@ -91,6 +114,7 @@ impl StmtParser {
                                             contents: group.clone(),
                                             depth: self.depth,
                                             loopdepth: self.loopdepth,
+                                            span: open_span.merge(&next_e.span),
                                         }));
                                         group.clear();
                                         open_status = StringAreaState::Closed;
@@ -100,10 +124,11 @@ impl StmtParser {
                                     }
                                 } else {
                                     // defer type
-                                    return Err(ParserError::UnexpectedTypeStmt);
+                                    return Err(ParserError::UnexpectedTypeStmt(e.span));
                                 }
                             } else {
-                                return Err(ParserError::CommentBlockNotClosed);
+                                // 閉じられないまま終端に達した場合
+                                group.push(e.contents);
                             }
                         } else {
                             group.push(e.contents);
@@ -120,6 +145,7 @@ impl StmtParser {
                                     contents: group.clone(),
                                     depth: self.depth,
                                     loopdepth: self.loopdepth,
+                                    span: open_span.merge(&e.span),
                                 }));
                                 group.clear();
                                 open_status = StringAreaState::Closed;
@@ -140,13 +166,15 @@ impl StmtParser {
                         if Self::COMMENT_OPEN.starts_with(e.contents)
                             || Self::COMMENT_START.starts_with(e.contents)
                         {
-                            if count < self.code_list.len() {
+                            if count + 1 < self.code_list.len() {
                                 if let StmtElem::UnKnownElem(next_e) = &self.code_list[count + 1] {
                                     if Self::COMMENT_OPEN.ends_with(next_e.contents) {
                                         open_status = StringAreaState::CommentOpen;
+                                        open_span = e.span;
                                         ignore_flag = true;
                                     } else if Self::COMMENT_START.ends_with(next_e.contents) {
                                         open_status = StringAreaState::CommentStart;
+                                        open_span = e.span;
                                         ignore_flag = true;
                                     } else {
                                         rlist.push(inner.clone())
@@ -159,6 +187,7 @@ impl StmtParser {
                             }
                         } else if Self::DOUBLE_QUOTATION == e.contents {
                             open_status = StringAreaState::QuotationOpen;
+                            open_span = e.span;
                         } else {
                             rlist.push(inner.clone());
                         }
@@ -173,14 +202,32 @@ impl StmtParser {
                 contents: group.clone(),
                 depth: self.depth,
                 loopdepth: self.loopdepth,
+                span: open_span.merge(&last_span),
             }));
         } else if let StringAreaState::CommentOpen = open_status {
-            return Err(ParserError::CommentBlockNotClosed);
+            // 回復時は終端までをコメントとして扱う
+            errors.push(ParserError::CommentBlockNotClosed(open_span));
+            rlist.push(StmtElem::CommentElem(CommentBranch {
+                contents: group.clone(),
+                depth: self.depth,
+                loopdepth: self.loopdepth,
+                span: open_span.merge(&last_span),
+            }));
         } else if let StringAreaState::QuotationOpen = open_status {
-            return Err(ParserError::QuotationNotClosed);
+            // 回復時は終端までを文字列として扱う
+            errors.push(ParserError::QuotationNotClosed {
+                open: open_span,
+                end: visible_span.tail(),
+            });
+            rlist.push(StmtElem::StringElem(StringBranch {
+                contents: group.clone(),
+                depth: self.depth,
+                loopdepth: self.loopdepth,
+                span: open_span.merge(&last_span),
+            }));
         }
         self.code_list = rlist;
-        Ok(())
+        report_errors(&mut self.errors, errors)
     }
 
     fn grouping_elements<T, U>(
@@ -196,35 +243,52 @@ impl StmtParser {
         let mut rlist: Vec<StmtElem> = Vec::new();
         let mut group: Vec<U> = Vec::new();
         let mut depth: isize = 0;
+        // 開いている括弧の位置(入れ子の場合は内側ほど後ろ)
+        let mut open_spans: Vec<Span> = Vec::new();
+        let mut errors: Vec<ParserError> = Vec::new();
 
         for inner in &self.code_list {
             if let StmtElem::UnKnownElem(ref b) = inner {
                 if b.contents == open_char {
+                    open_spans.push(b.span);
                     match depth {
                         0 => { /*pass*/ }
-                        1.. => group.push(Token::set_char_as_unknown(b.contents)),
-                        _ => return Err(ParserError::BraceNotOpened),
+                        1.. => group.push(Token::set_char_as_unknown(b.contents, b.span)),
+                        _ => return Err(ParserError::BraceNotOpened(b.span)),
                     }
                     depth += 1;
                 } else if b.contents == close_char {
                     depth -= 1;
+                    let open_span = open_spans.pop().unwrap_or_default();
                     match depth {
                         0 => {
                             rlist.push(elemtype(ASTAreaBranch::<U>::new(
                                 group.clone(),
                                 self.depth,
                                 self.loopdepth,
+                                open_span.merge(&b.span),
                             )));
                             group.clear();
                         }
-                        1.. => group.push(Token::set_char_as_unknown(b.contents)),
-                        _ => return Err(ParserError::BraceNotOpened),
+                        1.. => group.push(Token::set_char_as_unknown(b.contents, b.span)),
+                        _ => {
+                            // 対応する開き括弧がない場合はエラーの要素として残す
+                            let e = ParserError::BraceNotOpened(b.span);
+                            errors.push(e.clone());
+                            rlist.push(StmtElem::ErrorElem(ErrorBranch {
+                                error: e,
+                                depth: self.depth,
+                                loopdepth: self.loopdepth,
+                                span: b.span,
+                            }));
+                            depth = 0;
+                        }
                     }
                 } else {
                     match depth {
                         0 => rlist.push(inner.clone()),
-                        1.. => group.push(Token::set_char_as_unknown(b.contents)),
-                        _ => return Err(ParserError::BraceNotOpened),
+                        1.. => group.push(Token::set_char_as_unknown(b.contents, b.span)),
+                        _ => return Err(ParserError::BraceNotOpened(b.span)),
                     }
                 }
             } else {
@@ -237,6 +301,7 @@ impl StmtParser {
                                     s.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    s.span,
                                 ));
                             }
                             StmtElem::BlockElem(bl) => {
@@ -244,6 +309,7 @@ impl StmtParser {
                                     bl.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    bl.span,
                                 ));
                             }
                             StmtElem::ParenBlockElem(pb) => {
@@ -251,6 +317,7 @@ impl StmtParser {
                                     pb.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    pb.span,
                                 ));
                             }
                             StmtElem::ListBlockElem(lb) => {
@@ -258,6 +325,7 @@ impl StmtParser {
                                     lb.contents.clone(),
                                     self.depth,
                                     self.loopdepth,
+                                    lb.span,
                                 ));
                             }
                             StmtElem::CommentElem(cb) => {
@@ -265,48 +333,77 @@ impl StmtParser {
                                     cb.contents.clone(),
                                     cb.depth,
                                     cb.loopdepth,
+                                    cb.span,
                                 ));
                             }
-                            // todo
+                            // 回復で生成したエラーの要素はそのまま残し、元のエラーを報告する
+                            StmtElem::ErrorElem(eb) => group.push(ProcToken::t_error(eb.clone())),
                             _ => {
-                                // todo error処理
-                                println!("some thing wrong");
-                                println!("{:?}", inner);
-                                return Err(ParserError::UnexpectedTypeStmt);
+                                return Err(ParserError::UnexpectedTypeStmt(inner.get_span()));
                             }
                         }
                     }
-                    _ => return Err(ParserError::BraceNotClosed),
+                    _ => {
+                        return Err(ParserError::BraceNotClosed {
+                            open: inner.get_span(),
+                            end: inner.get_span(),
+                            close: close_char,
+                        })
+                    }
                 }
             }
         }
         if depth != 0 {
-            return Err(ParserError::BraceNotClosed);
+            // 回復時は終端で閉じられたものとして扱う
+            let open_span = open_spans.first().copied().unwrap_or_default();
+            let last_span = self
+                .code_list
+                .last()
+                .map(|inner| inner.get_span())
+                .unwrap_or_default();
+            errors.push(ParserError::BraceNotClosed {
+                open: open_span,
+                end: Self::closing_span(&self.code_list, open_span),
+                close: close_char,
+            });
+            rlist.push(elemtype(ASTAreaBranch::<U>::new(
+                group,
+                self.depth,
+                self.loopdepth,
+                open_span.merge(&last_span),
+            )));
+            if let Some(StmtElem::ListBlockElem(list_block_b)) = rlist.last_mut() {
+                list_block_b.trim_unclosed();
+            }
         }
         self.code_list = rlist;
-        Ok(())
+        report_errors(&mut self.errors, errors)
     }
 
     fn grouping_words(&mut self) -> Result<(), ParserError> {
         // macro
         macro_rules! add_rlist {
-            ($rlist:expr,$group:expr) => {
+            ($rlist:expr,$group:expr,$group_span:expr) => {
                 if let Ok(_) = Self::find_ope_priority(&$group) {
                     $rlist.push(StmtElem::OpeElem(OperatorBranch {
                         ope: $group.clone(),
                         depth: self.depth,
+                        span: $group_span,
+                        ope_type: None,
                     }))
                 } else {
                     $rlist.push(StmtElem::WordElem(WordBranch {
                         contents: $group.clone(),
                         depth: self.depth,
                         loopdepth: self.loopdepth,
+                        span: $group_span,
                     }));
                 }
             };
         }
         let mut rlist: Vec<StmtElem> = Vec::new();
         let mut group: String = String::new();
+        let mut group_span: Span = Span::default();
         let ope_str = Self::LENGTH_ORDER_OPE_LIST.map(|a| a.opestr).join("");
 
         for inner in &self.code_list {
@@ -315,30 +412,35 @@ impl StmtParser {
                 // inner in split
                 {
                     if !group.is_empty() {
-                        add_rlist!(rlist, group);
+                        add_rlist!(rlist, group, group_span);
                         group.clear();
                     }
                 } else if Self::EXCLUDE_WORDS.contains(&e.contents) || ope_str.contains(e.contents)
                 // inner in split
                 {
                     if !group.is_empty() {
-                        add_rlist!(rlist, group);
+                        add_rlist!(rlist, group, group_span);
                         group.clear();
                     }
                     rlist.push(inner.clone());
                 } else {
+                    if group.is_empty() {
+                        group_span = e.span;
+                    } else {
+                        group_span = group_span.merge(&e.span);
+                    }
                     group.push(e.contents);
                 }
             } else {
                 if !group.is_empty() {
-                    add_rlist!(rlist, group);
+                    add_rlist!(rlist, group, group_span);
                     group.clear();
                 }
                 rlist.push(inner.clone());
             }
         }
         if !group.is_empty() {
-            add_rlist!(rlist, group);
+            add_rlist!(rlist, group, group_span);
             group.clear();
         }
         self.code_list = rlist;
@@ -353,6 +455,8 @@ impl StmtParser {
     /// let b = 2; // <- stmt
     /// let c = 3; // <- stmt
     /// return a; // <- stmt
+    /// fn f() {} // <- stmt(関数宣言は`;`を必要としない)
+    /// struct P { x: i32 } // <- stmt(構造体宣言も`;`を必要としない)
     /// ```
     pub fn split_semicolon(&mut self) -> Result<(), ParserError> {
         let mut rlist: Vec<StmtElem> = Vec::new();
@@ -363,32 +467,7 @@ impl StmtParser {
                 StmtElem::UnKnownElem(unb) => {
                     if unb.contents == Self::SEMICOLON {
                         if !group.is_empty() {
-                            if let StmtElem::WordElem(word_b) = &group[0] {
-                                if Self::CONTROL_STATEMENT.contains(&word_b.contents.as_str()) {
-                                    // return 等の
-                                    // 予約語だった場合
-                                    rlist.push(StmtElem::Special(StmtBranch {
-                                        head: word_b.contents.clone(),
-                                        code_list: Self::stmt2expr(&group[1..])?,
-                                        depth: self.depth,
-                                        loopdepth: self.loopdepth,
-                                    }));
-                                } else {
-                                    // 普通の変数のwordだった場合
-                                    rlist.push(StmtElem::ExprElem(ExprBranch {
-                                        code_list: Self::stmt2expr(&group)?,
-                                        depth: self.depth,
-                                        loopdepth: self.loopdepth,
-                                    }));
-                                }
-                            } else {
-                                // 最初の要素がwordではなかった場合
-                                rlist.push(StmtElem::ExprElem(ExprBranch {
-                                    code_list: Self::stmt2expr(&group)?,
-                                    depth: self.depth,
-                                    loopdepth: self.loopdepth,
-                                }));
-                            }
+                            rlist.push(self.group2stmt(&group)?);
                         } else {
                             // group が空だった場合
                         }
@@ -401,32 +480,7 @@ impl StmtParser {
                 StmtElem::CommentElem(comment_b) => {
                     // コメントが文の途中で現れたとき
                     if !group.is_empty() {
-                        if let StmtElem::WordElem(word_b) = &group[0] {
-                            if Self::CONTROL_STATEMENT.contains(&word_b.contents.as_str()) {
-                                // return 等の
-                                // 予約語だった場合
-                                rlist.push(StmtElem::Special(StmtBranch {
-                                    head: word_b.contents.clone(),
-                                    code_list: Self::stmt2expr(&group[1..])?,
-                                    depth: self.depth,
-                                    loopdepth: self.loopdepth,
-                                }));
-                            } else {
-                                // 普通の変数のwordだった場合
-                                rlist.push(StmtElem::ExprElem(ExprBranch {
-                                    code_list: Self::stmt2expr(&group)?,
-                                    depth: self.depth,
-                                    loopdepth: self.loopdepth,
-                                }));
-                            }
-                        } else {
-                            // 最初の要素がwordではなかった場合
-                            rlist.push(StmtElem::ExprElem(ExprBranch {
-                                code_list: Self::stmt2expr(&group)?,
-                                depth: self.depth,
-                                loopdepth: self.loopdepth,
-                            }));
-                        }
+                        rlist.push(self.group2stmt(&group)?);
                     } else {
                         // group が空だった場合
                     }
@@ -435,8 +489,19 @@ impl StmtParser {
                         contents: comment_b.contents.clone(),
                         depth: self.depth,
                         loopdepth: self.loopdepth,
+                        span: comment_b.span,
                     }));
                 }
+                StmtElem::DecFuncElem(_)
+                | StmtElem::DecStructElem(_)
+                | StmtElem::DecEnumElem(_) => {
+                    // 関数宣言、構造体宣言、列挙型宣言はそれ自体で一つの文になる
+                    if !group.is_empty() {
+                        rlist.push(self.group2stmt(&group)?);
+                    }
+                    group.clear();
+                    rlist.push(inner.clone());
+                }
                 _ => {
                     group.push(inner.clone());
                 }
@@ -447,14 +512,381 @@ impl StmtParser {
                 code_list: Self::stmt2expr(&group)?,
                 depth: self.depth,
                 loopdepth: self.loopdepth,
+                span: merge_span(&group),
             }));
         }
         self.code_list = rlist;
         Ok(())
     }
 
+    /// `;`などで区切られた要素の集まりを文に変換する
+    fn group2stmt(&self, group: &[StmtElem]) -> Result<StmtElem, ParserError> {
+        if let StmtElem::WordElem(word_b) = &group[0] {
+            if Self::CONTROL_STATEMENT.contains(&word_b.contents.as_str()) {
+                // return 等の
+                // 予約語だった場合
+                return Ok(StmtElem::Special(StmtBranch {
+                    head: word_b.contents.clone(),
+                    code_list: Self::stmt2expr(&group[1..])?,
+                    depth: self.depth,
+                    loopdepth: self.loopdepth,
+                    span: merge_span(group),
+                }));
+            }
+        }
+        // 普通の変数のwordだった場合
+        // 最初の要素がwordではなかった場合
+        Ok(StmtElem::ExprElem(ExprBranch {
+            code_list: Self::stmt2expr(group)?,
+            depth: self.depth,
+            loopdepth: self.loopdepth,
+            span: merge_span(group),
+        }))
+    }
+
+    /// 関数宣言と構造体宣言をまとめる
+    ///
+    /// ```lichen
+    /// fn name(a: i32, b: i64) -> i32 {
+    ///     ...
+    /// }
+    /// pub_fn name() { ... }
+    /// struct Point { x: f64, y: f64 }
+    /// enum Shape { Circle(f64), Rect(f64, f64) }
+    /// ```
+    fn grouping_functions(&mut self) -> Result<(), ParserError> {
+        let mut rlist: Vec<StmtElem> = Vec::new();
+        let mut index = 0;
+
+        while index < self.code_list.len() {
+            let inner = &self.code_list[index];
+            if let StmtElem::WordElem(word_b) = inner {
+                if word_b.contents == Self::FUNCTION || word_b.contents == Self::PUB_FUNCTION {
+                    let (dec_func, next) = self.parse_dec_func(index)?;
+                    rlist.push(StmtElem::DecFuncElem(dec_func));
+                    index = next;
+                    continue;
+                }
+                if word_b.contents == Self::STRUCTURE {
+                    let (dec_struct, next) = self.parse_dec_struct(index)?;
+                    rlist.push(StmtElem::DecStructElem(dec_struct));
+                    index = next;
+                    continue;
+                }
+                if word_b.contents == Self::ENUMERATION {
+                    let (dec_enum, next) = self.parse_dec_enum(index)?;
+                    rlist.push(StmtElem::DecEnumElem(dec_enum));
+                    index = next;
+                    continue;
+                }
+            }
+            rlist.push(inner.clone());
+            index += 1;
+        }
+        self.code_list = rlist;
+        Ok(())
+    }
+
+    /// `start`から始まる関数宣言を解析し、宣言と次の要素の位置を返却する
+    fn parse_dec_func(&self, start: usize) -> Result<(DecFuncBranch, usize), ParserError> {
+        let head = &self.code_list[start];
+        let is_public = matches!(head, StmtElem::WordElem(w) if w.contents == Self::PUB_FUNCTION);
+        // 宣言の途中で終わった場合は宣言全体を指す
+        let error_span = merge_span(&self.code_list[start..]);
+        let get = |index: usize| -> Result<&StmtElem, ParserError> {
+            self.code_list
+                .get(index)
+                .ok_or(ParserError::DecFuncError(error_span))
+        };
+
+        // name
+        let name = match get(start + 1)? {
+            StmtElem::WordElem(word_b) if !Self::KEYWORDS.contains(&word_b.contents.as_str()) => {
+                word_b.contents.clone()
+            }
+            other => return Err(ParserError::DecFuncError(other.get_span())),
+        };
+        // args
+        let args = match get(start + 2)? {
+            StmtElem::ParenBlockElem(paren_b) => self.parse_func_args(&paren_b.contents)?,
+            other => return Err(ParserError::DecFuncError(other.get_span())),
+        };
+        // return type
+        let mut index = start + 3;
+        let mut return_type = None;
+        if let StmtElem::UnKnownElem(v) = get(index)? {
+            if v.contents == '-' {
+                match get(index + 1)? {
+                    StmtElem::UnKnownElem(v) if v.contents == '>' => {}
+                    other => return Err(ParserError::DecFuncError(other.get_span())),
+                }
+                index += 2;
+                let mut type_list: Vec<StmtElem> = Vec::new();
+                while !matches!(get(index)?, StmtElem::BlockElem(_)) {
+                    type_list.push(get(index)?.clone());
+                    index += 1;
+                }
+                if type_list.is_empty() {
+                    return Err(ParserError::InvalidType(get(index)?.get_span().head()));
+                }
+                return_type = Some(TypeParser::resolve_annotation(
+                    &Self::stmt2expr(&type_list)?,
+                    self.depth,
+                    self.loopdepth,
+                )?);
+            }
+        }
+        // body
+        let StmtElem::BlockElem(block_b) = get(index)? else {
+            return Err(ParserError::DecFuncError(get(index)?.get_span()));
+        };
+        Ok((
+            DecFuncBranch {
+                name,
+                args,
+                return_type,
+                contents: block_b.contents.clone(),
+                is_public,
+                depth: self.depth,
+                loopdepth: self.loopdepth,
+                span: head.get_span().merge(&block_b.span),
+            },
+            index + 1,
+        ))
+    }
+
+    /// 関数宣言の引数`a: i32, b: i64`を解析する
+    fn parse_func_args(&self, contents: &[ExprElem]) -> Result<Vec<FuncArgBranch>, ParserError> {
+        let mut c_parser =
+            CommaParser::create_parser_from_vec(contents.to_vec(), self.depth, self.loopdepth);
+        c_parser.resolve()?;
+
+        let mut rlist: Vec<FuncArgBranch> = Vec::new();
+        for item in &c_parser.code_list {
+            let ExprElem::ItemElem(item_b) = item else {
+                return Err(ParserError::DevError);
+            };
+            let mut e_parser = ExprParser::create_parser_from_vec(
+                item_b.contents.clone(),
+                self.depth,
+                self.loopdepth,
+            );
+            e_parser.code2vec()?;
+            // <name> : <type>
+            match &e_parser.code_list[..] {
+                [ExprElem::WordElem(name_b), ExprElem::UnKnownElem(colon), arg_type @ ..]
+                    if colon.contents == ':' =>
+                {
+                    rlist.push(FuncArgBranch {
+                        name: name_b.contents.clone(),
+                        arg_type: TypeParser::resolve_annotation(
+                            arg_type,
+                            self.depth,
+                            self.loopdepth,
+                        )?,
+                        span: item_b.span,
+                    });
+                }
+                _ => return Err(ParserError::DecFuncError(item_b.span)),
+            }
+        }
+        Ok(rlist)
+    }
+
+    /// `start`から始まる構造体宣言を解析し、宣言と次の要素の位置を返却する
+    fn parse_dec_struct(&self, start: usize) -> Result<(DecStructBranch, usize), ParserError> {
+        let head = &self.code_list[start];
+        let error_span = merge_span(&self.code_list[start..]);
+        let get = |index: usize| -> Result<&StmtElem, ParserError> {
+            self.code_list
+                .get(index)
+                .ok_or(ParserError::DecStructError(error_span))
+        };
+
+        // name
+        let name = match get(start + 1)? {
+            StmtElem::WordElem(word_b)
+                if !Self::KEYWORDS.contains(&word_b.contents.as_str())
+                    && is_type_name(&word_b.contents) =>
+            {
+                word_b.contents.clone()
+            }
+            other => return Err(ParserError::DecStructError(other.get_span())),
+        };
+        // fields
+        let StmtElem::BlockElem(block_b) = get(start + 2)? else {
+            return Err(ParserError::DecStructError(get(start + 2)?.get_span()));
+        };
+        let fields = self.parse_struct_fields(&Self::stmt2expr(&block_b.contents)?)?;
+        Ok((
+            DecStructBranch {
+                name,
+                fields,
+                depth: self.depth,
+                loopdepth: self.loopdepth,
+                span: head.get_span().merge(&block_b.span),
+            },
+            start + 3,
+        ))
+    }
+
+    /// 構造体宣言のフィールド`x: f64, y: f64`を解析する
+    /// 最後のフィールドの後の`,`は無視する
+    fn parse_struct_fields(
+        &self,
+        contents: &[ExprElem],
+    ) -> Result<Vec<StructFieldBranch>, ParserError> {
+        let mut c_parser =
+            CommaParser::create_parser_from_vec(contents.to_vec(), self.depth, self.loopdepth);
+        c_parser.resolve()?;
+
+        let mut rlist: Vec<StructFieldBranch> = Vec::new();
+        for item in &c_parser.code_list {
+            let ExprElem::ItemElem(item_b) = item else {
+                return Err(ParserError::DevError);
+            };
+            let mut e_parser = ExprParser::create_parser_from_vec(
+                item_b.contents.clone(),
+                self.depth,
+                self.loopdepth,
+            );
+            e_parser.code2vec()?;
+            // <name> : <type>
+            match &e_parser.code_list[..] {
+                [] => continue,
+                [ExprElem::WordElem(name_b), ExprElem::UnKnownElem(colon), field_type @ ..]
+                    if colon.contents == ':' =>
+                {
+                    rlist.push(StructFieldBranch {
+                        name: name_b.contents.clone(),
+                        field_type: TypeParser::resolve_annotation(
+                            field_type,
+                            self.depth,
+                            self.loopdepth,
+                        )?,
+                        span: item_b.span,
+                    });
+                }
+                _ => return Err(ParserError::DecStructError(item_b.span)),
+            }
+        }
+        Ok(rlist)
+    }
+
+    /// `start`から始まる列挙型宣言を解析し、宣言と次の要素の位置を返却する
+    fn parse_dec_enum(&self, start: usize) -> Result<(DecEnumBranch, usize), ParserError> {
+        let head = &self.code_list[start];
+        let error_span = merge_span(&self.code_list[start..]);
+        let get = |index: usize| -> Result<&StmtElem, ParserError> {
+            self.code_list
+                .get(index)
+                .ok_or(ParserError::DecEnumError(error_span))
+        };
+
+        // name
+        let name = match get(start + 1)? {
+            StmtElem::WordElem(word_b)
+                if !Self::KEYWORDS.contains(&word_b.contents.as_str())
+                    && is_type_name(&word_b.contents) =>
+            {
+                word_b.contents.clone()
+            }
+            other => return Err(ParserError::DecEnumError(other.get_span())),
+        };
+        // variants
+        let StmtElem::BlockElem(block_b) = get(start + 2)? else {
+            return Err(ParserError::DecEnumError(get(start + 2)?.get_span()));
+        };
+        let variants = self.parse_enum_variants(&Self::stmt2expr(&block_b.contents)?)?;
+        Ok((
+            DecEnumBranch {
+                name,
+                variants,
+                depth: self.depth,
+                loopdepth: self.loopdepth,
+                span: head.get_span().merge(&block_b.span),
+            },
+            start + 3,
+        ))
+    }
+
+    /// 列挙型宣言のヴァリアント`Red` `Circle(f64)`を解析する
+    /// 最後のヴァリアントの後の`,`は無視する
+    fn parse_enum_variants(
+        &self,
+        contents: &[ExprElem],
+    ) -> Result<Vec<EnumVariantBranch>, ParserError> {
+        let mut c_parser =
+            CommaParser::create_parser_from_vec(contents.to_vec(), self.depth, self.loopdepth);
+        c_parser.resolve()?;
+
+        let mut rlist: Vec<EnumVariantBranch> = Vec::new();
+        for item in &c_parser.code_list {
+            let ExprElem::ItemElem(item_b) = item else {
+                return Err(ParserError::DevError);
+            };
+            let mut e_parser = ExprParser::create_parser_from_vec(
+                item_b.contents.clone(),
+                self.depth,
+                self.loopdepth,
+            );
+            e_parser.code2vec()?;
+            // <name> or <name>(<type>, ...)
+            let (name_b, fields) = match &e_parser.code_list[..] {
+                [] => continue,
+                [ExprElem::WordElem(name_b)] => (name_b, Vec::new()),
+                [ExprElem::FuncElem(func_b)] => match (&*func_b.name, &func_b.contents[..]) {
+                    (ExprElem::WordElem(name_b), [ExprElem::ParenBlockElem(paren_b)]) => {
+                        (name_b, self.parse_variant_fields(&paren_b.contents)?)
+                    }
+                    _ => return Err(ParserError::DecEnumError(item_b.span)),
+                },
+                _ => return Err(ParserError::DecEnumError(item_b.span)),
+            };
+            if Self::KEYWORDS.contains(&name_b.contents.as_str()) || !is_type_name(&name_b.contents)
+            {
+                return Err(ParserError::DecEnumError(name_b.span));
+            }
+            rlist.push(EnumVariantBranch {
+                name: name_b.contents.clone(),
+                fields,
+                span: item_b.span,
+            });
+        }
+        Ok(rlist)
+    }
+
+    /// ヴァリアントが持つ値の型`(f64, f64)`を解析する
+    fn parse_variant_fields(&self, contents: &[ExprElem]) -> Result<Vec<TypeElem>, ParserError> {
+        let mut c_parser =
+            CommaParser::create_parser_from_vec(contents.to_vec(), self.depth, self.loopdepth);
+        c_parser.resolve()?;
+
+        let mut rlist: Vec<TypeElem> = Vec::new();
+        for item in &c_parser.code_list {
+            let ExprElem::ItemElem(item_b) = item else {
+                return Err(ParserError::DevError);
+            };
+            let mut e_parser = ExprParser::create_parser_from_vec(
+                item_b.contents.clone(),
+                self.depth,
+                self.loopdepth,
+            );
+            e_parser.code2vec()?;
+            if e_parser.code_list.is_empty() {
+                continue;
+            }
+            rlist.push(TypeParser::resolve_annotation(
+                &e_parser.code_list,
+                self.depth,
+                self.loopdepth,
+            )?);
+        }
+        Ok(rlist)
+    }
+
     /// function for converting `stmt` to `expr`
-    fn stmt2expr(i: &[StmtElem]) -> Result<Vec<ExprElem>, ParserError> {
+    pub fn stmt2expr(i: &[StmtElem]) -> Result<Vec<ExprElem>, ParserError> {
         let mut rlist: Vec<ExprElem> = Vec::new();
         for inner in i.iter() {
             rlist.push(match inner {
@@ -466,12 +898,40 @@ impl StmtParser {
                 StmtElem::OpeElem(a) => ExprElem::OpeElem(a.clone()),
                 StmtElem::WordElem(a) => ExprElem::WordElem(a.clone()),
                 StmtElem::UnKnownElem(a) => ExprElem::UnKnownElem(a.clone()),
-                _ => return Err(ParserError::UnableToConvertType),
+                StmtElem::ErrorElem(a) => ExprElem::ErrorElem(a.clone()),
+                _ => return Err(ParserError::UnableToConvertType(inner.get_span())),
             });
         }
         Ok(rlist)
     }
 
+    /// 回復モードで解析する
+    ///
+    /// 最初のエラーで中断せず、解析できなかった部分を`ErrorElem`に置き換えて
+    /// `self.code_list`に部分的な木を構築する
+    /// 文(`;`)やブロック(`{}`)の単位で解析を再開し、
+    /// 発生したすべてのエラーをソース上の位置の順で返却する
+    pub fn resolve_recovering(&mut self) -> Vec<ParserError> {
+        self.errors = Some(Vec::new());
+        let fatal = self.code2vec().err();
+        let mut errors = self.errors.take().unwrap_or_default();
+        if let Some(e) = fatal {
+            // 回復できないエラーの場合は全体をエラーの要素とする
+            self.code_list = vec![StmtElem::ErrorElem(ErrorBranch {
+                error: e.clone(),
+                depth: self.depth,
+                loopdepth: self.loopdepth,
+                span: merge_span(&self.code_list),
+            })];
+            errors.push(e);
+        }
+        for inner in &mut self.code_list {
+            inner.resolve_self_recovering(&mut errors);
+        }
+        errors.sort_by_key(|e| e.get_span().start);
+        errors
+    }
+
     pub fn create_parser_from_vec(
         code_list: Vec<StmtElem>,
         depth: isize,
@@ -482,6 +942,7 @@ impl StmtParser {
             code_list,
             depth,
             loopdepth,
+            errors: None,
         }
     }
 }
@@ -493,6 +954,7 @@ impl Parser<'_> for StmtParser {
             code_list: Self::code2_vec_pre_proc_func(&code),
             depth,
             loopdepth,
+            errors: None,
         }
     }
 
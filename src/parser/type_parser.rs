@@ -1,71 +1,84 @@
 use crate::errors::parser_errors::ParserError;
 
 use crate::abs::ast::*;
+use crate::token::list_block::ListBlockBranch;
+use crate::token::ttype::array::ArrayTypeBranch;
+use crate::token::ttype::primitive::{PrimitiveBranch, PrimitiveType};
 
-pub struct TypeParser {
-    pub code: String,
-    pub code_list: Vec<TypeElem>,
-    pub depth: isize,
-    pub loopdepth: isize,
-}
+/// # TypeParser
+/// 式の要素として分割された型注釈を`TypeElem`に変換する
+pub struct TypeParser;
 
 impl TypeParser {
-    fn code2vec(&mut self) -> Result<(), ParserError> {
-        self.grouping_elements(TypeElem::TypeBlockElem, '<', '>')?;
-        Ok(())
-    }
-
-    fn grouping_elements<T>(
-        &mut self,
-        elemtype: fn(T) -> TypeElem,
-        open_char: char,
-        close_char: char,
-    ) -> Result<(), ParserError>
-    where
-        T: TypeAreaBranch,
-    {
-        let mut rlist: Vec<TypeElem> = Vec::new();
-        let mut group: Vec<TypeElem> = Vec::new();
-        let mut depth: isize = 0;
-
-        for inner in &self.code_list {
-            if let TypeElem::UnKnownElem(ref b) = inner {
-                if b.contents == open_char {
-                    match depth {
-                        0 => { /*pass*/ }
-                        1.. => group.push(inner.clone()),
-                        _ => return Err(ParserError::BraceNotOpened),
-                    }
-                    depth += 1;
-                } else if b.contents == close_char {
-                    depth -= 1;
-                    match depth {
-                        0 => {
-                            rlist.push(elemtype(TypeAreaBranch::new(group.clone(), self.depth)));
-                            group.clear();
-                        }
-                        1.. => group.push(inner.clone()),
-                        _ => return Err(ParserError::BraceNotOpened),
-                    }
-                } else {
-                    match depth {
-                        0 => rlist.push(inner.clone()),
-                        1.. => group.push(inner.clone()),
-                        _ => return Err(ParserError::BraceNotOpened),
-                    }
-                }
-            } else {
-                match depth {
-                    0 => rlist.push(inner.clone()),
-                    1.. => group.push(inner.clone()),
-                    _ => return Err(ParserError::BraceNotClosed),
-                }
+    /// 式の要素として分割された型注釈を型に変換する
+    /// プリミティブ型以外の名前はユーザー定義の型の名前とし、生成時に解決する
+    /// ```lichen
+    /// fn f(a: i32, p: Point) -> i64 { ... }
+    /// //      ^^^     ^^^^^     ^^^
+    /// fn g(a: [i32; 10]) { ... }
+    /// //      ^^^^^^^^^
+    /// ```
+    pub fn resolve_annotation(
+        list: &[ExprElem],
+        depth: isize,
+        loopdepth: isize,
+    ) -> Result<TypeElem, ParserError> {
+        let elems: Vec<&ExprElem> = list
+            .iter()
+            .filter(|inner| !matches!(inner, ExprElem::CommentElem(_)))
+            .filter(|inner| !inner.is_whitespace())
+            .collect();
+        if let [ExprElem::WordElem(word_b)] = elems[..] {
+            if let Some(primitive_type) = PrimitiveType::from_name(&word_b.contents) {
+                return Ok(TypeElem::PrimitiveElem(PrimitiveBranch {
+                    primitive_type,
+                    depth,
+                    loopdepth,
+                    span: word_b.span,
+                }));
+            }
+            if is_type_name(&word_b.contents) {
+                return Ok(TypeElem::WordElem(word_b.clone()));
             }
         }
-        if depth != 0 {
-            return Err(ParserError::BraceNotClosed);
+        if let [ExprElem::ListBlockElem(list_block_b)] = elems[..] {
+            return Self::resolve_array_annotation(list_block_b, depth, loopdepth);
         }
-        self.code_list = rlist;
-        Ok(())
+        Err(ParserError::InvalidType(merge_span(elems)))
     }
+
+    /// `[<type>; <length>]`
+    /// 関数の引数などでは`[]`の中身がまだ解析されていないため、ここで解析する
+    fn resolve_array_annotation(
+        list_block_b: &ListBlockBranch,
+        depth: isize,
+        loopdepth: isize,
+    ) -> Result<TypeElem, ParserError> {
+        let mut list_block_b = list_block_b.clone();
+        if list_block_b.repeat.is_none() {
+            list_block_b.resolve_self()?;
+        }
+        let (Some(length), [ExprElem::ItemElem(item_b)]) =
+            (list_block_b.repeat, &list_block_b.contents[..])
+        else {
+            return Err(ParserError::InvalidType(list_block_b.span));
+        };
+        Ok(TypeElem::ArrayElem(ArrayTypeBranch {
+            element: Box::new(Self::resolve_annotation(
+                &item_b.contents,
+                depth,
+                loopdepth,
+            )?),
+            length,
+            depth,
+            loopdepth,
+            span: list_block_b.span,
+        }))
+    }
+}
+
+/// 型の名前として使用できる単語かどうか
+pub(crate) fn is_type_name(word: &str) -> bool {
+    !word.starts_with(|c: char| c.is_ascii_digit())
+        && word.chars().all(|c| c.is_alphanumeric() || c == '_')
 }
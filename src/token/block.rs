@@ -1,4 +1,5 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 use crate::errors::parser_errors::ParserError;
 use crate::parser::stmt_parser::*;
 
@@ -11,6 +12,7 @@ pub struct BlockBranch {
     pub contents: Vec<StmtElem>,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
 }
 
 impl RecursiveAnalysisElements for BlockBranch {
@@ -32,14 +34,25 @@ impl RecursiveAnalysisElements for BlockBranch {
             Err(e) => Err(e),
         }
     }
+
+    fn resolve_self_recovering(&mut self, errors: &mut Vec<ParserError>) {
+        let mut parser = StmtParser::create_parser_from_vec(
+            self.contents.clone(),
+            self.depth + 1,
+            self.loopdepth,
+        );
+        errors.extend(parser.resolve_recovering());
+        self.contents = parser.code_list;
+    }
 }
 
 impl ASTAreaBranch<StmtElem> for BlockBranch {
-    fn new(contents: Vec<StmtElem>, depth: isize, loopdepth: isize) -> Self {
+    fn new(contents: Vec<StmtElem>, depth: isize, loopdepth: isize, span: Span) -> Self {
         Self {
             contents,
             depth,
             loopdepth,
+            span,
         }
     }
 }
@@ -1,10 +1,12 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 
 #[derive(Clone, Debug)]
 pub struct CommentBranch {
     pub contents: String,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
 }
 
 impl ASTBranch for CommentBranch {
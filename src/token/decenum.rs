@@ -0,0 +1,59 @@
+use crate::abs::ast::*;
+use crate::abs::span::Span;
+
+/// # EnumVariantBranch
+/// 列挙型宣言のヴァリアント
+/// `fields`はヴァリアントが持つ値の型で、値を持たない場合は空
+/// ```lichen
+/// enum Shape { Circle(f64), Rect(f64, f64), Empty }
+/// //           ^^^^^^^^^^^
+/// ```
+#[derive(Clone, Debug)]
+pub struct EnumVariantBranch {
+    pub name: String,
+    pub fields: Vec<TypeElem>,
+    pub span: Span,
+}
+
+/// # DecEnumBranch
+/// 列挙型宣言を格納するstruct
+/// 名前とヴァリアントは解析時に決定する
+/// タグとレコードの配置は生成時に`EnumLayout`として計算する
+#[derive(Clone, Debug)]
+pub struct DecEnumBranch {
+    pub name: String,
+    pub variants: Vec<EnumVariantBranch>,
+    pub depth: isize,
+    pub loopdepth: isize,
+    pub span: Span,
+}
+
+impl ASTBranch for DecEnumBranch {
+    fn show(&self) {
+        print!("{}", self.get_show_as_string());
+    }
+
+    fn get_show_as_string(&self) -> String {
+        let variants: Vec<String> = self
+            .variants
+            .iter()
+            .map(|variant| {
+                if variant.fields.is_empty() {
+                    return variant.name.clone();
+                }
+                let fields: Vec<String> = variant
+                    .fields
+                    .iter()
+                    .map(|field| field.get_show_as_string())
+                    .collect();
+                format!("{}({})", variant.name, fields.join(", "))
+            })
+            .collect();
+        format!(
+            "{}DecEnum {} {{ {} }}\n",
+            " ".repeat(self.depth as usize * 4),
+            self.name,
+            variants.join(", ")
+        )
+    }
+}
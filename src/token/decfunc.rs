@@ -1,15 +1,87 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 use crate::errors::parser_errors::ParserError;
+use crate::parser::core_parser::Parser;
+use crate::parser::stmt_parser::StmtParser;
 
+/// # FuncArgBranch
+/// 関数宣言の引数
+/// ```lichen
+/// fn add(a: i32, b: i32) -> i32 { ... }
+/// //     ^^^^^^
+/// ```
+#[derive(Clone, Debug)]
+pub struct FuncArgBranch {
+    pub name: String,
+    pub arg_type: TypeElem,
+    pub span: Span,
+}
+
+/// # DecFuncBranch
+/// 関数宣言を格納するstruct
+/// 名前、引数、返り値の型は解析時に決定し、
+/// 本体(`{}`)は`resolve_self`で`StmtParser`に丸投げする
 #[derive(Clone, Debug)]
 pub struct DecFuncBranch {
+    pub name: String,
+    pub args: Vec<FuncArgBranch>,
+    pub return_type: Option<TypeElem>,
     pub contents: Vec<StmtElem>,
+    pub is_public: bool,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
+}
+
+impl DecFuncBranch {
+    /// 本体を解析するパーサ
+    /// 関数の内側は外側のループに属さないため`loopdepth`は0から数える
+    fn body_parser(&self) -> StmtParser {
+        StmtParser::create_parser_from_vec(self.contents.clone(), self.depth + 1, 0)
+    }
 }
 
 impl RecursiveAnalysisElements for DecFuncBranch {
     fn resolve_self(&mut self) -> Result<(), ParserError> {
-        todo!()
+        let mut parser = self.body_parser();
+        parser.resolve()?;
+        self.contents = parser.code_list;
+        Ok(())
+    }
+
+    fn resolve_self_recovering(&mut self, errors: &mut Vec<ParserError>) {
+        let mut parser = self.body_parser();
+        errors.extend(parser.resolve_recovering());
+        self.contents = parser.code_list;
+    }
+}
+
+impl ASTBranch for DecFuncBranch {
+    fn show(&self) {
+        print!("{}", self.get_show_as_string());
+    }
+
+    fn get_show_as_string(&self) -> String {
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| format!("{}: {}", arg.name, arg.arg_type.get_show_as_string()))
+            .collect();
+        let mut rtext = format!(
+            "{}DecFunc{} {}({})",
+            " ".repeat(self.depth as usize * 4),
+            if self.is_public { "(pub)" } else { "" },
+            self.name,
+            args.join(", ")
+        );
+        if let Some(return_type) = &self.return_type {
+            rtext.push_str(&format!(" -> {}", return_type.get_show_as_string()));
+        }
+        rtext.push_str(" {\n");
+        for inner in &self.contents {
+            rtext.push_str(&inner.get_show_as_string());
+        }
+        rtext.push_str(&format!("{}}}\n", " ".repeat(self.depth as usize * 4)));
+        rtext
     }
 }
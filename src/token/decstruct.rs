@@ -0,0 +1,48 @@
+use crate::abs::ast::*;
+use crate::abs::span::Span;
+
+/// # StructFieldBranch
+/// 構造体宣言のフィールド
+/// ```lichen
+/// struct Point { x: f64, y: f64 }
+/// //             ^^^^^^
+/// ```
+#[derive(Clone, Debug)]
+pub struct StructFieldBranch {
+    pub name: String,
+    pub field_type: TypeElem,
+    pub span: Span,
+}
+
+/// # DecStructBranch
+/// 構造体宣言を格納するstruct
+/// 名前とフィールドは解析時に決定する
+/// 配置(オフセットと大きさ)は生成時に`StructLayout`として計算する
+#[derive(Clone, Debug)]
+pub struct DecStructBranch {
+    pub name: String,
+    pub fields: Vec<StructFieldBranch>,
+    pub depth: isize,
+    pub loopdepth: isize,
+    pub span: Span,
+}
+
+impl ASTBranch for DecStructBranch {
+    fn show(&self) {
+        print!("{}", self.get_show_as_string());
+    }
+
+    fn get_show_as_string(&self) -> String {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|field| format!("{}: {}", field.name, field.field_type.get_show_as_string()))
+            .collect();
+        format!(
+            "{}DecStruct {} {{ {} }}\n",
+            " ".repeat(self.depth as usize * 4),
+            self.name,
+            fields.join(", ")
+        )
+    }
+}
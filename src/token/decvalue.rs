@@ -1,4 +1,5 @@
 use crate::abs::ast::ExprElem;
+use crate::abs::span::Span;
 
 #[derive(Clone, Debug)]
 pub struct DecValueBranch {
@@ -6,6 +7,7 @@ pub struct DecValueBranch {
     pub contents: Vec<ExprElem>,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
     // flags
     pub is_mutable: bool,
     pub is_public: bool,
@@ -0,0 +1,32 @@
+use crate::abs::ast::*;
+use crate::abs::span::Span;
+use crate::errors::parser_errors::ParserError;
+
+/// # ErrorBranch
+/// 回復モードで解析できなかった部分を表すstruct
+/// 原因となったエラーを保持する
+#[derive(Clone, Debug)]
+pub struct ErrorBranch {
+    pub error: ParserError,
+    pub depth: isize,
+    pub loopdepth: isize,
+    pub span: Span,
+}
+
+impl ASTBranch for ErrorBranch {
+    fn show(&self) {
+        println!(
+            "{}ErrorBranch {:?}",
+            " ".repeat(self.depth as usize * 4),
+            self.error
+        )
+    }
+
+    fn get_show_as_string(&self) -> String {
+        format!(
+            "{}ErrorBranch {:?}\n",
+            " ".repeat(self.depth as usize * 4),
+            self.error
+        )
+    }
+}
@@ -0,0 +1,36 @@
+use crate::abs::ast::*;
+use crate::abs::span::Span;
+use crate::errors::parser_errors::ParserError;
+use crate::token::ttype::primitive::PrimitiveType;
+
+/// # FieldBranch
+/// 構造体のフィールドへのアクセス`p.x`を格納するstruct
+/// `p.a.x`は`(p.a).x`のように入れ子になる
+#[derive(Clone, Debug)]
+pub struct FieldBranch {
+    pub base: Box<ExprElem>,
+    pub name: String,
+    pub depth: isize,
+    pub loopdepth: isize,
+    pub span: Span,
+    /// 型検査で決定したフィールドのオフセット
+    pub offset: u32,
+    /// 型検査で決定したフィールドのwasm上の型
+    pub field_type: Option<PrimitiveType>,
+}
+
+impl ASTBranch for FieldBranch {
+    fn show(&self) {
+        println!("{}", self.get_show_as_string());
+    }
+
+    fn get_show_as_string(&self) -> String {
+        format!("{}.{}", self.base.get_show_as_string(), self.name)
+    }
+}
+
+impl RecursiveAnalysisElements for FieldBranch {
+    fn resolve_self(&mut self) -> Result<(), ParserError> {
+        self.base.resolve_self()
+    }
+}
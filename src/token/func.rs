@@ -1,7 +1,6 @@
 use crate::abs::ast::*;
-use crate::abs::gen::*;
+use crate::abs::span::Span;
 
-use crate::errors::generate_errors::GenerateError;
 use crate::errors::parser_errors::ParserError;
 use crate::parser::comma_parser::CommaParser;
 use crate::parser::core_parser::Parser;
@@ -16,6 +15,7 @@ pub struct FuncBranch {
     pub contents: Vec<ExprElem>, // 引数
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
 }
 
 impl ASTBranch for FuncBranch {
@@ -70,6 +70,7 @@ impl RecursiveAnalysisElements for FuncBranch {
             contents: v,
             depth,
             loopdepth,
+            ..
         }) = first_elem
         {
             if 1 < self.contents.len() {
@@ -1,8 +1,6 @@
 use crate::abs::ast::*;
-use crate::abs::gen::Wasm_gen;
-use crate::errors::generate_errors::GenerateError;
+use crate::abs::span::Span;
 use crate::errors::parser_errors::ParserError;
-use crate::gen::wasm::MEMORY_SPACE_NAME;
 use crate::parser::expr_parser::ExprParser;
 
 /// 引数などの式を格納します
@@ -11,6 +9,7 @@ pub struct ItemBranch {
     pub contents: Vec<ExprElem>,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
 }
 
 impl ItemBranch {
@@ -1,19 +1,30 @@
 use crate::abs::ast::*;
-use crate::abs::gen::Wasm_gen;
+use crate::abs::span::Span;
 use crate::errors::parser_errors::ParserError;
 use crate::parser::comma_parser::CommaParser;
 use crate::parser::core_parser::Parser;
 
-use crate::errors::generate_errors::GenerateError;
-use crate::gen::wasm::MEMORY_SPACE_NAME;
 use crate::token::list_block::ListBlockBranch;
+use crate::token::ttype::primitive::PrimitiveType;
 
+/// # ListBranch
+/// 配列の要素へのアクセス`a[i]`を格納するstruct
+/// `a[i][j]`は`(a[i])[j]`のように入れ子になる
 #[derive(Clone, Debug)]
 pub struct ListBranch {
     pub name: Box<ExprElem>,
     pub contents: Vec<ExprElem>,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
+    /// 型検査で決定した要素のwasm上の型
+    pub element_type: Option<PrimitiveType>,
+    /// 型検査で決定した要素の大きさ
+    pub element_size: u32,
+    /// 型検査で決定した配列の要素の数
+    pub length: u32,
+    /// 添字を範囲の検査の間保持する隠れた変数(`Resolver`で決定する)
+    pub local: String,
 }
 
 impl ASTBranch for ListBranch {
@@ -60,6 +71,7 @@ impl RecursiveAnalysisElements for ListBranch {
             contents: v,
             depth,
             loopdepth,
+            ..
         }) = first_elem
         {
             let mut c_parser = CommaParser::create_parser_from_vec(v.to_vec(), *depth, *loopdepth);
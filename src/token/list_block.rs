@@ -1,17 +1,86 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 use crate::errors::parser_errors::ParserError;
 use crate::parser::comma_parser::CommaParser;
 use crate::parser::core_parser::Parser;
 use crate::parser::expr_parser::ExprParser;
+use crate::token::num::NumValue;
+use crate::token::ttype::primitive::PrimitiveType;
 
 /// #ListBlockBranch
 /// listを格納するためのデータstruct
 /// 中では式を解析するパーサを呼び出す必要がある
+///
+/// 配列のリテラル`[1, 2, 3]`、同じ値を並べる`[0; 10]`、配列の型`[i32; 10]`として使われる
 #[derive(Clone, Debug)]
 pub struct ListBlockBranch {
     pub contents: Vec<ExprElem>,
+    /// `[0; 10]`の要素の数
+    pub repeat: Option<u32>,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
+    /// 値を格納する間レコードのアドレスを保持する隠れた変数(`Resolver`で決定する)
+    pub local: String,
+    /// 型検査で決定した要素のwasm上の型
+    pub element_type: Option<PrimitiveType>,
+    /// 型検査で決定した要素の大きさ
+    pub element_size: u32,
+}
+
+impl ListBlockBranch {
+    /// 要素の数
+    pub fn len(&self) -> usize {
+        self.repeat.map_or(self.contents.len(), |n| n as usize)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 閉じられていないリストの末尾の`;`を取り除く
+    /// 末尾の`;`は文の終わりであり、`[0; n]`の区切りとしては扱わない
+    pub fn trim_unclosed(&mut self) {
+        while let Some(ExprElem::UnKnownElem(v)) = self.contents.last() {
+            if !v.contents.is_whitespace() && v.contents != ';' {
+                break;
+            }
+            self.contents.pop();
+        }
+    }
+
+    /// 括弧の外側にある`;`の位置
+    fn find_semicolon(&self) -> Option<usize> {
+        let mut depth = 0;
+        for (i, inner) in self.contents.iter().enumerate() {
+            if let ExprElem::UnKnownElem(v) = inner {
+                match v.contents {
+                    '(' | '[' | '{' => depth += 1,
+                    ')' | ']' | '}' => depth -= 1,
+                    ';' if depth == 0 => return Some(i),
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// `[0; 10]`の`10`を解析する
+    /// 要素の数は整数のリテラルでなければならない
+    fn parse_repeat(&self, contents: Vec<ExprElem>) -> Result<u32, ParserError> {
+        let span = merge_span(&contents);
+        let mut e_parser = ExprParser::create_parser_from_vec(contents, self.depth, self.loopdepth);
+        e_parser.code2vec()?;
+        match &e_parser.code_list[..] {
+            [ExprElem::NumElem(num_b)] if num_b.suffix.is_none() => match num_b.value {
+                NumValue::Int(n) => {
+                    u32::try_from(n).map_err(|_| ParserError::ArrayLengthError(span))
+                }
+                NumValue::Float(_) => Err(ParserError::ArrayLengthError(span)),
+            },
+            _ => Err(ParserError::ArrayLengthError(span)),
+        }
+    }
 }
 
 impl ASTBranch for ListBlockBranch {
@@ -29,32 +98,45 @@ impl ASTBranch for ListBlockBranch {
         for i in &self.contents {
             contents_group = format!("{}{}", contents_group, i.get_show_as_string());
         }
+        if let Some(n) = self.repeat {
+            contents_group = format!("{}; {}", contents_group, n);
+        }
         let close_paren = format!("{})", " ".repeat(self.depth as usize));
         format!("{}{}{}", list_name, contents_group, close_paren)
     }
 }
 
 impl ASTAreaBranch<ExprElem> for ListBlockBranch {
-    fn new(contents: Vec<ExprElem>, depth: isize, loopdepth: isize) -> Self {
+    fn new(contents: Vec<ExprElem>, depth: isize, loopdepth: isize, span: Span) -> Self {
         Self {
             contents,
+            repeat: None,
             depth,
             loopdepth,
+            span,
+            local: String::new(),
+            element_type: None,
+            element_size: 0,
         }
     }
 }
 
 impl RecursiveAnalysisElements for ListBlockBranch {
     fn resolve_self(&mut self) -> Result<(), ParserError> {
+        // `[0; 10]`は値と要素の数に分ける
+        if let Some(position) = self.find_semicolon() {
+            let length = self.contents.split_off(position + 1);
+            self.contents.pop();
+            self.repeat = Some(self.parse_repeat(length)?);
+        }
+        // `[1, 2, 3]`の要素をそれぞれ`ItemBranch`にする
         let mut c_parser =
             CommaParser::create_parser_from_vec(self.contents.clone(), self.depth, self.loopdepth);
-        c_parser.code2vec()?;
         c_parser.resolve()?;
-        let mut e_parser =
-            ExprParser::create_parser_from_vec(self.contents.clone(), self.depth, self.loopdepth);
-        e_parser.code2vec()?;
-        e_parser.resolve()?;
-        self.contents = e_parser.code_list;
+        self.contents = c_parser.code_list;
+        for inner in &mut self.contents {
+            inner.resolve_self()?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,197 @@
+use crate::abs::ast::*;
+use crate::abs::span::Span;
+use crate::errors::parser_errors::ParserError;
+use crate::parser::comma_parser::CommaParser;
+use crate::parser::core_parser::Parser;
+use crate::parser::expr_parser::ExprParser;
+use crate::token::ttype::primitive::PrimitiveType;
+
+/// # MatchBranch
+/// `match (x) { 0 => a, 1..10 => b, _ => c }`を格納するstruct
+/// `expr`は`()`の中の式、`arms`は`{}`の中を`,`で区切ったそれぞれの腕
+/// 値を持つ式として扱い、全ての腕の値の型は一致しなければならない
+#[derive(Clone, Debug)]
+pub struct MatchBranch {
+    pub expr: Vec<ExprElem>,
+    pub arms: Vec<MatchArmBranch>,
+    pub depth: isize,
+    pub loopdepth: isize,
+    pub span: Span,
+    /// 検査する値を保持する隠れた変数(`Resolver`で決定する)
+    pub local: String,
+    /// 型検査で決定した検査する値の型
+    pub expr_type: Option<PrimitiveType>,
+    /// 型検査で決定した`match`全体の値の型
+    pub result_type: Option<PrimitiveType>,
+    /// 型検査で決定した、検査する値がタグを格納したレコードのアドレスかどうか
+    pub boxed: bool,
+}
+
+/// # MatchArmBranch
+/// `pattern => contents`
+/// パターンは数値リテラル、数値リテラルの範囲`1..10` `1..=9`、ヴァリアント`Circle(r)` `Move(Up, _)`、`_`のいずれか
+#[derive(Clone, Debug)]
+pub struct MatchArmBranch {
+    pub pattern: Vec<ExprElem>,
+    pub contents: Vec<ExprElem>,
+    pub depth: isize,
+    pub loopdepth: isize,
+    pub span: Span,
+    /// 型検査で決定したヴァリアントのパターンのタグ
+    pub tag: Option<u32>,
+    /// 型検査で決定したヴァリアントが持つ値に対する検査
+    /// タグの検査に一致した場合のみ順に検査する
+    pub tests: Vec<FieldTest>,
+    /// 型検査で決定したパターンで束縛する変数(名前, 値を読み出すオフセットの列, wasm上の型)
+    pub bindings: Vec<(String, Vec<u32>, PrimitiveType)>,
+}
+
+/// # FieldTest
+/// `Move(Up, 1..10)`の`Up` `1..10`のような、ヴァリアントが持つ値に対する検査
+/// `path`は検査する値まで、検査する値のレコードから順にアドレスを読み出すオフセットの列
+#[derive(Clone, Debug)]
+pub struct FieldTest {
+    pub path: Vec<u32>,
+    pub value_type: PrimitiveType,
+    pub kind: FieldTestKind,
+}
+
+#[derive(Clone, Debug)]
+pub enum FieldTestKind {
+    /// 列挙型の値のタグ
+    Tag(u32),
+    /// 型検査済みの数値リテラル
+    Literal(ExprElem),
+    /// 範囲の演算子と型検査済みの両端
+    Range(String, ExprElem, ExprElem),
+}
+
+impl MatchBranch {
+    /// `{}`の中身を`,`で区切り、`=>`でパターンと値に分ける
+    /// 解析は`resolve_self`で行う
+    pub fn split_arms(
+        contents: Vec<ExprElem>,
+        depth: isize,
+        loopdepth: isize,
+    ) -> Result<Vec<MatchArmBranch>, ParserError> {
+        let mut c_parser = CommaParser::create_parser_from_vec(contents, depth, loopdepth);
+        c_parser.resolve()?;
+
+        let mut arms = Vec::new();
+        for item in c_parser.code_list {
+            let ExprElem::ItemElem(item_b) = item else {
+                return Err(ParserError::DevError);
+            };
+            let is_blank = item_b.contents.iter().all(
+                |inner| matches!(inner, ExprElem::UnKnownElem(v) if ExprParser::SPLIT_CHAR.contains(&v.contents)),
+            );
+            if is_blank {
+                // 最後の腕の後の`,`
+                continue;
+            }
+            let arrow = item_b.contents.windows(2).position(|pair| {
+                matches!(
+                    pair,
+                    [ExprElem::UnKnownElem(eq), ExprElem::UnKnownElem(gt)]
+                        if eq.contents == '=' && gt.contents == '>' && eq.span.end == gt.span.start
+                )
+            });
+            let Some(arrow) = arrow else {
+                return Err(ParserError::MatchArmError(item_b.span));
+            };
+            arms.push(MatchArmBranch {
+                pattern: item_b.contents[..arrow].to_vec(),
+                contents: item_b.contents[arrow + 2..].to_vec(),
+                depth,
+                loopdepth,
+                span: item_b.span,
+                tag: None,
+                tests: Vec::new(),
+                bindings: Vec::new(),
+            });
+        }
+        Ok(arms)
+    }
+}
+
+impl ASTBranch for MatchBranch {
+    fn show(&self) {
+        println!("match");
+        println!("expr");
+        for i in &self.expr {
+            i.show()
+        }
+        println!("{}{{", " ".repeat(self.depth as usize * 4));
+        for i in &self.arms {
+            i.show()
+        }
+        println!("{}}}", " ".repeat(self.depth as usize * 4));
+    }
+
+    fn get_show_as_string(&self) -> String {
+        let mut expr_string = String::new();
+        for i in &self.expr {
+            expr_string = format!("{}{}", expr_string, i.get_show_as_string());
+        }
+        let mut arms_string = format!("{}{{", " ".repeat(self.depth as usize * 4));
+        for i in &self.arms {
+            arms_string = format!("{}{}\n", arms_string, i.get_show_as_string());
+        }
+        arms_string = format!("{}{}}}", arms_string, " ".repeat(self.depth as usize * 4));
+        format!("matchexpr({}){}", expr_string, arms_string)
+    }
+}
+
+impl ASTBranch for MatchArmBranch {
+    fn show(&self) {
+        println!("{}", self.get_show_as_string());
+    }
+
+    fn get_show_as_string(&self) -> String {
+        let mut pattern_string = String::new();
+        for i in &self.pattern {
+            pattern_string = format!("{}{}", pattern_string, i.get_show_as_string());
+        }
+        let mut contents_string = String::new();
+        for i in &self.contents {
+            contents_string = format!("{}{}", contents_string, i.get_show_as_string());
+        }
+        format!("{} => {}", pattern_string, contents_string)
+    }
+}
+
+impl RecursiveAnalysisElements for MatchBranch {
+    fn resolve_self(&mut self) -> Result<(), ParserError> {
+        let mut e_parser =
+            ExprParser::create_parser_from_vec(self.expr.clone(), self.depth, self.loopdepth);
+        e_parser.resolve()?;
+        self.expr = e_parser.code_list;
+        for arm in &mut self.arms {
+            arm.resolve_self()?;
+        }
+        Ok(())
+    }
+}
+
+impl RecursiveAnalysisElements for MatchArmBranch {
+    fn resolve_self(&mut self) -> Result<(), ParserError> {
+        let mut p_parser = ExprParser::create_parser_from_vec(
+            self.pattern.clone(),
+            self.depth + 1,
+            self.loopdepth,
+        );
+        p_parser.resolve()?;
+        self.pattern = p_parser.code_list;
+        let mut e_parser = ExprParser::create_parser_from_vec(
+            self.contents.clone(),
+            self.depth + 1,
+            self.loopdepth,
+        );
+        e_parser.resolve()?;
+        self.contents = e_parser.code_list;
+        if self.pattern.is_empty() || self.contents.is_empty() {
+            return Err(ParserError::MatchArmError(self.span));
+        }
+        Ok(())
+    }
+}
@@ -1,20 +1,28 @@
 // tokens
 pub mod block;
 pub mod comment;
+pub mod field;
 pub mod func;
 pub mod list;
 pub mod list_block;
+pub mod match_syntax;
 pub mod operator;
 pub mod paren_block;
+pub mod structure;
 pub mod syntax;
 pub mod syntax_box;
+pub mod variant;
 
+pub mod decenum;
 pub mod decfunc;
+pub mod decstruct;
 pub mod decvalue;
 
 pub mod item;
 
 // structures without ASTAreaBranch trait b
+pub mod error;
+pub mod num;
 pub mod string;
 pub mod unknown;
 pub mod word;
@@ -0,0 +1,133 @@
+use crate::abs::ast::*;
+use crate::abs::span::Span;
+use crate::token::ttype::primitive::PrimitiveType;
+
+/// 数値リテラルの値
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NumValue {
+    Int(u128),
+    Float(f64),
+}
+
+/// # NumBranch
+/// 数値リテラルを格納するstruct
+/// ```lichen
+/// 1_000_000  0x1F  0o17  0b1010  1.5e-3  10i64  7u32  2.0f32
+/// ```
+/// 負の数は前置の`-`演算子として扱うため、値は常に0以上になる
+#[derive(Clone, Debug)]
+pub struct NumBranch {
+    pub contents: String, // ソースコード上の表記
+    pub value: NumValue,
+    pub suffix: Option<PrimitiveType>, // `10i64`の`i64`
+    pub depth: isize,
+    pub loopdepth: isize,
+    pub span: Span,
+    /// 型検査で決定した型
+    pub num_type: Option<PrimitiveType>,
+}
+
+impl NumBranch {
+    /// 数字から始まる単語を数値リテラルとして解析する
+    /// 数値リテラルとして不正な場合は`None`を返却する
+    pub fn parse(contents: &str) -> Option<(NumValue, Option<PrimitiveType>)> {
+        let (radix, body) = match contents.get(..2) {
+            Some("0x") => (16, &contents[2..]),
+            Some("0o") => (8, &contents[2..]),
+            Some("0b") => (2, &contents[2..]),
+            _ => (10, contents),
+        };
+        let (body, suffix) = split_suffix(body, radix);
+        let digits: String = body.chars().filter(|c| *c != '_').collect();
+        if digits.is_empty() || body.starts_with('_') {
+            return None;
+        }
+        let is_float = radix == 10 && digits.contains(['.', 'e', 'E']);
+        if is_float || suffix.is_some_and(|t| t.is_float()) {
+            if radix != 10 || suffix.is_some_and(|t| t.is_integer()) {
+                return None;
+            }
+            if !is_float_digits(&digits) {
+                return None;
+            }
+            let value: f64 = digits.parse().ok()?;
+            Some((NumValue::Float(value), suffix))
+        } else {
+            let value = u128::from_str_radix(&digits, radix).ok()?;
+            Some((NumValue::Int(value), suffix))
+        }
+    }
+
+    /// 値が`t`の範囲に収まるか
+    /// `negative`の場合は前置の`-`がついた値として判定する
+    pub fn fits(&self, t: PrimitiveType, negative: bool) -> bool {
+        match (self.value, t) {
+            (NumValue::Int(v), PrimitiveType::I32) => v <= i32::MAX as u128 + negative as u128,
+            (NumValue::Int(v), PrimitiveType::I64) => v <= i64::MAX as u128 + negative as u128,
+            // 符号なし整数に負の値は代入できない(`-0`を除く)
+            (NumValue::Int(v), PrimitiveType::U32) => {
+                v <= u32::MAX as u128 && (!negative || v == 0)
+            }
+            (NumValue::Int(v), PrimitiveType::U64) => {
+                v <= u64::MAX as u128 && (!negative || v == 0)
+            }
+            (NumValue::Float(v), PrimitiveType::F32) => (v as f32).is_finite(),
+            (NumValue::Float(v), PrimitiveType::F64) => v.is_finite(),
+            _ => false,
+        }
+    }
+
+    /// wasmの`const`命令に渡す値の表記
+    pub fn value_as_string(&self) -> String {
+        match self.value {
+            NumValue::Int(v) => v.to_string(),
+            NumValue::Float(v) => format!("{:?}", v),
+        }
+    }
+}
+
+/// 型接尾辞を取り除く
+/// 16進数の場合は`f32`などが数字と区別できないため整数の接尾辞のみ認める
+fn split_suffix(body: &str, radix: u32) -> (&str, Option<PrimitiveType>) {
+    for name in ["i32", "i64", "u32", "u64", "f32", "f64"] {
+        if radix == 16 && name.starts_with('f') {
+            continue;
+        }
+        if let Some(rest) = body.strip_suffix(name) {
+            return (rest, PrimitiveType::from_name(name));
+        }
+    }
+    (body, None)
+}
+
+/// `digits ('.' digits)? ([eE] [+-]? digits)?`
+fn is_float_digits(digits: &str) -> bool {
+    let (mantissa, exponent) = match digits.find(['e', 'E']) {
+        Some(index) => (&digits[..index], Some(&digits[index + 1..])),
+        None => (digits, None),
+    };
+    let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let mantissa_ok = match mantissa.split_once('.') {
+        Some((int, frac)) => is_digits(int) && is_digits(frac),
+        None => is_digits(mantissa),
+    };
+    let exponent_ok = match exponent {
+        Some(e) => is_digits(e.strip_prefix(['+', '-']).unwrap_or(e)),
+        None => true,
+    };
+    mantissa_ok && exponent_ok
+}
+
+impl ASTBranch for NumBranch {
+    fn show(&self) {
+        println!("{}", self.get_show_as_string())
+    }
+
+    fn get_show_as_string(&self) -> String {
+        format!(
+            "{}Num \"{}\"",
+            " ".repeat(self.depth as usize * 4),
+            self.contents
+        )
+    }
+}
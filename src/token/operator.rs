@@ -1,4 +1,6 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
+use crate::token::ttype::primitive::PrimitiveType;
 
 /// #OperatorBranch
 /// 全ての演算子
@@ -7,6 +9,19 @@ use crate::abs::ast::*;
 pub struct OperatorBranch {
     pub ope: String,
     pub depth: isize,
+    pub span: Span,
+    /// 演算の対象となる値の型(型検査で決定する)
+    pub ope_type: Option<PrimitiveType>,
+}
+
+impl OperatorBranch {
+    /// 代入と複合代入の演算子かどうか
+    pub fn is_assignment(&self) -> bool {
+        matches!(
+            &*self.ope,
+            "=" | "+=" | "-=" | "*=" | "/=" | "%=" | "&=" | "|=" | "^=" | "<<=" | ">>="
+        )
+    }
 }
 
 impl ASTBranch for OperatorBranch {
@@ -1,4 +1,5 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 use crate::errors::parser_errors::ParserError;
 use crate::parser::expr_parser::ExprParser;
 
@@ -12,6 +13,7 @@ pub struct ParenBlockBranch {
     pub contents: Vec<ExprElem>,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
 }
 
 impl RecursiveAnalysisElements for ParenBlockBranch {
@@ -57,11 +59,12 @@ impl ASTBranch for ParenBlockBranch {
 }
 
 impl ASTAreaBranch<ExprElem> for ParenBlockBranch {
-    fn new(contents: Vec<ExprElem>, depth: isize, loopdepth: isize) -> Self {
+    fn new(contents: Vec<ExprElem>, depth: isize, loopdepth: isize, span: Span) -> Self {
         Self {
             contents,
             depth,
             loopdepth,
+            span,
         }
     }
 }
@@ -1,5 +1,6 @@
 use crate::abs::ast::{ASTBranch, ExprElem, RecursiveAnalysisElements, Token};
 use crate::abs::gen::Wasm_gen;
+use crate::abs::span::Span;
 use crate::errors::generate_errors::GenerateError;
 use crate::errors::parser_errors::ParserError;
 use crate::parser::expr_parser::ExprParser;
@@ -10,6 +11,7 @@ pub struct ExprBranch {
     pub code_list: Vec<ExprElem>,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
 }
 
 impl RecursiveAnalysisElements for ExprBranch {
@@ -28,6 +30,13 @@ impl RecursiveAnalysisElements for ExprBranch {
             Err(e) => Err(e),
         }
     }
+
+    fn resolve_self_recovering(&mut self, errors: &mut Vec<ParserError>) {
+        let mut parser =
+            ExprParser::create_parser_from_vec(self.code_list.clone(), self.depth, self.loopdepth);
+        errors.extend(parser.resolve_recovering());
+        self.code_list = parser.code_list;
+    }
 }
 
 impl ASTBranch for ExprBranch {
@@ -64,6 +73,13 @@ impl Wasm_gen for ExprBranch {
                 ExprElem::SyntaxBoxElem(synt_b) => {
                     assembly_text.push_str(&synt_b.generate_wasm()?);
                 }
+                ExprElem::MatchElem(match_b) => {
+                    assembly_text.push_str(&match_b.generate_wasm()?);
+                    if match_b.result_type.is_some() {
+                        // 文として使われた`match`の値は捨てる
+                        assembly_text.push_str("drop\n");
+                    }
+                }
                 _ => {
                     // ここではエラーを返すべきである
                 }
@@ -1,2 +1,3 @@
 pub mod expr;
+#[allow(clippy::module_inception)]
 pub mod stmt;
@@ -1,8 +1,10 @@
 use crate::abs::ast::{ASTBranch, ExprElem, RecursiveAnalysisElements, Token};
 use crate::abs::gen::Wasm_gen;
+use crate::abs::span::Span;
 use crate::errors::generate_errors::GenerateError;
 use crate::errors::parser_errors::ParserError;
 use crate::parser::expr_parser::ExprParser;
+use crate::token::item::ItemBranch;
 
 /// `return` `continue` `break` `yield` `let`
 /// などを処理をする
@@ -16,6 +18,7 @@ pub struct StmtBranch {
     pub code_list: Vec<ExprElem>,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
 }
 
 impl RecursiveAnalysisElements for StmtBranch {
@@ -58,6 +61,7 @@ impl ASTBranch for StmtBranch {
 
 impl Wasm_gen for StmtBranch {
     fn generate_wasm(&self) -> Result<String, GenerateError> {
+        use crate::gen::resolver::let_parts;
         use crate::gen::wasm::{BLOCK_ADDR, LOOP_ADDR};
 
         let mut assembly_text = String::default();
@@ -65,8 +69,42 @@ impl Wasm_gen for StmtBranch {
         let block_addr = format!("{}{}", BLOCK_ADDR, self.loopdepth - 1);
         match &*self.head {
             "return" => {
+                // 返り値がある場合は先に式を展開する
+                if !self.code_list.is_empty() {
+                    let value = ItemBranch {
+                        contents: self.code_list.clone(),
+                        depth: self.depth,
+                        loopdepth: self.loopdepth,
+                        span: self.span,
+                    };
+                    assembly_text.push_str(&value.generate_wasm()?);
+                }
                 assembly_text.push_str("return\n");
             }
+            "let" | "let_mut" => {
+                // 変数名は`Resolver`によって一意な名前に付け替えられている
+                let (pattern, init) = let_parts(&self.code_list)
+                    .ok_or(GenerateError::InvalidleftPattern(self.span))?;
+                let Some(ExprElem::WordElem(name)) = pattern.first() else {
+                    return Err(GenerateError::InvalidleftPattern(self.span));
+                };
+                if let Some(init) = init {
+                    let value = ItemBranch {
+                        contents: vec![init.clone()],
+                        depth: self.depth,
+                        loopdepth: self.loopdepth,
+                        span: init.get_span(),
+                    };
+                    assembly_text.push_str(&value.generate_wasm()?);
+                    assembly_text.push_str(&format!("local.set ${}\n", name.contents));
+                }
+            }
+            "break" | "continue" if self.loopdepth <= 0 => {
+                return Err(GenerateError::BreakOutsideLoop {
+                    keyword: self.head.clone(),
+                    span: self.span,
+                });
+            }
             "break" => {
                 // ここは、どのループの入れ子構造に属しているかで変わる
                 assembly_text.push_str(&format!("br ${}\n", block_addr));
@@ -78,10 +116,8 @@ impl Wasm_gen for StmtBranch {
                 assembly_text.push_str("unreachable\n");
             }
             _ => {
-                // error 不明なcontroll statement
-                // TODO
-                println!("error 不明なcontroll statement {}", self.head.clone());
-                todo!()
+                // `assert`などの生成に対応していないcontroll statement
+                return Err(GenerateError::UnsupportedStatement(self.span));
             }
         }
         Ok(assembly_text)
@@ -1,4 +1,5 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 
 /// # WordBranch
 /// 単語を格納するためのstruct
@@ -8,6 +9,7 @@ pub struct StringBranch {
     pub contents: String,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
 }
 
 impl ASTBranch for StringBranch {
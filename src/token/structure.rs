@@ -0,0 +1,149 @@
+use crate::abs::ast::*;
+use crate::abs::span::Span;
+use crate::errors::parser_errors::ParserError;
+use crate::parser::comma_parser::CommaParser;
+use crate::parser::core_parser::Parser;
+use crate::parser::expr_parser::ExprParser;
+use crate::token::ttype::primitive::PrimitiveType;
+
+/// # StructBranch
+/// 構造体のリテラル`Point { x: 1.0, y: 2.0 }`を格納するstruct
+/// `fields`は`{}`の中を`,`で区切ったそれぞれのフィールドの値
+/// 値はレコードを確保した後、書かれた順に評価して格納する
+#[derive(Clone, Debug)]
+pub struct StructBranch {
+    pub name: String,
+    pub fields: Vec<FieldInitBranch>,
+    pub depth: isize,
+    pub loopdepth: isize,
+    pub span: Span,
+    /// 値を格納する間レコードのアドレスを保持する隠れた変数(`Resolver`で決定する)
+    pub local: String,
+    /// 型検査で決定したレコードの大きさ
+    pub size: u32,
+}
+
+/// # FieldInitBranch
+/// `name: value`
+#[derive(Clone, Debug)]
+pub struct FieldInitBranch {
+    pub name: String,
+    pub value: Vec<ExprElem>,
+    pub depth: isize,
+    pub loopdepth: isize,
+    pub span: Span,
+    /// 型検査で決定したフィールドのオフセット
+    pub offset: u32,
+    /// 型検査で決定したフィールドのwasm上の型
+    pub field_type: Option<PrimitiveType>,
+}
+
+impl StructBranch {
+    /// `{}`の中身を`,`で区切り、最初の`:`で名前と値に分ける
+    /// 値の解析は`resolve_self`で行う
+    pub fn split_fields(
+        contents: Vec<ExprElem>,
+        depth: isize,
+        loopdepth: isize,
+    ) -> Result<Vec<FieldInitBranch>, ParserError> {
+        let mut c_parser = CommaParser::create_parser_from_vec(contents, depth, loopdepth);
+        c_parser.resolve()?;
+
+        let mut fields = Vec::new();
+        for item in c_parser.code_list {
+            let ExprElem::ItemElem(item_b) = item else {
+                return Err(ParserError::DevError);
+            };
+            let is_blank = item_b.contents.iter().all(
+                |inner| matches!(inner, ExprElem::UnKnownElem(v) if ExprParser::SPLIT_CHAR.contains(&v.contents)),
+            );
+            if is_blank {
+                // 最後のフィールドの後の`,`
+                continue;
+            }
+            let colon = item_b
+                .contents
+                .iter()
+                .position(|inner| matches!(inner, ExprElem::UnKnownElem(v) if v.contents == ':'));
+            let Some(colon) = colon else {
+                return Err(ParserError::StructLiteralError(item_b.span));
+            };
+            let mut name = String::new();
+            for inner in &item_b.contents[..colon] {
+                match inner {
+                    ExprElem::UnKnownElem(v) if ExprParser::SPLIT_CHAR.contains(&v.contents) => {}
+                    ExprElem::UnKnownElem(v)
+                        if v.contents.is_alphanumeric() || v.contents == '_' =>
+                    {
+                        name.push(v.contents)
+                    }
+                    _ => return Err(ParserError::StructLiteralError(item_b.span)),
+                }
+            }
+            if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+                return Err(ParserError::StructLiteralError(item_b.span));
+            }
+            fields.push(FieldInitBranch {
+                name,
+                value: item_b.contents[colon + 1..].to_vec(),
+                depth,
+                loopdepth,
+                span: item_b.span,
+                offset: 0,
+                field_type: None,
+            });
+        }
+        Ok(fields)
+    }
+}
+
+impl ASTBranch for StructBranch {
+    fn show(&self) {
+        println!("{}", self.get_show_as_string());
+    }
+
+    fn get_show_as_string(&self) -> String {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|field| field.get_show_as_string())
+            .collect();
+        format!("{} {{ {} }}", self.name, fields.join(", "))
+    }
+}
+
+impl ASTBranch for FieldInitBranch {
+    fn show(&self) {
+        println!("{}", self.get_show_as_string());
+    }
+
+    fn get_show_as_string(&self) -> String {
+        let mut value_string = String::new();
+        for i in &self.value {
+            value_string = format!("{}{}", value_string, i.get_show_as_string());
+        }
+        format!("{}: {}", self.name, value_string)
+    }
+}
+
+impl RecursiveAnalysisElements for StructBranch {
+    fn resolve_self(&mut self) -> Result<(), ParserError> {
+        for field in &mut self.fields {
+            field.resolve_self()?;
+        }
+        Ok(())
+    }
+}
+
+impl RecursiveAnalysisElements for FieldInitBranch {
+    fn resolve_self(&mut self) -> Result<(), ParserError> {
+        let mut e_parser =
+            ExprParser::create_parser_from_vec(self.value.clone(), self.depth + 1, self.loopdepth);
+        e_parser.resolve()?;
+        self.value = e_parser.code_list;
+        if self.value.is_empty() {
+            return Err(ParserError::StructLiteralError(self.span));
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,10 @@
 use crate::abs::ast::*;
-use crate::abs::gen::Wasm_gen;
-use crate::errors::generate_errors::GenerateError;
+use crate::abs::span::Span;
 use crate::errors::parser_errors::ParserError;
 use crate::parser::core_parser::Parser;
 use crate::parser::expr_parser::ExprParser;
 use crate::parser::stmt_parser::StmtParser;
+use crate::token::ttype::primitive::PrimitiveType;
 
 /// # SyntaxBranch
 /// `if` `elif` `else` `while` `loop` `for`などのデータを扱うstruct
@@ -19,6 +19,9 @@ pub struct SyntaxBranch {
     pub contents: Vec<StmtElem>,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
+    /// 型検査で決定した、`for`で反復する配列の要素のwasm上の型、要素の大きさ、要素の数
+    pub iter_array: Option<(PrimitiveType, u32, u32)>,
 }
 
 impl ASTBranch for SyntaxBranch {
@@ -52,6 +55,13 @@ impl ASTBranch for SyntaxBranch {
     }
 }
 
+impl SyntaxBranch {
+    /// `break` `continue`の対象となるループ構造かどうか
+    pub fn is_loop(&self) -> bool {
+        matches!(&*self.name, "while" | "for" | "loop")
+    }
+}
+
 impl RecursiveAnalysisElements for SyntaxBranch {
     fn resolve_self(&mut self) -> Result<(), ParserError> {
         let mut e_parser =
@@ -61,7 +71,7 @@ impl RecursiveAnalysisElements for SyntaxBranch {
         let mut s_parser = StmtParser::create_parser_from_vec(
             self.contents.clone(),
             self.depth,
-            if self.name == "while" || self.name == "for" {
+            if self.is_loop() {
                 self.loopdepth + 1
             } else {
                 self.loopdepth
@@ -71,4 +81,22 @@ impl RecursiveAnalysisElements for SyntaxBranch {
         self.contents = s_parser.code_list;
         Ok(())
     }
+
+    fn resolve_self_recovering(&mut self, errors: &mut Vec<ParserError>) {
+        let mut e_parser =
+            ExprParser::create_parser_from_vec(self.expr.clone(), self.depth, self.loopdepth);
+        errors.extend(e_parser.resolve_recovering());
+        self.expr = e_parser.code_list;
+        let mut s_parser = StmtParser::create_parser_from_vec(
+            self.contents.clone(),
+            self.depth,
+            if self.is_loop() {
+                self.loopdepth + 1
+            } else {
+                self.loopdepth
+            },
+        );
+        errors.extend(s_parser.resolve_recovering());
+        self.contents = s_parser.code_list;
+    }
 }
@@ -1,4 +1,5 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 use crate::errors::parser_errors::ParserError;
 use crate::token::syntax::SyntaxBranch;
 
@@ -10,6 +11,7 @@ pub struct SyntaxBoxBranch {
     pub contents: Vec<SyntaxBranch>,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
 }
 
 impl ASTBranch for SyntaxBoxBranch {
@@ -36,4 +38,10 @@ impl RecursiveAnalysisElements for SyntaxBoxBranch {
         }
         Ok(())
     }
+
+    fn resolve_self_recovering(&mut self, errors: &mut Vec<ParserError>) {
+        for inner in &mut self.contents {
+            inner.resolve_self_recovering(errors);
+        }
+    }
 }
@@ -0,0 +1,24 @@
+use crate::abs::ast::*;
+use crate::abs::span::Span;
+
+/// # ArrayTypeBranch
+/// 配列の型`[i32; 10]`
+/// 要素の型は入れ子にできる(`[[f64; 3]; 3]`)
+#[derive(Clone, Debug)]
+pub struct ArrayTypeBranch {
+    pub element: Box<TypeElem>,
+    pub length: u32,
+    pub depth: isize,
+    pub loopdepth: isize,
+    pub span: Span,
+}
+
+impl ASTBranch for ArrayTypeBranch {
+    fn get_show_as_string(&self) -> String {
+        format!("[{}; {}]", self.element.get_show_as_string(), self.length)
+    }
+
+    fn show(&self) {
+        println!("{}", self.get_show_as_string())
+    }
+}
@@ -1,2 +1,3 @@
+pub mod array;
 pub mod primitive;
 pub mod type_block;
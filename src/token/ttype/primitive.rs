@@ -1,22 +1,56 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PrimitiveType {
     I32, // i32
     I64, // i64
+    U32, // u32
+    U64, // u64
     F32, // f32
     F64, // f64
 }
 
 impl PrimitiveType {
+    /// 型の名前から型を返却する
+    /// プリミティブ型でない場合は`None`を返却する
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "i32" => Some(Self::I32),
+            "i64" => Some(Self::I64),
+            "u32" => Some(Self::U32),
+            "u64" => Some(Self::U64),
+            "f32" => Some(Self::F32),
+            "f64" => Some(Self::F64),
+            _ => None,
+        }
+    }
+
+    /// 整数型かどうか
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Self::I32 | Self::I64 | Self::U32 | Self::U64)
+    }
+
+    /// 符号なし整数型かどうか
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self, Self::U32 | Self::U64)
+    }
+
+    /// 浮動小数点数型かどうか
+    pub fn is_float(&self) -> bool {
+        matches!(self, Self::F32 | Self::F64)
+    }
+
     fn show(&self) {
         println!("{}", self.get_show_as_string());
     }
 
-    fn get_show_as_string(&self) -> String {
+    pub fn get_show_as_string(&self) -> String {
         String::from(match self {
             Self::I32 => "i32",
             Self::I64 => "i64",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
             Self::F32 => "f32",
             Self::F64 => "f64",
         })
@@ -28,6 +62,7 @@ pub struct PrimitiveBranch {
     pub primitive_type: PrimitiveType,
     pub depth: isize,
     pub loopdepth: isize,
+    pub span: Span,
 }
 
 impl ASTBranch for PrimitiveBranch {
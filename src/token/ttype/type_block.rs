@@ -1,4 +1,5 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 use crate::errors::parser_errors::ParserError;
 
 // type match
@@ -10,6 +11,7 @@ use crate::errors::parser_errors::ParserError;
 pub struct TypeBlockBranch {
     pub code_list: Vec<TypeElem>,
     pub depth: isize,
+    pub span: Span,
     // loopdepth: isize,
 }
 
@@ -30,8 +32,12 @@ impl ASTBranch for TypeBlockBranch {
 }
 
 impl TypeAreaBranch for TypeBlockBranch {
-    fn new(code_list: Vec<TypeElem>, depth: isize) -> Self {
-        Self { code_list, depth }
+    fn new(code_list: Vec<TypeElem>, depth: isize, span: Span) -> Self {
+        Self {
+            code_list,
+            depth,
+            span,
+        }
     }
 }
 
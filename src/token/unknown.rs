@@ -1,10 +1,12 @@
 use crate::abs::ast::*;
+use crate::abs::span::Span;
 
 /// # UnKnownBranch
 ///未定トークンが以下のstructに分類される
 #[derive(Clone, Debug)]
 pub struct UnKnownBranch {
     pub contents: char,
+    pub span: Span,
 }
 
 impl ASTBranch for UnKnownBranch {
@@ -0,0 +1,54 @@
+use crate::abs::ast::*;
+use crate::abs::span::Span;
+use crate::errors::parser_errors::ParserError;
+use crate::token::ttype::primitive::PrimitiveType;
+
+/// # VariantBranch
+/// 列挙型のヴァリアントの値`Red` `Circle(1.0)`を格納するstruct
+/// 解析時には変数の参照や関数呼び出しと区別できないため、`Resolver`がヴァリアントの名前を見つけて置き換える
+#[derive(Clone, Debug)]
+pub struct VariantBranch {
+    pub name: String,
+    /// ヴァリアントが持つ値
+    pub args: Vec<ExprElem>,
+    pub depth: isize,
+    pub loopdepth: isize,
+    pub span: Span,
+    /// 値を格納する間レコードのアドレスを保持する隠れた変数(`Resolver`で決定する)
+    /// 値を持たない列挙型の場合は空
+    pub local: String,
+    /// 型検査で決定したタグ
+    pub tag: u32,
+    /// 型検査で決定したレコードの大きさ
+    /// 値を持たない列挙型の場合はレコードを確保せず、タグのみで表す
+    pub size: Option<u32>,
+    /// 型検査で決定したそれぞれの値のオフセットとwasm上の型
+    pub fields: Vec<(u32, PrimitiveType)>,
+}
+
+impl ASTBranch for VariantBranch {
+    fn show(&self) {
+        println!("{}", self.get_show_as_string());
+    }
+
+    fn get_show_as_string(&self) -> String {
+        if self.args.is_empty() {
+            return format!("Variant {}", self.name);
+        }
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| arg.get_show_as_string())
+            .collect();
+        format!("Variant {}({})", self.name, args.join(", "))
+    }
+}
+
+impl RecursiveAnalysisElements for VariantBranch {
+    fn resolve_self(&mut self) -> Result<(), ParserError> {
+        for arg in &mut self.args {
+            arg.resolve_self()?;
+        }
+        Ok(())
+    }
+}
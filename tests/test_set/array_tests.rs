@@ -0,0 +1,302 @@
+// 配列のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::abs::ast::*;
+use lichen_lang::errors::generate_errors::GenerateError;
+use lichen_lang::errors::parser_errors::ParserError;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+use wasmer::Value;
+
+#[test]
+fn array_test00() {
+    // 配列の型とリテラルの解析
+    let code = "
+    fn f(a: [i32; 10], b: [[f64; 3]; 2]) -> [i64; 4] {
+        let c = [1, 2, 3];
+        let d = [0.0; 8];
+        return [a[0], b[1][2], c[d[0]], 4];
+    }
+    ";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve().unwrap();
+    let StmtElem::DecFuncElem(dec_func) = &s_parser.code_list[0] else {
+        panic!("DecFuncElem expected");
+    };
+    let args: Vec<String> = dec_func
+        .args
+        .iter()
+        .map(|arg| arg.arg_type.get_show_as_string())
+        .collect();
+    assert_eq!(args, ["[i32; 10]", "[[f64; 3]; 2]"]);
+    assert_eq!(
+        dec_func.return_type.as_ref().unwrap().get_show_as_string(),
+        "[i64; 4]"
+    );
+
+    // 要素の数は整数のリテラルでなければならない
+    for code in [
+        "fn f(a: [i32; n]) {}",
+        "fn f(a: [i32; 1.5]) {}",
+        "fn f() { let a = [0; n]; }",
+        "fn f() { let a = [0; 3i32]; }",
+    ] {
+        let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+        let r = s_parser.resolve();
+        assert!(
+            matches!(r, Err(ParserError::ArrayLengthError(_))),
+            "{} -> {:?}",
+            code,
+            r
+        );
+    }
+    let mut s_parser = StmtParser::new("fn f(a: [i32]) {}".to_string(), 0, 0);
+    assert!(matches!(
+        s_parser.resolve(),
+        Err(ParserError::InvalidType(_))
+    ));
+}
+
+#[test]
+fn array_test01() -> anyhow::Result<()> {
+    // 要素は大きさの倍数のオフセットに並べ、添字が定数の場合は`offset`で表す
+    let code = "
+    pub_fn f(i: i32) -> i64 {
+        let a = [10i64, 20, 30, 40];
+        return a[i] + a[3];
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i32.const 32\ncall $__alloc\n"));
+    assert!(module_wat.contains("i64.store offset=24\n"));
+    assert!(module_wat.contains("i32.const 8\ni32.mul\ni32.add\ni64.load offset=0\n"));
+    assert!(module_wat.contains("i64.load offset=24\n"));
+
+    for (i, expected) in [(0, 50), (1, 60), (3, 80)] {
+        let result = run_module(&module_wat, "f", &[Value::I32(i)])?;
+        assert_eq!(result[0], Value::I64(expected), "{}", i);
+    }
+    Ok(())
+}
+
+#[test]
+fn array_test02() -> anyhow::Result<()> {
+    // 要素への代入と複合代入
+    // 配列は参照として渡される
+    let code = "
+    fn fill(a: [f64; 5], x: f64) {
+        for (i in 0..5) {
+            a[i] = x * i as f64;
+        };
+    }
+    pub_fn f(x: f64) -> f64 {
+        let_mut a = [0.0; 5];
+        fill(a, x);
+        let_mut i = 4;
+        a[i] += 1.0;
+        a[0] -= 2.0;
+        return a[0] + a[1] + a[4];
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[Value::F64(2.0)])?;
+    assert_eq!(result[0], Value::F64(9.0));
+    Ok(())
+}
+
+#[test]
+fn array_test03() -> anyhow::Result<()> {
+    // 多次元の配列
+    // `[value; n]`は要素ごとに値を評価するため、各行は別の配列になる
+    let code = "
+    fn identity() -> [[i32; 3]; 3] {
+        let_mut m = [[0; 3]; 3];
+        for (i in 0..3) {
+            m[i][i] = 1;
+        };
+        return m;
+    }
+    pub_fn trace(k: i32) -> i32 {
+        let_mut m = identity();
+        m[1][2] += k;
+        let_mut sum = 0;
+        for (i in 0..3) {
+            for (j in 0..3) {
+                sum += m[i][j] * (i * 3 + j + 1);
+            };
+        };
+        return sum;
+    }
+    pub_fn corner() -> i32 {
+        let m = [[1, 2], [3, 4], [5, 6]];
+        return m[2][1] * 10 + m[0][1];
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "trace", &[Value::I32(2)])?;
+    assert_eq!(result[0], Value::I32(1 + 5 + 9 + 2 * 6));
+    let result = run_module(&module_wat, "corner", &[])?;
+    assert_eq!(result[0], Value::I32(62));
+    Ok(())
+}
+
+#[test]
+fn array_test04() -> anyhow::Result<()> {
+    // 構造体の配列と配列のフィールド
+    let code = "
+    struct Point { x: f64, y: f64 }
+    struct Path { points: [Point; 3], weights: [f64; 3] }
+    pub_fn f() -> f64 {
+        let_mut path = Path {
+            points: [Point { x: 1.0, y: 2.0 }, Point { x: 3.0, y: 4.0 }, Point { x: 5.0, y: 6.0 }],
+            weights: [0.5; 3],
+        };
+        path.points[1].y = 10.0;
+        path.weights[2] += 1.5;
+        let_mut sum = 0.0;
+        for (i in 0..3) {
+            sum += path.points[i].y * path.weights[i];
+        };
+        return sum;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[])?;
+    assert_eq!(result[0], Value::F64(1.0 + 5.0 + 12.0));
+    Ok(())
+}
+
+#[test]
+fn array_test05() {
+    // 配列に関するエラー
+    let test_cases = [
+        ("fn f() { let a = [1, 2.0]; }", "TypeMismatch"),
+        ("fn f() { let a: [i32; 2] = [1, 2, 3]; }", "TypeMismatch"),
+        ("fn f() { let a: [i32; 2] = [1.0; 2]; }", "TypeMismatch"),
+        (
+            "fn f(a: [i32; 2]) -> i32 { return a[1.0]; }",
+            "TypeMismatch",
+        ),
+        ("fn f() { let a = []; }", "UnknownArrayType"),
+        ("fn f(a: i32) -> i32 { return a[0]; }", "NotIndexable"),
+        (
+            "fn f(a: [i32; 2]) -> i32 { return a[0][0]; }",
+            "NotIndexable",
+        ),
+        (
+            "fn f(a: [i32; 2]) -> i32 { return a[2]; }",
+            "IndexOutOfBounds",
+        ),
+        (
+            "fn f(a: [i32; 2]) -> i32 { return a[0, 1]; }",
+            "UnsupportedListAccess",
+        ),
+        (
+            "fn f(a: [i32; 2], b: [i32; 2]) { let c = a + b; }",
+            "InvalidOperandType",
+        ),
+        ("fn f() { let a = [1, 2]; a[0] = 3; }", "AssignToImmutable"),
+        (
+            "fn f() { let a = [[1], [2]]; a[0][0] = 3; }",
+            "AssignToImmutable",
+        ),
+        (
+            "fn g() -> i32 { return 0; }\nfn f(a: [i32; 2]) { a[g()] += 1; }",
+            "InvalidleftPattern",
+        ),
+        ("fn f(a: [P; 2]) {}", "UndefinedType"),
+    ];
+    for (code, expected) in test_cases {
+        let r = compile(code);
+        let Err(e) = &r else {
+            panic!("{} -> {:?}", code, r);
+        };
+        assert!(
+            format!("{:?}", e).starts_with(expected),
+            "{} -> {:?}",
+            code,
+            e
+        );
+    }
+}
+
+#[test]
+fn array_test06() -> anyhow::Result<()> {
+    // 定数でない添字が要素の数以上(負の値を含む)の場合は実行時に停止する
+    let code = "
+    pub_fn get(i: i32, j: i32) -> i32 {
+        let m = [[1, 2, 3], [4, 5, 6]];
+        return m[i][j];
+    }
+    pub_fn set(i: i32) -> i32 {
+        let_mut a = [0; 3];
+        a[i] = 7;
+        return a[0] + a[1] + a[2];
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i32.const 3\ni32.ge_u\nif\nunreachable\nend\n"));
+    for (i, j, expected) in [(0, 0, 1), (1, 2, 6)] {
+        let result = run_module(&module_wat, "get", &[Value::I32(i), Value::I32(j)])?;
+        assert_eq!(result[0], Value::I32(expected), "{} {}", i, j);
+    }
+    for (i, j) in [(2, 0), (0, 3), (-1, 0), (1, -1)] {
+        let result = run_module(&module_wat, "get", &[Value::I32(i), Value::I32(j)]);
+        assert!(
+            result
+                .as_ref()
+                .is_err_and(|e| e.to_string().contains("unreachable")),
+            "{} {} -> {:?}",
+            i,
+            j,
+            result
+        );
+    }
+    let result = run_module(&module_wat, "set", &[Value::I32(2)])?;
+    assert_eq!(result[0], Value::I32(7));
+    for i in [3, -1] {
+        let result = run_module(&module_wat, "set", &[Value::I32(i)]);
+        assert!(
+            result
+                .as_ref()
+                .is_err_and(|e| e.to_string().contains("unreachable")),
+            "{} -> {:?}",
+            i,
+            result
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn array_test07() {
+    // 線形メモリに収まらない配列はエラーになる
+    let test_cases = [
+        (
+            "pub_fn f() {\nlet a = [0; 2000000000];\n}",
+            8000000000,
+            (2, 9),
+        ),
+        (
+            "pub_fn f() {\nlet a = [0i64; 536870912];\n}",
+            4294967296,
+            (2, 9),
+        ),
+        ("pub_fn f(a: [f64; 600000000]) {}", 4800000000, (1, 13)),
+    ];
+    for (code, expected, position) in test_cases {
+        let e = compile(code).unwrap_err();
+        let GenerateError::ArrayTooLarge { size, span, .. } = e else {
+            panic!("{} -> {:?}", code, e);
+        };
+        assert_eq!(size, expected, "{}", code);
+        assert_eq!((span.line, span.column), position, "{}", code);
+        let rendered = e.to_diagnostic().render(code);
+        assert!(rendered.contains("is too large"), "{}", rendered);
+    }
+    // 上限以下の配列は生成できる
+    let module_wat = compile("pub_fn f() {\nlet a = [0; 1000000];\n}").unwrap();
+    assert!(module_wat.contains("i32.const 4000000\n"));
+}
@@ -0,0 +1,158 @@
+// ビット演算とシフトのテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::errors::generate_errors::GenerateError;
+
+use wasmer::Value;
+
+#[test]
+fn bitwise_test00() -> anyhow::Result<()> {
+    // ビット演算とシフト
+    let code = "
+    pub_fn and(a: i32, b: i32) -> i32 {
+        return a & b;
+    }
+    pub_fn or(a: i32, b: i32) -> i32 {
+        return a | b;
+    }
+    pub_fn xor(a: i64, b: i64) -> i64 {
+        return a ^ b;
+    }
+    pub_fn not(a: i32) -> i32 {
+        return ~a;
+    }
+    pub_fn shl(a: i32, b: i32) -> i32 {
+        return a << b;
+    }
+    pub_fn shr(a: i32, b: i32) -> i32 {
+        return a >> b;
+    }
+    pub_fn shr_u(a: u32, b: u32) -> u32 {
+        return a >> b;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i64.xor"));
+    assert!(module_wat.contains("i32.shr_s"));
+    assert!(module_wat.contains("i32.shr_u"));
+
+    let call = |name: &str, args: &[Value]| run_module(&module_wat, name, args);
+    assert_eq!(
+        call("and", &[Value::I32(0b1100), Value::I32(0b1010)])?[0],
+        Value::I32(0b1000)
+    );
+    assert_eq!(
+        call("or", &[Value::I32(0b1100), Value::I32(0b1010)])?[0],
+        Value::I32(0b1110)
+    );
+    assert_eq!(
+        call("xor", &[Value::I64(0b1100), Value::I64(0b1010)])?[0],
+        Value::I64(0b0110)
+    );
+    assert_eq!(call("not", &[Value::I32(5)])?[0], Value::I32(!5));
+    assert_eq!(
+        call("shl", &[Value::I32(3), Value::I32(4)])?[0],
+        Value::I32(48)
+    );
+    assert_eq!(
+        call("shr", &[Value::I32(-16), Value::I32(2)])?[0],
+        Value::I32(-4)
+    );
+    assert_eq!(
+        call("shr_u", &[Value::I32(-16), Value::I32(28)])?[0],
+        Value::I32(15)
+    );
+    Ok(())
+}
+
+#[test]
+fn bitwise_test01() -> anyhow::Result<()> {
+    // Cと同じ優先順位
+    let code = "
+    pub_fn f(a: i32, b: i32) -> i32 {
+        return a | b & 6 ^ 1 << 2 + 1;
+    }
+    pub_fn g(a: i32) -> i32 {
+        if (a & 1 == 1) {
+            return 1;
+        };
+        return 0;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    // a | ((b & 6) ^ (1 << 3))
+    let result = run_module(&module_wat, "f", &[Value::I32(1), Value::I32(7)])?;
+    assert_eq!(result[0], Value::I32(1 | ((7 & 6) ^ (1 << 3))));
+    // a & (1 == 1)
+    let result = run_module(&module_wat, "g", &[Value::I32(3)])?;
+    assert_eq!(result[0], Value::I32(1));
+    Ok(())
+}
+
+#[test]
+fn bitwise_test02() -> anyhow::Result<()> {
+    // 複合代入と組み込み関数
+    let code = "
+    pub_fn f(a: i32) -> i32 {
+        let_mut x = a;
+        x &= 0xFF;
+        x |= 0x100;
+        x ^= 1;
+        x <<= 4;
+        x >>= 2;
+        return x;
+    }
+    pub_fn rot(a: i32) -> i32 {
+        return rotl(a, 4) + rotr(a, 4);
+    }
+    pub_fn rot64(a: i64) -> i64 {
+        return rotl(a, 1);
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i32.rotl"));
+    assert!(module_wat.contains("i32.rotr"));
+    assert!(module_wat.contains("i64.rotl"));
+
+    let mut x: i32 = 0x1234;
+    x &= 0xFF;
+    x |= 0x100;
+    x ^= 1;
+    x <<= 4;
+    x >>= 2;
+    let result = run_module(&module_wat, "f", &[Value::I32(0x1234)])?;
+    assert_eq!(result[0], Value::I32(x));
+    let a: i32 = 0x1234_5678;
+    let result = run_module(&module_wat, "rot", &[Value::I32(a)])?;
+    assert_eq!(
+        result[0],
+        Value::I32(a.rotate_left(4).wrapping_add(a.rotate_right(4)))
+    );
+    let result = run_module(&module_wat, "rot64", &[Value::I64(i64::MIN)])?;
+    assert_eq!(result[0], Value::I64(1));
+    Ok(())
+}
+
+#[test]
+fn bitwise_test03() {
+    // ビット演算は整数のみ
+    let code = "
+    pub_fn f(a: f64) -> f64 {
+        return a & 1.0;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::InvalidOperandType { .. })
+    ));
+    let code = "
+    pub_fn f(a: i32) -> i32 {
+        return rotl(a);
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::ArgumentCountMismatch { .. })
+    ));
+}
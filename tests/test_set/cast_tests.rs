@@ -0,0 +1,87 @@
+// `as`による型変換のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::errors::generate_errors::GenerateError;
+
+use wasmer::Value;
+
+#[test]
+fn cast_test00() -> anyhow::Result<()> {
+    // 整数と浮動小数点数の変換
+    let code = "
+    pub_fn widen(a: i32) -> i64 {
+        return a as i64 * 3;
+    }
+    pub_fn narrow(a: i64) -> i32 {
+        return a as i32;
+    }
+    pub_fn average(a: i32, b: i32) -> f64 {
+        return (a + b) as f64 / 2.0;
+    }
+    pub_fn trunc(a: f64) -> i32 {
+        return a as i32;
+    }
+    pub_fn single(a: f64) -> f32 {
+        return a as f32 + 1.0;
+    }
+    pub_fn neg(a: i32) -> f32 {
+        return -a as f32;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i64.extend_i32_s"));
+    assert!(module_wat.contains("i32.wrap_i64"));
+    assert!(module_wat.contains("f64.convert_i32_s"));
+    assert!(module_wat.contains("i32.trunc_sat_f64_s"));
+    assert!(module_wat.contains("f32.demote_f64"));
+    assert!(module_wat.contains("f32.convert_i32_s"));
+
+    let result = run_module(&module_wat, "widen", &[Value::I32(i32::MAX)])?;
+    assert_eq!(result[0], Value::I64(i32::MAX as i64 * 3));
+    let result = run_module(&module_wat, "narrow", &[Value::I64(0x1_0000_0005)])?;
+    assert_eq!(result[0], Value::I32(5));
+    let result = run_module(&module_wat, "average", &[Value::I32(1), Value::I32(2)])?;
+    assert_eq!(result[0], Value::F64(1.5));
+    // 範囲外の値は飽和する
+    let result = run_module(&module_wat, "trunc", &[Value::F64(-2.7)])?;
+    assert_eq!(result[0], Value::I32(-2));
+    let result = run_module(&module_wat, "trunc", &[Value::F64(1e20)])?;
+    assert_eq!(result[0], Value::I32(i32::MAX));
+    let result = run_module(&module_wat, "single", &[Value::F64(0.5)])?;
+    assert_eq!(result[0], Value::F32(1.5));
+    let result = run_module(&module_wat, "neg", &[Value::I32(3)])?;
+    assert_eq!(result[0], Value::F32(-3.0));
+    Ok(())
+}
+
+#[test]
+fn cast_test01() {
+    // 変換しなければ異なる型同士の演算はできない
+    let code = "
+    pub_fn f(a: i32, b: f64) -> f64 {
+        return a as f64 + b;
+    }
+    ";
+    assert!(compile(code).is_ok());
+    let code = "
+    pub_fn f(a: i32, b: f64) -> f64 {
+        return (a + b) as f64;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::TypeMismatch { .. })
+    ));
+
+    // 変換先がプリミティブ型ではない
+    let code = "
+    pub_fn f(a: i32) -> i64 {
+        return a as foo;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::InvalidTypeError(_))
+    ));
+}
@@ -0,0 +1,200 @@
+// `match`の網羅性と到達しない腕のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::errors::diagnostic::Level;
+use lichen_lang::errors::generate_errors::GenerateError;
+use lichen_lang::errors::generate_warnings::GenerateWarning;
+use lichen_lang::gen::module::generate_wasm_module_with_warnings;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+use wasmer::Value;
+
+fn compile_with_warnings(code: &str) -> (String, Vec<GenerateWarning>) {
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve().unwrap();
+    generate_wasm_module_with_warnings(&s_parser.code_list).unwrap()
+}
+
+fn missing(code: &str) -> Vec<String> {
+    match compile(code) {
+        Err(GenerateError::NonExhaustivePatterns { missing, .. }) => missing,
+        other => panic!("NonExhaustivePatterns expected: {:?}", other),
+    }
+}
+
+#[test]
+fn coverage_test00() {
+    // 網羅されていない範囲を型の最小値と最大値を使って表示する
+    let code = "
+    pub_fn f(x: i32) -> i32 {
+        return match (x) {
+            0..=2 => 1,
+        };
+    }
+    ";
+    assert_eq!(missing(code), ["i32::MIN..=-1", "3..=i32::MAX"]);
+
+    let code = "
+    pub_fn f(x: u32) -> i32 {
+        return match (x) {
+            0 => 1,
+            2..10 => 2,
+        };
+    }
+    ";
+    assert_eq!(missing(code), ["1", "10..=u32::MAX"]);
+
+    // 浮動小数点数は`_`でのみ網羅できる
+    let code = "
+    pub_fn f(x: f64) -> i32 {
+        return match (x) {
+            0.0 => 1,
+        };
+    }
+    ";
+    assert_eq!(missing(code), ["_"]);
+}
+
+#[test]
+fn coverage_test01() -> anyhow::Result<()> {
+    // 範囲の組み合わせで型の全ての値を網羅する場合は`_`がなくてもよい
+    let code = "
+    pub_fn sign(x: i64) -> i32 {
+        return match (x) {
+            -9223372036854775808..0 => -1,
+            0 => 0,
+            1..=9223372036854775807 => 1,
+        };
+    }
+    pub_fn low(x: u32) -> i32 {
+        return match (x) {
+            0..=9 => 1,
+            10..=4294967295 => 0,
+        };
+    }
+    ";
+    let (module_wat, warnings) = compile_with_warnings(code);
+    assert!(warnings.is_empty());
+    for (x, expected) in [(i64::MIN, -1), (-1, -1), (0, 0), (1, 1), (i64::MAX, 1)] {
+        let result = run_module(&module_wat, "sign", &[Value::I64(x)])?;
+        assert_eq!(result[0], Value::I32(expected), "{}", x);
+    }
+    let result = run_module(&module_wat, "low", &[Value::I32(-1)])?;
+    assert_eq!(result[0], Value::I32(0));
+    Ok(())
+}
+
+#[test]
+fn coverage_test02() {
+    // 前の腕に網羅されている腕は警告される
+    let code = "
+    pub_fn f(x: i32) -> i32 {
+        return match (x) {
+            0..10 => 1,
+            5 => 2,
+            10 => 3,
+            0..=10 => 4,
+            _ => 5,
+            11 => 6,
+        };
+    }
+    ";
+    let (_, warnings) = compile_with_warnings(code);
+    let lines: Vec<usize> = warnings
+        .iter()
+        .map(|warning| {
+            let GenerateWarning::UnreachablePattern(span) = warning;
+            span.line
+        })
+        .collect();
+    // `5` `0..=10` `11`
+    assert_eq!(lines, [5, 7, 9]);
+
+    // 全ての値を網羅した後の`_`
+    let code = "
+    pub_fn f(x: u32) -> i32 {
+        return match (x) {
+            0..=4294967295 => 1,
+            _ => 2,
+        };
+    }
+    ";
+    let (_, warnings) = compile_with_warnings(code);
+    assert_eq!(warnings.len(), 1);
+    let diagnostic = warnings[0].to_diagnostic();
+    assert_eq!(diagnostic.level, Level::Warning);
+    assert_eq!(diagnostic.message, "unreachable pattern");
+}
+
+#[test]
+fn coverage_test03() {
+    // 網羅されていないパターンの診断
+    let code = "pub_fn f(x: i32) -> i32 {\nreturn match (x) { 1 => 1, 3 => 2, };\n}";
+    let e = compile(code).unwrap_err();
+    assert_eq!(
+        e.to_diagnostic().render(code),
+        "error: non-exhaustive patterns: patterns `i32::MIN..=0`, `2` and `4..=i32::MAX` not covered\n \
+         --> 2:15\n  \
+         |\n\
+         2 | return match (x) { 1 => 1, 3 => 2, };\n  \
+         |               ^ patterns `i32::MIN..=0`, `2` and `4..=i32::MAX` not covered\n  \
+         = note: add arms for the missing values or a wildcard arm `_ => ...`\n"
+    );
+
+    // 4つ以上の場合は省略する
+    let code = "
+    pub_fn f(x: u32) -> i32 {
+        return match (x) {
+            1 => 1,
+            3 => 1,
+            5 => 1,
+        };
+    }
+    ";
+    let message = compile(code).unwrap_err().to_diagnostic().message;
+    assert_eq!(
+        message,
+        "non-exhaustive patterns: patterns `0`, `2`, `4` and 1 more not covered"
+    );
+}
+
+#[test]
+fn coverage_test04() {
+    // 一つも値を含まない範囲のパターンはエラーになる
+    for (arm, inclusive) in [
+        ("5..=1", true),
+        ("5..1", false),
+        ("3..3", false),
+        ("-1..=-5", true),
+        ("Some(5..=1)", true),
+    ] {
+        let code = format!(
+            "enum Opt {{ Some(i32), Nothing }}\npub_fn f(x: i32) -> i32 {{\nreturn match (x) {{ {} => 1, _ => 2, }};\n}}",
+            arm
+        );
+        let code = if arm.starts_with("Some") {
+            code.replace("match (x)", "match (Some(x))")
+        } else {
+            code
+        };
+        let e = compile(&code).unwrap_err();
+        assert!(
+            matches!(&e, GenerateError::EmptyRangePattern { inclusive: found, span } if *found == inclusive && span.line == 3),
+            "{} -> {:?}",
+            arm,
+            e
+        );
+    }
+
+    // 両端が等しい`..=`は一つの値を含む
+    let code = "pub_fn f(x: i32) -> i32 {\nreturn match (x) { 3..=3 => 1, _ => 2, };\n}";
+    assert!(compile(code).is_ok());
+
+    let code = "pub_fn f(x: i32) -> i32 {\nreturn match (x) { 5..=1 => 1, _ => 2, };\n}";
+    let rendered = compile(code).unwrap_err().to_diagnostic().render(code);
+    println!("{}", rendered);
+    assert!(rendered.contains("error: lower range bound must be less than or equal to upper"));
+    assert!(rendered.contains("^^^^^ this range pattern matches no values"));
+}
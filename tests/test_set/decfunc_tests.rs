@@ -0,0 +1,115 @@
+// 関数宣言のテスト
+// `fn name(args) -> Type { body }`が`DecFuncBranch`として解析されるかチェックする
+
+extern crate lichen_lang;
+use lichen_lang::abs::ast::*;
+use lichen_lang::errors::parser_errors::ParserError;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+use lichen_lang::token::decfunc::DecFuncBranch;
+
+fn parse(code: &str) -> Result<Vec<StmtElem>, ParserError> {
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve()?;
+    Ok(s_parser.code_list)
+}
+
+fn dec_funcs(list: &[StmtElem]) -> Vec<&DecFuncBranch> {
+    list.iter()
+        .filter_map(|inner| match inner {
+            StmtElem::DecFuncElem(dec_func) => Some(dec_func),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn decfunc_test00() {
+    // 名前、引数、返り値の型、本体
+    let code = "
+    fn add(a: i32, b: i64) -> i32 {
+        return a + b;
+    }
+    pub_fn main() {
+        add(1, 2);
+    }
+    ";
+    let list = parse(code).unwrap();
+    let funcs = dec_funcs(&list);
+    assert_eq!(list.len(), 2);
+    assert_eq!(funcs.len(), 2);
+
+    assert_eq!(funcs[0].name, "add");
+    assert!(!funcs[0].is_public);
+    let args: Vec<String> = funcs[0]
+        .args
+        .iter()
+        .map(|arg| format!("{}: {}", arg.name, arg.arg_type.get_show_as_string()))
+        .collect();
+    assert_eq!(args, vec!["a: i32", "b: i64"]);
+    assert_eq!(
+        funcs[0]
+            .return_type
+            .as_ref()
+            .map(|t| t.get_show_as_string()),
+        Some(String::from("i32"))
+    );
+    assert!(matches!(funcs[0].contents[0], StmtElem::Special(_)));
+    assert_eq!(
+        &code[funcs[0].span.start..funcs[0].span.end],
+        "fn add(a: i32, b: i64) -> i32 {
+        return a + b;
+    }"
+    );
+
+    assert_eq!(funcs[1].name, "main");
+    assert!(funcs[1].is_public);
+    assert!(funcs[1].args.is_empty());
+    assert!(funcs[1].return_type.is_none());
+    assert!(matches!(funcs[1].contents[0], StmtElem::ExprElem(_)));
+}
+
+#[test]
+fn decfunc_test01() {
+    // 関数の中で宣言された関数
+    let code = "
+    fn outer(n: i32) -> i32 {
+        fn inner(m: i32) -> i32 {
+            return m * 2;
+        };
+        return inner(n);
+    }
+    ";
+    let list = parse(code).unwrap();
+    let outer = dec_funcs(&list)[0];
+    let inner = dec_funcs(&outer.contents)[0];
+    assert_eq!(inner.name, "inner");
+    assert_eq!(inner.depth, 1);
+    assert_eq!(outer.contents.len(), 2);
+}
+
+#[test]
+fn decfunc_test02() {
+    // 不正な関数宣言
+    let test_cases = [
+        ("fn (a: i32) {}", "DecFuncError"),
+        ("fn f {}", "DecFuncError"),
+        ("fn f(a i32) {}", "DecFuncError"),
+        ("fn f(a: i32)", "DecFuncError"),
+        ("fn f(a: 1) {}", "InvalidType"),
+        ("fn f() -> {}", "InvalidType"),
+        ("fn f() -> i32 i64 {}", "InvalidType"),
+    ];
+    for (code, expected) in test_cases {
+        let r = parse(code);
+        let Err(e) = &r else {
+            panic!("{} -> {:?}", code, r);
+        };
+        assert!(
+            format!("{:?}", e).starts_with(expected),
+            "{} -> {:?}",
+            code,
+            e
+        );
+    }
+}
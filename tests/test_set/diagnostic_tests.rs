@@ -0,0 +1,119 @@
+// 診断(diagnostic)のテスト
+// パースに失敗したソースコードに対してエラー位置を指す注釈付きの出力がされるかチェックする
+
+extern crate lichen_lang;
+use lichen_lang::abs::span::Span;
+use lichen_lang::errors::diagnostic::Diagnostic;
+use lichen_lang::errors::parser_errors::ParserError;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+fn parse_err(code: &str) -> ParserError {
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    match s_parser.resolve() {
+        Ok(_) => panic!("parse error expected"),
+        Err(e) => e,
+    }
+}
+
+#[test]
+fn diagnostic_test00() {
+    // 閉じられていない括弧
+    // 閉じ括弧が必要な位置を示し、開き括弧の位置を補足として示す
+    let code = "fn main() {\n    a = 1;\n";
+    let e = parse_err(code);
+    assert!(matches!(e, ParserError::BraceNotClosed { .. }));
+    assert_eq!(
+        e.to_diagnostic().render(code),
+        "error: this delimiter is never closed\n \
+         --> 2:11\n  \
+         |\n\
+         1 | fn main() {\n  \
+         |           - brace opened here\n\
+         2 |     a = 1;\n  \
+         |           ^ expected `}`\n"
+    );
+}
+
+#[test]
+fn diagnostic_test01() {
+    // 閉じられていない文字列
+    let code = "a = 1;\nb = \"abc;\n";
+    let e = parse_err(code);
+    assert!(matches!(e, ParserError::QuotationNotClosed { .. }));
+    assert_eq!(
+        e.to_diagnostic().render(code),
+        "error: unterminated string literal\n \
+         --> 2:10\n  \
+         |\n\
+         2 | b = \"abc;\n  \
+         |          ^ expected `\"`\n  \
+         |     - string starts here\n  \
+         = note: add a closing `\"` to end the string\n"
+    );
+}
+
+#[test]
+fn diagnostic_test02() {
+    // 対応する開き括弧のない閉じ括弧
+    let code = "a = (1 + 2));";
+    let e = parse_err(code);
+    assert!(matches!(e, ParserError::BraceNotOpened(_)));
+    assert_eq!(e.get_span(), Span::new(11, 12, 1, 12));
+}
+
+#[test]
+fn diagnostic_test03() {
+    // 補足の位置は`-`で、離れた行は`...`で区切って出力される
+    let code = "let a = 1;\n\n\na = 2;\n";
+    let diagnostic = Diagnostic::error(
+        "cannot assign twice to immutable variable",
+        Span::new(13, 18, 4, 1),
+        "cannot assign twice",
+    )
+    .with_label(Span::new(4, 5, 1, 5), "first assignment");
+    assert_eq!(
+        diagnostic.render(code),
+        "error: cannot assign twice to immutable variable\n \
+         --> 4:1\n  \
+         |\n\
+         1 | let a = 1;\n  \
+         |     - first assignment\n\
+         ...\n\
+         4 | a = 2;\n  \
+         | ^^^^^ cannot assign twice\n"
+    );
+}
+
+#[test]
+fn diagnostic_test04() {
+    // 色付きの出力にはANSIエスケープシーケンスが含まれる
+    let code = "fn main() {";
+    let e = parse_err(code);
+    let colored = e.to_diagnostic().render_colored(code);
+    assert!(colored.contains("\x1b[31m"));
+    assert!(colored.contains("\x1b[0m"));
+    assert!(!e.to_diagnostic().render(code).contains('\x1b'));
+}
+
+#[test]
+fn diagnostic_test05() {
+    // 複数行にわたる要素の後で閉じ括弧が必要な場合も、その要素の終わりの行を示す
+    let code = "a = f(1, {\n    b;\n}\n";
+    let e = parse_err(code);
+    let ParserError::BraceNotClosed { open, close, .. } = &e else {
+        panic!("{:?}", e);
+    };
+    assert_eq!((*open, *close), (Span::new(5, 6, 1, 6), ')'));
+    assert_eq!(
+        e.to_diagnostic().render(code),
+        "error: this delimiter is never closed\n \
+         --> 3:2\n  \
+         |\n\
+         1 | a = f(1, {\n  \
+         |      - brace opened here\n\
+         ...\n\
+         3 | }\n  \
+         |  ^ expected `)`\n"
+    );
+}
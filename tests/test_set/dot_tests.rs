@@ -0,0 +1,80 @@
+// `@`と`->`のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::errors::generate_errors::GenerateError;
+use lichen_lang::parser::core_parser::{OpeTable, Parser};
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+use wasmer::Value;
+
+#[test]
+fn dot_test00() -> anyhow::Result<()> {
+    // `@`は左辺の型に対応する`dot_*`関数の呼び出しになる
+    let code = "
+    fn dot_i32(a: i32, b: i32) -> i32 {
+        return a * b + 1;
+    }
+    fn dot_f64(a: f64, b: i32) -> f64 {
+        return a * b as f64;
+    }
+    pub_fn f(x: i32, y: i32) -> i32 {
+        return x @ y @ 2 + 1;
+    }
+    pub_fn g(x: f64) -> f64 {
+        return x @ 3;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("call $dot_i32"));
+    assert!(module_wat.contains("call $dot_f64"));
+
+    // ((x @ y) @ 2) + 1
+    let result = run_module(&module_wat, "f", &[Value::I32(3), Value::I32(4)])?;
+    assert_eq!(result[0], Value::I32((3 * 4 + 1) * 2 + 1 + 1));
+    let result = run_module(&module_wat, "g", &[Value::F64(1.5)])?;
+    assert_eq!(result[0], Value::F64(4.5));
+    Ok(())
+}
+
+#[test]
+fn dot_test01() {
+    // 実装となる関数がない
+    let code = "
+    pub_fn f(x: i64, y: i64) -> i64 {
+        return x @ y;
+    }
+    ";
+    match compile(code) {
+        Err(GenerateError::UndefinedOperatorFunction {
+            found, name, span, ..
+        }) => {
+            assert_eq!(found, "i64");
+            assert_eq!(name, "dot_i64");
+            assert_eq!(span.line, 3);
+        }
+        r => panic!("{:?}", r),
+    }
+}
+
+#[test]
+fn arrow_test00() {
+    // `->`は式の中では使用できない
+    assert!(matches!(OpeTable::set("->"), Ok(OpeTable::ARROW)));
+    assert!(matches!(OpeTable::set("||"), Ok(OpeTable::OR)));
+
+    let code = "
+    pub_fn f(a: i32, b: i32) -> i32 {
+        return a -> b;
+    }
+    ";
+    match compile(code) {
+        Err(GenerateError::ReservedOperator(span)) => assert_eq!(span.line, 3),
+        r => panic!("{:?}", r),
+    }
+
+    // 型注釈の中の関数型は構文として解析できる
+    let code = "let a: (i32, i32) -> i32 = f();";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    assert!(s_parser.resolve().is_ok());
+}
@@ -0,0 +1,480 @@
+// 列挙型のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::abs::ast::*;
+use lichen_lang::errors::generate_errors::GenerateError;
+use lichen_lang::errors::parser_errors::ParserError;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+use wasmer::Value;
+
+#[test]
+fn enum_test00() {
+    // 列挙型宣言の解析
+    let code = "
+    enum Color { Red, Green, Blue, }
+    enum Shape { Circle(f64), Rect(f64, f64), Empty }
+    ";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve().unwrap();
+    let StmtElem::DecEnumElem(color) = &s_parser.code_list[0] else {
+        panic!("DecEnumElem expected");
+    };
+    let names: Vec<&str> = color.variants.iter().map(|v| &*v.name).collect();
+    assert_eq!(names, ["Red", "Green", "Blue"]);
+    assert!(color.variants.iter().all(|v| v.fields.is_empty()));
+
+    let StmtElem::DecEnumElem(shape) = &s_parser.code_list[1] else {
+        panic!("DecEnumElem expected");
+    };
+    let counts: Vec<usize> = shape.variants.iter().map(|v| v.fields.len()).collect();
+    assert_eq!(counts, [1, 2, 0]);
+
+    // 不正な宣言
+    for code in [
+        "enum { A }",
+        "enum E A",
+        "enum E { A B }",
+        "enum E { 1 }",
+        "enum E { A(1) }",
+    ] {
+        let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+        let r = s_parser.resolve();
+        assert!(
+            matches!(
+                r,
+                Err(ParserError::DecEnumError(_) | ParserError::InvalidType(_))
+            ),
+            "{} -> {:?}",
+            code,
+            r
+        );
+    }
+}
+
+#[test]
+fn enum_test01() -> anyhow::Result<()> {
+    // 値を持たない列挙型はタグの`i32`として扱う
+    let code = "
+    enum Color { Red, Green, Blue }
+    fn color(x: i32) -> Color {
+        if (x == 0) {
+            return Red;
+        };
+        if (x == 1) {
+            return Green;
+        };
+        return Blue;
+    }
+    pub_fn f(x: i32) -> i32 {
+        let c = color(x);
+        return match (c) {
+            Red => 10,
+            Green => 20,
+            Blue => 30,
+        };
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(!module_wat.contains("__alloc"));
+    assert!(module_wat.contains("br_table"));
+
+    for (x, expected) in [(0, 10), (1, 20), (2, 30)] {
+        let result = run_module(&module_wat, "f", &[Value::I32(x)])?;
+        assert_eq!(result[0], Value::I32(expected), "{}", x);
+    }
+    Ok(())
+}
+
+#[test]
+fn enum_test02() -> anyhow::Result<()> {
+    // 値を持つヴァリアントはタグと値をレコードに格納し、`match`で取り出す
+    let code = "
+    enum Shape { Circle(f64), Rect(f64, f64), Empty }
+    fn shape(kind: i32, a: f64, b: f64) -> Shape {
+        if (kind == 0) {
+            return Circle(a);
+        };
+        if (kind == 1) {
+            return Rect(a, b);
+        };
+        return Empty;
+    }
+    pub_fn area(kind: i32, a: f64, b: f64) -> f64 {
+        let s = shape(kind, a, b);
+        return match (s) {
+            Circle(r) => 3.0 * r * r,
+            Rect(w, h) => w * h,
+            Empty => 0.0,
+        };
+    }
+    pub_fn width(kind: i32, a: f64, b: f64) -> f64 {
+        return match (shape(kind, a, b)) {
+            Rect(w, _) => w,
+            _ => -1.0,
+        };
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    // タグの後に`f64`の値を8バイト境界に揃えて配置する
+    assert!(module_wat.contains("i32.const 24\ncall $__alloc\n"));
+    assert!(module_wat.contains("f64.store offset=16\n"));
+    assert!(module_wat.contains("i32.load offset=0\n"));
+
+    for (kind, expected) in [(0, 12.0), (1, 6.0), (2, 0.0)] {
+        let args = [Value::I32(kind), Value::F64(2.0), Value::F64(3.0)];
+        let result = run_module(&module_wat, "area", &args)?;
+        assert_eq!(result[0], Value::F64(expected), "{}", kind);
+    }
+    for (kind, expected) in [(0, -1.0), (1, 2.0), (2, -1.0)] {
+        let args = [Value::I32(kind), Value::F64(2.0), Value::F64(3.0)];
+        let result = run_module(&module_wat, "width", &args)?;
+        assert_eq!(result[0], Value::F64(expected), "{}", kind);
+    }
+    Ok(())
+}
+
+#[test]
+fn enum_test03() -> anyhow::Result<()> {
+    // 列挙型は構造体のフィールドや他の列挙型の値として使用できる
+    // 束縛した変数は腕の中でのみ参照できる
+    let code = "
+    struct Point { x: i64, y: i64 }
+    enum Dir { Up, Down }
+    enum Cmd { Move(Dir, i64), Jump(Point), Stop }
+    fn apply(p: Point, cmd: Cmd) {
+        match (cmd) {
+            Move(d, n) => {
+                match (d) {
+                    Up => { p.y += n; },
+                    Down => { p.y -= n; },
+                };
+            },
+            Jump(to) => {
+                p.x = to.x;
+                p.y = to.y;
+            },
+            Stop => {},
+        };
+    }
+    pub_fn f() -> i64 {
+        let_mut p = Point { x: 0, y: 0 };
+        apply(p, Move(Up, 5));
+        apply(p, Move(Down, 2));
+        apply(p, Stop);
+        let y = p.y;
+        apply(p, Jump(Point { x: 100, y: 7 }));
+        return p.x + p.y * 10 + y * 1000;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[])?;
+    assert_eq!(result[0], Value::I64(3170));
+    Ok(())
+}
+
+#[test]
+fn enum_test04() {
+    // 網羅していない`match`と到達しない腕
+    let header = "
+    enum Shape { Circle(f64), Rect(f64, f64), Empty }
+    ";
+    let code = format!(
+        "{}{}",
+        header,
+        "
+    pub_fn f(s: Shape) -> i32 {
+        return match (s) {
+            Circle(r) => 1,
+        };
+    }
+    "
+    );
+    match compile(&code) {
+        Err(GenerateError::NonExhaustivePatterns { missing, .. }) => {
+            assert_eq!(missing, ["Rect(_, _)", "Empty"]);
+        }
+        r => panic!("{:?}", r),
+    }
+
+    let mut s_parser = StmtParser::new(
+        format!(
+            "{}{}",
+            header,
+            "
+    pub_fn f(s: Shape) -> i32 {
+        return match (s) {
+            Circle(r) => 1,
+            Rect(w, h) => 2,
+            Circle(_) => 3,
+            Empty => 4,
+            _ => 5,
+        };
+    }
+    "
+        ),
+        0,
+        0,
+    );
+    s_parser.resolve().unwrap();
+    let (_, warnings) =
+        lichen_lang::gen::module::generate_wasm_module_with_warnings(&s_parser.code_list).unwrap();
+    assert_eq!(warnings.len(), 2, "{:?}", warnings);
+}
+
+#[test]
+fn enum_test05() {
+    // 列挙型に関するエラー
+    let header = "
+    enum Color { Red, Green }
+    enum Shape { Circle(f64), Rect(f64, f64) }
+    ";
+    let test_cases = [
+        // 宣言されていないヴァリアント
+        (
+            "fn f(c: Color) -> i32 { return match (c) { Red => 1, Gren => 2 }; }",
+            "UndefinedVariant",
+        ),
+        ("fn f() { let c = Blue; }", "UndefinedVariable"),
+        // 他の列挙型のヴァリアント
+        (
+            "fn f(c: Color) -> i32 { return match (c) { Red => 1, Circle(r) => 2, _ => 3 }; }",
+            "TypeMismatch",
+        ),
+        (
+            "fn f(x: i32) -> i32 { return match (x) { Red => 1, _ => 2 }; }",
+            "TypeMismatch",
+        ),
+        (
+            "fn f(c: Color) -> i32 { return match (c) { 0 => 1, _ => 2 }; }",
+            "TypeMismatch",
+        ),
+        // 値の数と型
+        (
+            "fn f() { let s = Circle(1.0, 2.0); }",
+            "ArgumentCountMismatch",
+        ),
+        ("fn f() { let s = Rect(1.0, 2); }", "TypeMismatch"),
+        (
+            "fn f(s: Shape) -> f64 { return match (s) { Circle(r, x) => r, _ => 0.0 }; }",
+            "ArgumentCountMismatch",
+        ),
+        ("fn f() { let c: Shape = Red; }", "TypeMismatch"),
+        // 演算
+        (
+            "fn f(a: Color, b: Color) { let c = a + b; }",
+            "InvalidOperandType",
+        ),
+        // 束縛した変数には再代入できない
+        (
+            "fn f(s: Shape) { match (s) { Circle(r) => { r = 1.0; }, _ => {} }; }",
+            "AssignToImmutable",
+        ),
+        // 束縛した変数は腕の外では参照できない
+        (
+            "fn f(s: Shape) -> f64 { match (s) { Circle(r) => {}, _ => {} }; return r; }",
+            "UndefinedVariable",
+        ),
+    ];
+    for (code, expected) in test_cases {
+        let r = compile(&format!("{}{}", header, code));
+        let Err(e) = &r else {
+            panic!("{} -> {:?}", code, r);
+        };
+        assert!(
+            format!("{:?}", e).starts_with(expected),
+            "{} -> {:?}",
+            code,
+            e
+        );
+    }
+
+    // 名前の重複
+    for code in [
+        "enum E { A, A }",
+        "enum E { A }\nenum F { A }",
+        "enum E { A }\nstruct E { x: i32 }",
+        "enum E { A }\nfn A() {}",
+    ] {
+        let r = compile(code);
+        assert!(
+            matches!(r, Err(GenerateError::DuplicateDefinition { .. })),
+            "{} -> {:?}",
+            code,
+            r
+        );
+    }
+}
+
+#[test]
+fn enum_test06() -> anyhow::Result<()> {
+    // ヴァリアントが持つ値はヴァリアント、リテラル、範囲のパターンでも検査できる
+    // 値のパターンの名前はヴァリアントでなければ変数の束縛になる
+    let code = "
+    enum Dir { Up, Down, Left }
+    enum Opt { Some(i32), Nothing }
+    enum Cmd { Move(Dir, i64), Wrap(Opt), Stop }
+    fn score(cmd: Cmd) -> i64 {
+        return match (cmd) {
+            Move(Up, 0) => 1,
+            Move(Up, n) => n,
+            Move(Down, _) => -1,
+            Move(d, 1..=9) => 10,
+            Wrap(Some(0..10)) => 20,
+            Wrap(Some(x)) => 30,
+            Wrap(Nothing) => 40,
+            _ => 50,
+        };
+    }
+    pub_fn moving(dir: i32, n: i64) -> i64 {
+        let d = match (dir) { 0 => Up, 1 => Down, _ => Left };
+        return score(Move(d, n));
+    }
+    pub_fn wrapped(x: i32) -> i64 {
+        return score(Wrap(Some(x)));
+    }
+    pub_fn rest() -> i64 {
+        return score(Wrap(Nothing)) + score(Stop) * 100;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    for (dir, n, expected) in [
+        (0, 0, 1),
+        (0, 7, 7),
+        (1, 0, -1),
+        (1, 5, -1),
+        (2, 5, 10),
+        (2, 10, 50),
+    ] {
+        let args = [Value::I32(dir), Value::I64(n)];
+        let result = run_module(&module_wat, "moving", &args)?;
+        assert_eq!(result[0], Value::I64(expected), "{} {}", dir, n);
+    }
+    for (x, expected) in [(0, 20), (9, 20), (10, 30), (-1, 30)] {
+        let result = run_module(&module_wat, "wrapped", &[Value::I32(x)])?;
+        assert_eq!(result[0], Value::I64(expected), "{}", x);
+    }
+    let result = run_module(&module_wat, "rest", &[])?;
+    assert_eq!(result[0], Value::I64(5040));
+    Ok(())
+}
+
+#[test]
+fn enum_test07() {
+    // ヴァリアントが持つ値のパターンの網羅性とエラー
+    let header = "
+    enum Color { Red, Green }
+    enum Dir { Up, Down, Left }
+    enum Opt { Some(i32), Nothing }
+    enum Cmd { Move(Dir, i64), Wrap(Opt), Stop }
+    ";
+    let code = format!(
+        "{}{}",
+        header,
+        "
+    pub_fn f(c: Cmd) -> i32 {
+        return match (c) {
+            Move(Up, _) => 1,
+            Wrap(Some(0..10)) => 2,
+            Stop => 3,
+        };
+    }
+    "
+    );
+    match compile(&code) {
+        Err(GenerateError::NonExhaustivePatterns { missing, .. }) => {
+            assert_eq!(
+                missing,
+                [
+                    "Move(Down, _)",
+                    "Move(Left, _)",
+                    "Wrap(Some(i32::MIN..=-1))",
+                    "Wrap(Some(10..=i32::MAX))",
+                    "Wrap(Nothing)",
+                ]
+            );
+        }
+        r => panic!("{:?}", r),
+    }
+
+    let mut s_parser = StmtParser::new(
+        format!(
+            "{}{}",
+            header,
+            "
+    pub_fn f(c: Cmd) -> i32 {
+        return match (c) {
+            Move(Up, 0..5) => 1,
+            Move(Up, 2) => 2,
+            Move(Up, _) => 3,
+            Move(Down, _) => 4,
+            Move(Left, _) => 5,
+            Move(_, _) => 6,
+            Wrap(_) => 7,
+            Stop => 8,
+        };
+    }
+    "
+        ),
+        0,
+        0,
+    );
+    s_parser.resolve().unwrap();
+    let (_, warnings) =
+        lichen_lang::gen::module::generate_wasm_module_with_warnings(&s_parser.code_list).unwrap();
+    assert_eq!(warnings.len(), 2, "{:?}", warnings);
+
+    let test_cases = [
+        // 他の列挙型のヴァリアント
+        (
+            "fn f(c: Cmd) -> i32 { return match (c) { Move(Red, _) => 1, _ => 2 }; }",
+            "TypeMismatch",
+        ),
+        (
+            "fn f(c: Cmd) -> i32 { return match (c) { Move(Up, Nothing) => 1, _ => 2 }; }",
+            "TypeMismatch",
+        ),
+        // 値の型と異なるリテラル
+        (
+            "fn f(c: Cmd) -> i32 { return match (c) { Move(Up, 1.5) => 1, _ => 2 }; }",
+            "TypeMismatch",
+        ),
+        (
+            "fn f(c: Cmd) -> i32 { return match (c) { Move(5, _) => 1, _ => 2 }; }",
+            "TypeMismatch",
+        ),
+        (
+            "fn f(c: Cmd) -> i32 { return match (c) { Wrap(Some(3000000000)) => 1, _ => 2 }; }",
+            "LiteralOutOfRange",
+        ),
+        // 値の数
+        (
+            "fn f(c: Cmd) -> i32 { return match (c) { Wrap(Some) => 1, _ => 2 }; }",
+            "ArgumentCountMismatch",
+        ),
+        // 宣言されていないヴァリアント
+        (
+            "fn f(c: Cmd) -> i32 { return match (c) { Wrap(Sme(x)) => 1, _ => 2 }; }",
+            "UndefinedVariant",
+        ),
+        // パターンとして使えない式
+        (
+            "fn f(c: Cmd) -> i32 { return match (c) { Move(Up, n + 1) => 1, _ => 2 }; }",
+            "InvalidPattern",
+        ),
+    ];
+    for (code, expected) in test_cases {
+        let r = compile(&format!("{}{}", header, code));
+        let Err(e) = &r else {
+            panic!("{} -> {:?}", code, r);
+        };
+        assert!(
+            format!("{:?}", e).starts_with(expected),
+            "{} -> {:?}",
+            code,
+            e
+        );
+    }
+}
@@ -0,0 +1,280 @@
+// `for (x in iterable) {}`のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::errors::generate_errors::GenerateError;
+
+use wasmer::Value;
+
+#[test]
+fn for_test00() -> anyhow::Result<()> {
+    // 終わりの値を含まない範囲
+    let code = "
+    pub_fn sum(n: i32) -> i32 {
+        let_mut s = 0;
+        for (i in 0..n) {
+            s += i;
+        };
+        return s;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("loop $#f0"));
+    assert!(module_wat.contains("block $#b0"));
+
+    let result = run_module(&module_wat, "sum", &[Value::I32(10)])?;
+    assert_eq!(result[0], Value::I32(45));
+    let result = run_module(&module_wat, "sum", &[Value::I32(0)])?;
+    assert_eq!(result[0], Value::I32(0));
+    Ok(())
+}
+
+#[test]
+fn for_test01() -> anyhow::Result<()> {
+    // 終わりの値を含む範囲
+    let code = "
+    pub_fn sum(a: i32, b: i32) -> i32 {
+        let_mut s = 0;
+        for (i in a..=b) {
+            s += i;
+        };
+        return s;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "sum", &[Value::I32(1), Value::I32(10)])?;
+    assert_eq!(result[0], Value::I32(55));
+    let result = run_module(&module_wat, "sum", &[Value::I32(3), Value::I32(3)])?;
+    assert_eq!(result[0], Value::I32(3));
+    let result = run_module(&module_wat, "sum", &[Value::I32(4), Value::I32(3)])?;
+    assert_eq!(result[0], Value::I32(0));
+    // 終わりの値が型の最大値でもあふれない
+    let result = run_module(
+        &module_wat,
+        "sum",
+        &[Value::I32(i32::MAX), Value::I32(i32::MAX)],
+    )?;
+    assert_eq!(result[0], Value::I32(i32::MAX));
+    Ok(())
+}
+
+#[test]
+fn for_test02() -> anyhow::Result<()> {
+    // `continue`は次の要素に進み、`break`はループを抜ける
+    let code = "
+    pub_fn sum_odd(n: i32) -> i32 {
+        let_mut s = 0;
+        for (i in 0..100) {
+            if (i >= n) {
+                break;
+            };
+            if (i % 2 == 0) {
+                continue;
+            };
+            s += i;
+        };
+        return s;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "sum_odd", &[Value::I32(10)])?;
+    assert_eq!(result[0], Value::I32(1 + 3 + 5 + 7 + 9));
+    Ok(())
+}
+
+#[test]
+fn for_test03() -> anyhow::Result<()> {
+    // 入れ子になったループ
+    let code = "
+    pub_fn count(n: i32) -> i32 {
+        let_mut c = 0;
+        for (i in 0..n) {
+            for (j in 0..i) {
+                c += 1;
+            };
+        };
+        return c;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("loop $#f1"));
+
+    // 0 + 1 + 2 + 3 + 4
+    let result = run_module(&module_wat, "count", &[Value::I32(5)])?;
+    assert_eq!(result[0], Value::I32(10));
+    Ok(())
+}
+
+#[test]
+fn for_test04() -> anyhow::Result<()> {
+    // 範囲の型は両端の型になる
+    let code = "
+    pub_fn sum(n: i64) -> i64 {
+        let_mut s: i64 = 0;
+        for (i in 1..n) {
+            s += i * i;
+        };
+        return s;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i64.ge_s"));
+    let result = run_module(&module_wat, "sum", &[Value::I64(4)])?;
+    assert_eq!(result[0], Value::I64(1 + 4 + 9));
+    Ok(())
+}
+
+#[test]
+fn for_test05() -> anyhow::Result<()> {
+    // 配列のリテラル
+    let code = "
+    pub_fn sum(a: f64) -> f64 {
+        let_mut s = 0.0;
+        for (x in [1.5, a, 2.0]) {
+            s += x;
+        };
+        return s;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "sum", &[Value::F64(0.25)])?;
+    assert_eq!(result[0], Value::F64(3.75));
+    Ok(())
+}
+
+#[test]
+fn for_test06() {
+    // 束縛した変数には再代入できない
+    let code = "
+    pub_fn f() -> i32 {
+        for (i in 0..10) {
+            i = 1;
+        };
+        return 0;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::AssignToImmutable { .. })
+    ));
+}
+
+#[test]
+fn for_test07() {
+    // 束縛した変数はループの外では参照できない
+    let code = "
+    pub_fn f() -> i32 {
+        for (i in 0..10) {
+        };
+        return i;
+    }
+    ";
+    assert!(compile(code).is_err());
+}
+
+#[test]
+fn for_test08() {
+    // 反復できない値
+    let code = "
+    pub_fn f(n: i32) -> i32 {
+        for (i in n) {
+        };
+        return 0;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::NotIterable { .. })
+    ));
+    // 整数以外の範囲
+    let code = "
+    pub_fn f() -> i32 {
+        for (i in 0.0..1.0) {
+        };
+        return 0;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::InvalidOperandType { .. })
+    ));
+    // `in`のない`for`
+    let code = "
+    pub_fn f() -> i32 {
+        for (i) {
+        };
+        return 0;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::InvalidForLoop(_))
+    ));
+}
+
+#[test]
+fn for_test09() -> anyhow::Result<()> {
+    // 配列のリテラルの要素はループの前に一度だけ評価する
+    let code = "
+    struct Counter { n: i32 }
+    fn bump(c: Counter) -> i32 {
+        c.n += 1;
+        return c.n * 10;
+    }
+    pub_fn f() -> i32 {
+        let_mut c = Counter { n: 0 };
+        let_mut s = 0;
+        for (x in [bump(c), bump(c), bump(c), bump(c)]) {
+            s += x;
+        };
+        return s + c.n * 1000;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(!module_wat.contains("select"));
+    let result = run_module(&module_wat, "f", &[])?;
+    assert_eq!(result[0], Value::I32(4100));
+    Ok(())
+}
+
+#[test]
+fn for_test10() -> anyhow::Result<()> {
+    // 変数や引数の配列、配列の配列、構造体の配列
+    let code = "
+    struct Point { x: i32, y: i32 }
+    fn total(a: [f64; 3]) -> f64 {
+        let_mut s = 0.0;
+        for (x in a) {
+            s += x;
+        };
+        return s;
+    }
+    pub_fn f() -> i32 {
+        let a = [4, 5, 6];
+        let_mut s = 0;
+        for (x in a) {
+            s += x;
+        };
+        let m = [[1, 2], [3, 4]];
+        for (row in m) {
+            for (v in row) {
+                s += v * 10;
+            };
+        };
+        let ps = [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+        for (p in ps) {
+            s += p.x * 100 + p.y * 1000;
+        };
+        return s;
+    }
+    pub_fn g() -> f64 {
+        return total([0.5, 1.25, 2.0]);
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[])?;
+    assert_eq!(result[0], Value::I32(6515));
+    let result = run_module(&module_wat, "g", &[])?;
+    assert_eq!(result[0], Value::F64(3.75));
+    Ok(())
+}
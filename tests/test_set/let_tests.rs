@@ -0,0 +1,85 @@
+// `let` `let_mut`によるローカル変数のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::errors::generate_errors::GenerateError;
+
+use wasmer::Value;
+
+#[test]
+fn let_test00() -> anyhow::Result<()> {
+    // 宣言と初期化
+    let code = "
+    pub_fn f(n: i32) -> i32 {
+        let a = n * 2;
+        let_mut b: i32 = a + 1;
+        b += 10;
+        let_mut c;
+        c = b;
+        return c;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("(local $a i32)"));
+    assert!(module_wat.contains("(local $b i32)"));
+    assert!(module_wat.contains("(local $c i32)"));
+    assert!(module_wat.contains("local.set $a"));
+
+    let result = run_module(&module_wat, "f", &[Value::I32(3)])?;
+    assert_eq!(result[0], Value::I32(17));
+    Ok(())
+}
+
+#[test]
+fn let_test01() -> anyhow::Result<()> {
+    // 内側のブロックでのシャドーイング
+    let code = "
+    pub_fn f(n: i32) -> i32 {
+        let a = 1;
+        let_mut total = 0;
+        if (n > 0) {
+            let a = a + 100;
+            total += a;
+        };
+        total += a;
+        let a = a + 1000;
+        return total + a;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    // 同じ名前の変数は一意な名前のローカル変数として宣言される
+    assert!(module_wat.contains("(local $a i32)"));
+    assert!(module_wat.contains("(local $a.1 i32)"));
+    assert!(module_wat.contains("(local $a.2 i32)"));
+
+    // (1 + 100) + 1 + (1 + 1000)
+    let result = run_module(&module_wat, "f", &[Value::I32(1)])?;
+    assert_eq!(result[0], Value::I32(1103));
+    // 1 + (1 + 1000)
+    let result = run_module(&module_wat, "f", &[Value::I32(0)])?;
+    assert_eq!(result[0], Value::I32(1002));
+    Ok(())
+}
+
+#[test]
+fn let_test02() {
+    // 型注釈は宣言されるローカル変数の型になる
+    let code = "
+    pub_fn f() {
+        let_mut a: i64 = 1;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("(local $a i64)"));
+
+    // 左辺が変数名ではない場合
+    let code = "
+    pub_fn f() {
+        let 1 + 2 = 3;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::InvalidleftPattern(_))
+    ));
+}
@@ -0,0 +1,74 @@
+// 論理演算子の短絡評価のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+
+use wasmer::Value;
+
+#[test]
+fn logical_test00() -> anyhow::Result<()> {
+    // 左辺で結果が決まる場合は右辺を評価しない
+    let code = "
+    pub_fn and(i: i32) -> i32 {
+        if (i != 0 && 10 / i > 1) {
+            return 1;
+        };
+        return 0;
+    }
+    pub_fn or(i: i32) -> i32 {
+        if (i == 0 || 10 / i > 1) {
+            return 1;
+        };
+        return 0;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("if (result i32)"));
+    assert!(!module_wat.contains("i32.and"));
+
+    assert_eq!(
+        run_module(&module_wat, "and", &[Value::I32(0)])?[0],
+        Value::I32(0)
+    );
+    assert_eq!(
+        run_module(&module_wat, "and", &[Value::I32(2)])?[0],
+        Value::I32(1)
+    );
+    assert_eq!(
+        run_module(&module_wat, "and", &[Value::I32(20)])?[0],
+        Value::I32(0)
+    );
+    assert_eq!(
+        run_module(&module_wat, "or", &[Value::I32(0)])?[0],
+        Value::I32(1)
+    );
+    assert_eq!(
+        run_module(&module_wat, "or", &[Value::I32(20)])?[0],
+        Value::I32(0)
+    );
+    Ok(())
+}
+
+#[test]
+fn logical_test01() -> anyhow::Result<()> {
+    // 結果は0か1に正規化される
+    let code = "
+    pub_fn and(a: i32, b: i32) -> i32 {
+        return a && b;
+    }
+    pub_fn or(a: i32, b: i32) -> i32 {
+        return a || b;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let call =
+        |name: &str, a: i32, b: i32| run_module(&module_wat, name, &[Value::I32(a), Value::I32(b)]);
+    assert_eq!(call("and", 2, 1)?[0], Value::I32(1));
+    assert_eq!(call("and", 2, 4)?[0], Value::I32(1));
+    assert_eq!(call("and", 2, 0)?[0], Value::I32(0));
+    assert_eq!(call("and", 0, 4)?[0], Value::I32(0));
+    assert_eq!(call("or", 0, 4)?[0], Value::I32(1));
+    assert_eq!(call("or", 3, 0)?[0], Value::I32(1));
+    assert_eq!(call("or", 0, 0)?[0], Value::I32(0));
+    Ok(())
+}
@@ -0,0 +1,112 @@
+// `loop {}`のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::abs::ast::*;
+use lichen_lang::errors::generate_errors::GenerateError;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+use wasmer::Value;
+
+#[test]
+fn loop_test00() -> anyhow::Result<()> {
+    // `break`と`continue`
+    let code = "
+    pub_fn sum_odd(n: i32) -> i32 {
+        let_mut i = 0;
+        let_mut sum = 0;
+        loop {
+            i += 1;
+            if (i > n) {
+                break;
+            };
+            if (i % 2 == 0) {
+                continue;
+            };
+            sum += i;
+        };
+        return sum;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("loop $#l0"));
+    assert!(module_wat.contains("block $#b0"));
+
+    let result = run_module(&module_wat, "sum_odd", &[Value::I32(10)])?;
+    assert_eq!(result[0], Value::I32(1 + 3 + 5 + 7 + 9));
+    Ok(())
+}
+
+#[test]
+fn loop_test01() -> anyhow::Result<()> {
+    // 入れ子になったループの`break`は内側のループを抜ける
+    let code = "
+    pub_fn count(n: i32) -> i32 {
+        let_mut i = 0;
+        let_mut c = 0;
+        while (i < n) {
+            let_mut j = 0;
+            loop {
+                if (j >= i) {
+                    break;
+                };
+                c += 1;
+                j += 1;
+            };
+            i += 1;
+        };
+        loop {
+            return c;
+        };
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("loop $#l1"));
+
+    // 0 + 1 + 2 + 3 + 4
+    let result = run_module(&module_wat, "count", &[Value::I32(5)])?;
+    assert_eq!(result[0], Value::I32(10));
+    Ok(())
+}
+
+#[test]
+fn loop_test02() {
+    // `loop`の中の文は一段深いループに属する
+    let code = "loop { break; };";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve().unwrap();
+    let StmtElem::ExprElem(expr_b) = &s_parser.code_list[0] else {
+        panic!("ExprElem expected");
+    };
+    let ExprElem::SyntaxBoxElem(syntax_box_b) = &expr_b.code_list[0] else {
+        panic!("SyntaxBoxElem expected");
+    };
+    let StmtElem::Special(stmt_b) = &syntax_box_b.contents[0].contents[0] else {
+        panic!("Special expected");
+    };
+    assert_eq!(stmt_b.head, "break");
+    assert_eq!(stmt_b.loopdepth, 1);
+}
+
+#[test]
+fn loop_test03() {
+    // ループの外の`break` `continue`
+    for (code, keyword) in [
+        ("pub_fn f() {\nbreak;\n}", "break"),
+        (
+            "pub_fn f(a: i32) {\nif (a > 0) {\ncontinue;\n};\n}",
+            "continue",
+        ),
+    ] {
+        let e = compile(code).unwrap_err();
+        assert!(
+            matches!(&e, GenerateError::BreakOutsideLoop { keyword: k, .. } if k == keyword),
+            "{} -> {:?}",
+            code,
+            e
+        );
+        let rendered = e.to_diagnostic().render(code);
+        assert!(rendered.contains(&format!("`{}` outside of a loop", keyword)));
+    }
+}
@@ -0,0 +1,221 @@
+// `match`のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::abs::ast::*;
+use lichen_lang::errors::generate_errors::GenerateError;
+use lichen_lang::errors::parser_errors::ParserError;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+use wasmer::Value;
+
+#[test]
+fn match_test00() {
+    // `match`は腕ごとにパターンと値に分けて解析される
+    let code = "match (x) { 0 => a, 1..10 => b + 1, _ => { c = 1; } };";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve().unwrap();
+    let StmtElem::ExprElem(expr_b) = &s_parser.code_list[0] else {
+        panic!("ExprElem expected");
+    };
+    let ExprElem::MatchElem(match_b) = &expr_b.code_list[0] else {
+        panic!("MatchElem expected");
+    };
+    assert_eq!(match_b.expr.len(), 1);
+    assert_eq!(match_b.arms.len(), 3);
+    assert!(matches!(
+        &match_b.arms[0].pattern[..],
+        [ExprElem::NumElem(_)]
+    ));
+    assert!(matches!(
+        &match_b.arms[1].pattern[..],
+        [ExprElem::FuncElem(_)]
+    ));
+    assert!(matches!(
+        &match_b.arms[1].contents[..],
+        [ExprElem::FuncElem(_)]
+    ));
+    assert!(matches!(&match_b.arms[2].pattern[..], [ExprElem::WordElem(w)] if w.contents == "_"));
+    assert!(matches!(
+        &match_b.arms[2].contents[..],
+        [ExprElem::BlockElem(_)]
+    ));
+
+    // `=>`のない腕
+    let code = "match (x) { 0 => a, 1 };";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    assert!(matches!(
+        s_parser.resolve(),
+        Err(ParserError::MatchArmError(_))
+    ));
+}
+
+#[test]
+fn match_test01() -> anyhow::Result<()> {
+    // 密な整数のパターンは`br_table`で分岐する
+    let code = "
+    pub_fn f(x: i32) -> i32 {
+        let y = match (x) {
+            0 => 10,
+            1 => 20,
+            2..=4 => 30,
+            5 => 40,
+            _ => -1,
+        };
+        return y;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("br_table"));
+
+    for (x, expected) in [
+        (0, 10),
+        (1, 20),
+        (2, 30),
+        (4, 30),
+        (5, 40),
+        (6, -1),
+        (-1, -1),
+        (i32::MIN, -1),
+    ] {
+        let result = run_module(&module_wat, "f", &[Value::I32(x)])?;
+        assert_eq!(result[0], Value::I32(expected), "{}", x);
+    }
+    Ok(())
+}
+
+#[test]
+fn match_test02() -> anyhow::Result<()> {
+    // 疎なパターンは比較の連鎖で分岐し、先に書かれた腕が優先される
+    let code = "
+    pub_fn f(x: i64) -> i64 {
+        return match (x) {
+            -100 => 1,
+            0..1000 => 2,
+            500 => 3,
+            1000000 => 4,
+            _ => 5,
+        };
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(!module_wat.contains("br_table"));
+
+    for (x, expected) in [
+        (-100, 1),
+        (0, 2),
+        (500, 2),
+        (999, 2),
+        (1000, 5),
+        (1000000, 4),
+        (-1, 5),
+    ] {
+        let result = run_module(&module_wat, "f", &[Value::I64(x)])?;
+        assert_eq!(result[0], Value::I64(expected), "{}", x);
+    }
+    Ok(())
+}
+
+#[test]
+fn match_test03() -> anyhow::Result<()> {
+    // 値を持たない`match`は文として使える
+    let code = "
+    pub_fn count(n: u32) -> i32 {
+        let_mut c = 0;
+        let_mut i = 0u32;
+        while (i < n) {
+            match (i % 4u32) {
+                0 => { c += 1; },
+                1 => { c += 10; },
+                3 => { c += 100; },
+                _ => {},
+            };
+            i += 1u32;
+        };
+        return c;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    // 0..8 -> 0 1 2 3 0 1 2 3
+    let result = run_module(&module_wat, "count", &[Value::I32(8)])?;
+    assert_eq!(result[0], Value::I32(222));
+    Ok(())
+}
+
+#[test]
+fn match_test04() -> anyhow::Result<()> {
+    // 入れ子になった`match`と浮動小数点数
+    let code = "
+    pub_fn f(x: i32, y: f64) -> f64 {
+        return match (x) {
+            0 => match (y) {
+                0.5 => 1.0,
+                _ => 2.0,
+            },
+            _ => y * 2.0,
+        };
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[Value::I32(0), Value::F64(0.5)])?;
+    assert_eq!(result[0], Value::F64(1.0));
+    let result = run_module(&module_wat, "f", &[Value::I32(0), Value::F64(0.25)])?;
+    assert_eq!(result[0], Value::F64(2.0));
+    let result = run_module(&module_wat, "f", &[Value::I32(1), Value::F64(0.25)])?;
+    assert_eq!(result[0], Value::F64(0.5));
+    Ok(())
+}
+
+#[test]
+fn match_test05() {
+    // パターンの型は検査する値の型と一致しなければならない
+    let code = "
+    pub_fn f(x: i32) -> i32 {
+        return match (x) {
+            1.5 => 1,
+            _ => 0,
+        };
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::TypeMismatch { .. })
+    ));
+    // 腕の値の型は一致しなければならない
+    let code = "
+    pub_fn f(x: i32) -> i32 {
+        return match (x) {
+            1 => 1,
+            _ => 2i64,
+        };
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::TypeMismatch { .. })
+    ));
+    // 変数はパターンとして使えない
+    let code = "
+    pub_fn f(x: i32, y: i32) -> i32 {
+        return match (x) {
+            y => 1,
+            _ => 0,
+        };
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::InvalidPattern(_))
+    ));
+    // 値が型の範囲に収まらないパターン
+    let code = "
+    pub_fn f(x: u32) -> i32 {
+        return match (x) {
+            -1 => 1,
+            _ => 0,
+        };
+    }
+    ";
+    assert!(compile(code).is_err());
+}
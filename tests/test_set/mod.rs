@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(clippy::empty_docs)]
 pub mod gen_tests;
 
 #[cfg(test)]
@@ -6,3 +7,64 @@ pub mod expr_tests;
 
 #[cfg(test)]
 pub mod stmt_tests;
+
+#[cfg(test)]
+pub mod span_tests;
+
+#[cfg(test)]
+pub mod diagnostic_tests;
+
+#[cfg(test)]
+pub mod recovery_tests;
+
+#[cfg(test)]
+pub mod no_panic_tests;
+
+#[cfg(test)]
+pub mod decfunc_tests;
+
+#[cfg(test)]
+pub mod module_tests;
+
+#[cfg(test)]
+pub mod let_tests;
+
+#[cfg(test)]
+pub mod mutability_tests;
+
+#[cfg(test)]
+pub mod resolver_tests;
+
+#[cfg(test)]
+pub mod type_tests;
+
+#[cfg(test)]
+pub mod array_tests;
+#[cfg(test)]
+pub mod bitwise_tests;
+#[cfg(test)]
+pub mod cast_tests;
+#[cfg(test)]
+pub mod coverage_tests;
+#[cfg(test)]
+pub mod dot_tests;
+#[cfg(test)]
+pub mod enum_tests;
+#[cfg(test)]
+pub mod for_tests;
+#[cfg(test)]
+pub mod logical_tests;
+#[cfg(test)]
+pub mod loop_tests;
+#[cfg(test)]
+pub mod match_tests;
+#[cfg(test)]
+pub mod num_literal_tests;
+#[cfg(test)]
+pub mod pow_tests;
+#[cfg(test)]
+pub mod struct_tests;
+#[cfg(test)]
+pub mod typed_gen_tests;
+#[cfg(test)]
+pub mod unsigned_tests;
@@ -0,0 +1,194 @@
+// モジュール生成のテスト
+// 関数宣言から生成した`(module ...)`をwasmerで実行して結果を検証する
+
+extern crate lichen_lang;
+use lichen_lang::errors::generate_errors::GenerateError;
+use lichen_lang::gen::module::generate_wasm_module;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+use wasmer::{imports, Instance, Module, Store, Value};
+
+/// ソースコードからwasmのモジュールを生成する
+pub fn compile(code: &str) -> Result<String, GenerateError> {
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve().unwrap();
+    generate_wasm_module(&s_parser.code_list)
+}
+
+/// 生成したモジュールの関数`name`を呼び出す
+pub fn run_module(module_wat: &str, name: &str, args: &[Value]) -> anyhow::Result<Box<[Value]>> {
+    println!("--- wasm code ---");
+    println!("{}", module_wat);
+    let mut store = Store::default();
+    let module = Module::new(&store, module_wat)?;
+    let import_object = imports! {};
+    let instance = Instance::new(&mut store, &module, &import_object)?;
+    let func = instance.exports.get_function(name)?;
+    Ok(func.call(&mut store, args)?)
+}
+
+#[test]
+fn module_test00() -> anyhow::Result<()> {
+    // 引数と返り値
+    let code = "
+    pub_fn add(a: i32, b: i32) -> i32 {
+        return a + b;
+    }
+    pub_fn sub(a: i32, b: i32) -> i32 {
+        return a - b;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    // 同じシグネチャの型は一つだけ宣言される
+    let types = module_wat.lines().filter(|l| l.starts_with("(type "));
+    assert_eq!(types.count(), 1);
+    assert!(module_wat.contains("(export \"add\" (func $add))"));
+
+    let result = run_module(&module_wat, "add", &[Value::I32(2), Value::I32(3)])?;
+    assert_eq!(result[0], Value::I32(5));
+    let result = run_module(&module_wat, "sub", &[Value::I32(2), Value::I32(3)])?;
+    assert_eq!(result[0], Value::I32(-1));
+    Ok(())
+}
+
+#[test]
+fn module_test01() -> anyhow::Result<()> {
+    // ローカル変数、ループ、関数呼び出し
+    let code = "
+    fn rem(a: i32, b: i32) -> i32 {
+        return a % b;
+    }
+    pub_fn gcd(a: i32, b: i32) -> i32 {
+        while (b != 0) {
+            let t = rem(a, b);
+            a = b;
+            b = t;
+        };
+        return a;
+    }
+    pub_fn count_primes(n: i32) -> i32 {
+        fn is_prime(x: i32) -> i32 {
+            let_mut i = 2;
+            while (i < x) {
+                if (x % i == 0) {
+                    return 0;
+                };
+                i += 1;
+            };
+            return 1;
+        }
+        let_mut counter = 0;
+        let_mut i = 2;
+        while (i < n) {
+            counter += is_prime(i);
+            i += 1;
+        };
+        return counter;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("(local $t i32)"));
+    // 非公開の関数はexportされない
+    assert!(!module_wat.contains("(export \"rem\""));
+    assert!(!module_wat.contains("(export \"is_prime\""));
+
+    let result = run_module(&module_wat, "gcd", &[Value::I32(48), Value::I32(18)])?;
+    assert_eq!(result[0], Value::I32(6));
+    let result = run_module(&module_wat, "count_primes", &[Value::I32(100)])?;
+    assert_eq!(result[0], Value::I32(25));
+    Ok(())
+}
+
+#[test]
+fn module_test02() {
+    // トップレベルに関数宣言以外の文がある場合
+    let code = "
+    let a = 1;
+    pub_fn f() {}
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::UnsupportedStatement(_))
+    ));
+}
+
+#[test]
+fn module_test03() -> anyhow::Result<()> {
+    // 関数の中で宣言された関数は外側の関数の名前を付けて生成する
+    // 内側で宣言された関数は外側の同じ名前の関数よりも優先される
+    let code = "
+    fn helper() -> i32 {
+        return 1;
+    }
+    fn g() -> i32 {
+        fn helper() -> i32 {
+            return 10;
+        }
+        return helper();
+    }
+    pub_fn f() -> i32 {
+        fn helper() -> i32 {
+            fn helper() -> i32 {
+                return 1000;
+            }
+            return 100 + helper();
+        }
+        return helper() + g();
+    }
+    pub_fn h() -> i32 {
+        return helper();
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("(func $g.helper "));
+    assert!(module_wat.contains("(func $f.helper.helper "));
+    assert!(module_wat.contains("call $f.helper\n"));
+    let result = run_module(&module_wat, "f", &[])?;
+    assert_eq!(result[0], Value::I32(1110));
+    let result = run_module(&module_wat, "h", &[])?;
+    assert_eq!(result[0], Value::I32(1));
+    Ok(())
+}
+
+#[test]
+fn module_test04() {
+    // 同じスコープで宣言された関数の名前の重複と予約された名前
+    for (code, expected) in [
+        ("fn f() {}\nfn f() {}", "DuplicateDefinition"),
+        (
+            "fn g() {\nfn helper() {}\nfn helper() {}\n}",
+            "DuplicateDefinition",
+        ),
+        ("fn __alloc(n: i32) -> i32 { return n; }", "ReservedName"),
+        ("fn __pow_i32() {}", "ReservedName"),
+        ("fn g() {\nfn __helper() {}\n}", "ReservedName"),
+    ] {
+        let r = compile(code);
+        let Err(e) = &r else {
+            panic!("{} -> {:?}", code, r);
+        };
+        assert!(
+            format!("{:?}", e).starts_with(expected),
+            "{} -> {:?}",
+            code,
+            e
+        );
+    }
+}
+
+#[test]
+fn module_test05() {
+    // 引数の名前の重複は二つ目の引数の位置で報告する
+    for (code, column) in [
+        ("fn f(a: i32, a: i32) {}", 14),
+        ("fn g() {\nfn helper(x: f64, y: f64, x: i32) {}\n}", 27),
+    ] {
+        let r = compile(code);
+        let Err(GenerateError::DuplicateDefinition { name, span }) = &r else {
+            panic!("{} -> {:?}", code, r);
+        };
+        assert!(name == "a" || name == "x", "{}", name);
+        assert_eq!(span.column, column, "{}", code);
+    }
+}
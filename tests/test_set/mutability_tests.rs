@@ -0,0 +1,278 @@
+// `let`と`let_mut`の再代入の検査のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::abs::span::Span;
+use lichen_lang::errors::generate_errors::GenerateError;
+
+use wasmer::Value;
+
+#[test]
+fn mutability_test00() -> anyhow::Result<()> {
+    // `let_mut`と引数には再代入できる
+    let code = "
+    pub_fn f(n: i32) -> i32 {
+        let_mut a = 1;
+        a = a + n;
+        a *= 3;
+        n -= 1;
+        return a + n;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[Value::I32(2)])?;
+    assert_eq!(result[0], Value::I32(10));
+    Ok(())
+}
+
+#[test]
+fn mutability_test01() {
+    // `let`で宣言された変数への代入と複合代入
+    for assignment in ["a = 2;", "a += 2;", "a %= 2;"] {
+        let code = format!("pub_fn f() {{\nlet a = 1;\n{}\n}}", assignment);
+        let Err(GenerateError::AssignToImmutable {
+            name,
+            span,
+            declared,
+        }) = compile(&code)
+        else {
+            panic!("{}", assignment);
+        };
+        assert_eq!(name, "a");
+        assert_eq!(declared, Span::new(17, 18, 2, 5));
+        assert_eq!((span.line, span.column), (3, 1));
+    }
+}
+
+#[test]
+fn mutability_test02() {
+    // シャドーイングした変数の可変性は宣言ごとに異なる
+    let code = "
+    pub_fn f() {
+        let_mut a = 1;
+        if (a > 0) {
+            let a = 2;
+            a = 3;
+        };
+        a = 4;
+    }
+    ";
+    let Err(GenerateError::AssignToImmutable { span, declared, .. }) = compile(code) else {
+        panic!();
+    };
+    assert_eq!((declared.line, span.line), (5, 6));
+
+    let code = "
+    pub_fn f() {
+        let a = 1;
+        if (a > 0) {
+            let_mut a = 2;
+            a = 3;
+        };
+    }
+    ";
+    assert!(compile(code).is_ok());
+}
+
+#[test]
+fn mutability_test03() {
+    // 宣言されていない変数への代入
+    let code = "
+    pub_fn f() {
+        if (1 > 0) {
+            let_mut a = 1;
+        };
+        a = 2;
+    }
+    ";
+    let e = compile(code).unwrap_err();
+    assert!(matches!(&e, GenerateError::UndefinedVariable { name, .. } if name == "a"));
+    assert_eq!(e.get_span().line, 6);
+}
+
+#[test]
+fn mutability_test04() {
+    // 診断には代入と宣言の両方が表示される
+    let code = "pub_fn f() {\nlet a = 1;\na = 2;\n}";
+    let diagnostic = compile(code).unwrap_err().to_diagnostic();
+    let rendered = diagnostic.render(code);
+    println!("{}", rendered);
+    assert!(rendered.contains("cannot assign twice to immutable variable `a`"));
+    assert!(rendered.contains("declared as immutable here"));
+    assert!(rendered.contains("let_mut a"));
+}
+
+#[test]
+fn mutability_test05() -> anyhow::Result<()> {
+    // 引数のフィールドや要素に代入する関数には`let`で宣言した変数を渡せない
+    // 引数をそのまま別の関数に渡す関数も同様に扱う
+    let code = "
+    struct Point { x: f64, y: f64 }
+    fn set_y(p: Point, y: f64) {
+        p.y = y;
+    }
+    fn lift(p: Point) {
+        set_y(p, p.y + 1.0);
+    }
+    fn norm(p: Point) -> f64 {
+        return p.x * p.x + p.y * p.y;
+    }
+    pub_fn f() -> f64 {
+        let p = Point { x: 1.0, y: 2.0 };
+        let_mut q = p;
+        lift(q);
+        set_y(Point { x: 0.0, y: 0.0 }, 1.0);
+        return norm(p) + norm(q);
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[])?;
+    assert_eq!(result[0], Value::F64(5.0 + 10.0));
+
+    for (call, function) in [("set_y(p, 1.0);", "set_y"), ("lift(p);", "lift")] {
+        let code = format!(
+            "struct Point {{ x: f64, y: f64 }}
+fn set_y(p: Point, y: f64) {{
+p.y = y;
+}}
+fn lift(p: Point) {{
+set_y(p, p.y + 1.0);
+}}
+pub_fn f() {{
+let p = Point {{ x: 1.0, y: 2.0 }};
+{}
+}}",
+            call
+        );
+        let Err(GenerateError::ImmutableArgument {
+            name,
+            function: found,
+            span,
+            declared,
+        }) = compile(&code)
+        else {
+            panic!("{}", call);
+        };
+        assert_eq!((name.as_str(), found.as_str()), ("p", function));
+        assert_eq!((span.line, declared.line), (10, 9));
+    }
+    Ok(())
+}
+
+#[test]
+fn mutability_test06() {
+    // `for`と`match`で束縛した変数、関数の中で宣言された関数
+    for body in [
+        "for (row in rows) { clear(row); };",
+        "match (Wrap(rows[0])) { Wrap(row) => { clear(row); }, };",
+        "fn inner(r: [i32; 2]) { clear(r); }\nlet r = rows[0];\ninner(r);",
+    ] {
+        let code = format!(
+            "enum E {{ Wrap([i32; 2]) }}
+fn clear(a: [i32; 2]) {{
+a[0] = 0;
+}}
+pub_fn f() {{
+let_mut rows = [[1, 2], [3, 4]];
+{}
+}}",
+            body
+        );
+        let e = compile(&code).unwrap_err();
+        assert!(
+            matches!(&e, GenerateError::ImmutableArgument { function, .. } if function == "clear" || function == "inner"),
+            "{} -> {:?}",
+            body,
+            e
+        );
+    }
+
+    // 診断には引数と宣言の両方が表示される
+    let code = "struct P { x: i32 }\nfn set(p: P) {\np.x = 1;\n}\npub_fn f() {\nlet p = P { x: 0 };\nset(p);\n}";
+    let rendered = compile(code).unwrap_err().to_diagnostic().render(code);
+    println!("{}", rendered);
+    assert!(rendered.contains("cannot pass immutable variable `p` to `set`, which modifies it"));
+    assert!(rendered.contains("declared as immutable here"));
+    assert!(rendered.contains("let_mut p"));
+}
+
+#[test]
+fn mutability_test07() -> anyhow::Result<()> {
+    // 初期値を持たない`let`の変数には、全ての経路で一度だけ代入できる
+    let code = "
+    pub_fn f(c: i32) -> i32 {
+        let a;
+        a = 1;
+        let b: i32;
+        if (c > 0) { b = 10; } else { b = 20; };
+        return a + b;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[Value::I32(1)])?;
+    assert_eq!(result[0], Value::I32(11));
+    let result = run_module(&module_wat, "f", &[Value::I32(0)])?;
+    assert_eq!(result[0], Value::I32(21));
+
+    // 代入されている可能性がある変数には代入できない
+    for (body, line) in [
+        ("let b;\nb = 1;\nb = 2;", 4),
+        ("let b;\nif (c > 0) { b = 1; };\nb = 2;", 4),
+        ("let b;\nwhile (c > 0) { b = 1; c -= 1; };", 3),
+    ] {
+        let code = format!("pub_fn f(c: i32) {{\n{}\n}}", body);
+        let Err(GenerateError::AssignToImmutable {
+            name,
+            span,
+            declared,
+        }) = compile(&code)
+        else {
+            panic!("{}", body);
+        };
+        assert_eq!(name, "b");
+        assert_eq!((span.line, declared.line), (line, 2), "{}", body);
+    }
+
+    // フィールドへの代入は再代入可能な変数でなければならない
+    let code = "struct P { x: i32 }\npub_fn f() {\nlet p: P;\np.x = 1;\n}";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::AssignToImmutablePlace { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn mutability_test08() {
+    // 再代入できない変数のフィールドや要素への代入
+    for (assignment, place) in [("v.x = 2.0;", "field"), ("a[0] = 2;", "element")] {
+        let code = format!(
+            "struct V {{ x: f64 }}\npub_fn f() {{\nlet v = V {{ x: 1.0 }};\nlet a = [1, 2];\n{}\n}}",
+            assignment
+        );
+        let e = compile(&code).unwrap_err();
+        let GenerateError::AssignToImmutablePlace {
+            name,
+            place: found,
+            span,
+            ..
+        } = &e
+        else {
+            panic!("{} -> {:?}", assignment, e);
+        };
+        assert_eq!((name.as_str(), *found), (&assignment[..1], place));
+        assert_eq!(span.line, 5);
+        let rendered = e.to_diagnostic().render(&code);
+        println!("{}", rendered);
+        assert!(rendered.contains(&format!(
+            "cannot assign to {} of immutable binding `{}`",
+            place,
+            &assignment[..1]
+        )));
+        assert!(rendered.contains("declared as immutable here"));
+        assert!(rendered.contains(&format!(
+            "consider making it mutable: `let_mut {}`",
+            &assignment[..1]
+        )));
+    }
+}
@@ -0,0 +1,122 @@
+// 未対応の構文のテスト
+// 生成に対応していない構文を渡したときにpanicせずにエラーを返却するかチェックする
+
+extern crate lichen_lang;
+use lichen_lang::abs::ast::*;
+use lichen_lang::abs::gen::Wasm_gen;
+use lichen_lang::abs::span::Span;
+use lichen_lang::errors::generate_errors::GenerateError;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+use lichen_lang::token::string::StringBranch;
+use lichen_lang::token::syntax::SyntaxBranch;
+use lichen_lang::token::syntax_box::SyntaxBoxBranch;
+use lichen_lang::token::unknown::UnKnownBranch;
+
+/// 文を解析してwasmを生成する
+fn gen_stmts(code: &str) -> Result<String, GenerateError> {
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve().unwrap();
+    let mut wasm_text_format = String::new();
+    for inner in s_parser.code_list {
+        match inner {
+            StmtElem::ExprElem(expr_b) => wasm_text_format.push_str(&expr_b.generate_wasm()?),
+            StmtElem::Special(control_b) => wasm_text_format.push_str(&control_b.generate_wasm()?),
+            _ => {}
+        }
+    }
+    Ok(wasm_text_format)
+}
+
+#[test]
+fn no_panic_test00() {
+    // 条件式が不正な場合
+    let test_cases = [
+        "if {a = 1;};",
+        "if (1) {a = 1;} elif {a = 2;};",
+        "while {a = 1;};",
+        "if (a) {a = 1;};",
+        "if (1 < a) {a = 1;} elif (b) {a = 2;};",
+        "while (a) {a = 1;};",
+        "if (1 < a) {a = 1;} else (b) {a = 2;};",
+    ];
+    for code in test_cases {
+        let r = gen_stmts(code);
+        assert!(
+            matches!(
+                r,
+                Err(GenerateError::EmptyCondition(_) | GenerateError::InvalidCondition(_))
+            ),
+            "{} -> {:?}",
+            code,
+            r
+        );
+    }
+}
+
+#[test]
+fn no_panic_test01() {
+    // 生成に対応していない文や構文の場合
+    let test_cases = [
+        ("assert a;", "UnsupportedStatement"),
+        ("while (a < 1) { assert a; };", "UnsupportedStatement"),
+        ("for (a) {a = 1;};", "InvalidForLoop"),
+        ("a[0] = 1;", "UnsupportedListAccess"),
+        ("b = a[0];", "UnsupportedListAccess"),
+        ("f(a)[0] = 1;", "UnsupportedListAccess"),
+        ("a[0] += 1;", "UnsupportedListAccess"),
+        ("= 1;", "InvalidleftPattern"),
+    ];
+    for (code, expected) in test_cases {
+        let r = gen_stmts(code);
+        let Err(e) = &r else {
+            panic!("{} -> {:?}", code, r);
+        };
+        assert!(
+            format!("{:?}", e).starts_with(expected),
+            "{} -> {:?}",
+            code,
+            e
+        );
+    }
+}
+
+#[test]
+fn no_panic_test02() {
+    // 文として生成できない要素がブロック内にある場合
+    let string_b = StmtElem::StringElem(StringBranch {
+        contents: String::from("abc"),
+        depth: 1,
+        loopdepth: 0,
+        span: Span::new(8, 13, 1, 9),
+    });
+    let syntax_box = SyntaxBoxBranch {
+        name: String::from("if"),
+        contents: vec![SyntaxBranch {
+            name: String::from("else"),
+            expr: Vec::new(),
+            contents: vec![string_b],
+            depth: 0,
+            loopdepth: 0,
+            span: Span::new(0, 15, 1, 1),
+            iter_array: None,
+        }],
+        depth: 0,
+        loopdepth: 0,
+        span: Span::new(0, 15, 1, 1),
+    };
+    assert!(matches!(
+        syntax_box.generate_wasm(),
+        Err(GenerateError::UnsupportedStatement(span)) if span == Span::new(8, 13, 1, 9)
+    ));
+}
+
+#[test]
+fn no_panic_test03() {
+    // 型の要素の解決
+    let mut type_elem = TypeElem::UnKnownElem(UnKnownBranch {
+        contents: 'a',
+        span: Span::new(0, 1, 1, 1),
+    });
+    assert!(type_elem.resolve_self().is_ok());
+}
@@ -0,0 +1,110 @@
+// 数値リテラルのテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::errors::generate_errors::GenerateError;
+use lichen_lang::errors::parser_errors::ParserError;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+use wasmer::Value;
+
+#[test]
+fn num_literal_test00() -> anyhow::Result<()> {
+    // 基数の接頭辞、区切り文字、指数、型接尾辞
+    let code = "
+    pub_fn ints() -> i32 {
+        return 0x1F + 0o17 + 0b1010 + 1_000_000;
+    }
+    pub_fn exp() -> f64 {
+        return 1.5e-3 + 2E+2;
+    }
+    pub_fn suffix() -> i64 {
+        let a = 10i64;
+        return a * 3;
+    }
+    pub_fn half() -> f32 {
+        return 2.0f32 / 4.0;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i32.const 31\n"));
+    assert!(module_wat.contains("(local $a i64)"));
+    assert!(module_wat.contains("i64.const 3\n"));
+    assert!(module_wat.contains("f32.const 2.0\n"));
+
+    let result = run_module(&module_wat, "ints", &[])?;
+    assert_eq!(result[0], Value::I32(31 + 15 + 10 + 1_000_000));
+    let result = run_module(&module_wat, "exp", &[])?;
+    assert_eq!(result[0], Value::F64(1.5e-3 + 2e2));
+    let result = run_module(&module_wat, "suffix", &[])?;
+    assert_eq!(result[0], Value::I64(30));
+    let result = run_module(&module_wat, "half", &[])?;
+    assert_eq!(result[0], Value::F32(0.5));
+    Ok(())
+}
+
+#[test]
+fn num_literal_test01() {
+    // 不正な数値リテラル
+    for literal in [
+        "0x", "0x_", "0b102", "1.5e", "1.5i32", "1.5u64", "0x1G", "12abc",
+    ] {
+        let code = format!("fn f() {{ a = {}; }}", literal);
+        let mut s_parser = StmtParser::new(code, 0, 0);
+        let errors = s_parser.resolve_recovering();
+        assert!(
+            matches!(errors[..], [ParserError::InvalidNumLiteral(_)]),
+            "{}: {:?}",
+            literal,
+            errors
+        );
+    }
+}
+
+#[test]
+fn num_literal_test02() -> anyhow::Result<()> {
+    // 型の範囲に収まらない数値リテラル
+    let code = "
+    pub_fn f() {
+        let a: i32 = 3000000000;
+    }
+    ";
+    match compile(code) {
+        Err(GenerateError::LiteralOutOfRange {
+            literal,
+            target,
+            span,
+        }) => {
+            assert_eq!(literal, "3000000000");
+            assert_eq!(target, "i32");
+            assert_eq!(span.line, 3);
+        }
+        r => panic!("{:?}", r),
+    }
+    let code = "
+    pub_fn f() -> i64 {
+        return 9_223_372_036_854_775_808i64;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::LiteralOutOfRange { .. })
+    ));
+
+    // 前置の`-`がついた最小値は範囲に収まる
+    let code = "
+    pub_fn min32() -> i32 {
+        return -2147483648;
+    }
+    pub_fn min64() -> i64 {
+        return -9223372036854775808;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "min32", &[])?;
+    assert_eq!(result[0], Value::I32(i32::MIN));
+    let result = run_module(&module_wat, "min64", &[])?;
+    assert_eq!(result[0], Value::I64(i64::MIN));
+    Ok(())
+}
@@ -0,0 +1,164 @@
+// `**`のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::errors::generate_errors::GenerateError;
+
+use wasmer::{imports, Function, Instance, Module, Store, Value};
+
+#[test]
+fn pow_test00() -> anyhow::Result<()> {
+    // 整数のべき乗は補助関数で計算する
+    let code = "
+    pub_fn pow32(a: i32, b: i32) -> i32 {
+        return a ** b;
+    }
+    pub_fn pow64(a: i64, b: i64) -> i64 {
+        return a ** b;
+    }
+    pub_fn right(a: i32) -> i32 {
+        return 2 ** a ** 2;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("(func $__pow_i32"));
+    assert!(module_wat.contains("(func $__pow_i64"));
+    assert!(!module_wat.contains("import"));
+
+    let call = |name: &str, args: &[Value]| run_module(&module_wat, name, args);
+    assert_eq!(
+        call("pow32", &[Value::I32(3), Value::I32(4)])?[0],
+        Value::I32(81)
+    );
+    assert_eq!(
+        call("pow32", &[Value::I32(-2), Value::I32(0)])?[0],
+        Value::I32(1)
+    );
+    assert_eq!(
+        call("pow32", &[Value::I32(-2), Value::I32(31)])?[0],
+        Value::I32(i32::MIN)
+    );
+    assert_eq!(
+        call("pow64", &[Value::I64(10), Value::I64(18)])?[0],
+        Value::I64(10i64.pow(18))
+    );
+    // 右結合 2 ** (a ** 2)
+    assert_eq!(call("right", &[Value::I32(3)])?[0], Value::I32(512));
+    Ok(())
+}
+
+#[test]
+fn pow_test01() -> anyhow::Result<()> {
+    // 両辺が数値リテラルの場合はコンパイル時に計算する
+    let code = "
+    pub_fn f() -> i32 {
+        return 2 ** 10;
+    }
+    pub_fn g() -> f64 {
+        return 2.0 ** 0.5;
+    }
+    pub_fn h() -> i64 {
+        return 3i64 ** 39;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i32.const 1024\n"));
+    assert!(!module_wat.contains("__pow_"));
+
+    assert_eq!(run_module(&module_wat, "f", &[])?[0], Value::I32(1024));
+    assert_eq!(
+        run_module(&module_wat, "g", &[])?[0],
+        Value::F64(2f64.powf(0.5))
+    );
+    assert_eq!(
+        run_module(&module_wat, "h", &[])?[0],
+        Value::I64(3i64.pow(39))
+    );
+    Ok(())
+}
+
+#[test]
+fn pow_test02() -> anyhow::Result<()> {
+    // 浮動小数点数のべき乗は実行環境の`env.pow`を呼び出す
+    let code = "
+    pub_fn pow64(a: f64, b: f64) -> f64 {
+        return a ** b;
+    }
+    pub_fn pow32(a: f32, b: f32) -> f32 {
+        return a ** b;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("(import \"env\" \"pow\" (func $__pow_f64"));
+
+    let mut store = Store::default();
+    let module = Module::new(&store, &module_wat)?;
+    let pow = Function::new_typed(&mut store, |a: f64, b: f64| a.powf(b));
+    let import_object = imports! {
+        "env" => {
+            "pow" => pow,
+        }
+    };
+    let instance = Instance::new(&mut store, &module, &import_object)?;
+    let pow64 = instance.exports.get_function("pow64")?;
+    let result = pow64.call(&mut store, &[Value::F64(2.0), Value::F64(0.5)])?;
+    assert_eq!(result[0], Value::F64(2f64.powf(0.5)));
+    let pow32 = instance.exports.get_function("pow32")?;
+    let result = pow32.call(&mut store, &[Value::F32(1.5), Value::F32(2.0)])?;
+    assert_eq!(result[0], Value::F32(2.25));
+    Ok(())
+}
+
+#[test]
+fn pow_test03() -> anyhow::Result<()> {
+    // 入れ子の`**`もコンパイル時に計算する
+    let code = "
+    pub_fn f() -> i32 {
+        return 2 ** 3 ** 2;
+    }
+    pub_fn g() -> i32 {
+        return (-2) ** 31;
+    }
+    pub_fn h() -> i64 {
+        return 2 ** 31;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i32.const 512\n"));
+    assert!(module_wat.contains("i64.const 2147483648\n"));
+    assert!(!module_wat.contains("__pow_"));
+    assert_eq!(run_module(&module_wat, "f", &[])?[0], Value::I32(512));
+    assert_eq!(run_module(&module_wat, "g", &[])?[0], Value::I32(i32::MIN));
+
+    // 計算した値が型の範囲に収まらない場合は数値リテラルと同様にエラーになる
+    for (code, literal, target) in [
+        ("pub_fn f() -> i32 {\nreturn 2 ** 31;\n}", "2 ** 31", "i32"),
+        (
+            "pub_fn f() -> i32 {\nreturn 3 ** 3 ** 3;\n}",
+            "3 ** 27",
+            "i32",
+        ),
+        (
+            "pub_fn f() -> u32 {\nreturn 2u32 ** 32;\n}",
+            "2 ** 32",
+            "u32",
+        ),
+        (
+            "pub_fn f() -> f64 {\nreturn 10.0 ** 400.0;\n}",
+            "10.0 ** 400.0",
+            "f64",
+        ),
+    ] {
+        match compile(code) {
+            Err(GenerateError::LiteralOutOfRange {
+                literal: l,
+                target: t,
+                span,
+            }) => {
+                assert_eq!((&*l, &*t, span.line), (literal, target, 2), "{}", code);
+            }
+            r => panic!("{} -> {:?}", code, r),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,99 @@
+// 回復モードのテスト
+// 一つのソースコードから複数のエラーが報告され、部分的な木が構築されるかチェックする
+
+extern crate lichen_lang;
+use lichen_lang::abs::ast::*;
+use lichen_lang::errors::parser_errors::ParserError;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+#[test]
+fn recovery_test00() {
+    // ブロック内の文ごとにエラーを報告する
+    let code = "fn main() {
+    a = (1 + 2));
+    elif (b) { c = 2; };
+    d = 3;
+}
+fn f() {
+    e = [1, 2;
+}";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    let errors = s_parser.resolve_recovering();
+    let lines: Vec<usize> = errors.iter().map(|e| e.get_span().line).collect();
+    assert_eq!(lines, vec![2, 3, 7]);
+    assert!(matches!(errors[0], ParserError::BraceNotOpened(_)));
+    assert!(matches!(errors[1], ParserError::GroupingSyntaxBoxError(_)));
+    assert!(matches!(errors[2], ParserError::BraceNotClosed { .. }));
+
+    // エラーの後ろの文も解析されている
+    let StmtElem::DecFuncElem(dec_func) = &s_parser.code_list[0] else {
+        panic!("DecFuncElem expected");
+    };
+    assert_eq!(dec_func.contents.len(), 3);
+    let StmtElem::ExprElem(stmt_b) = &dec_func.contents[1] else {
+        panic!("ExprElem expected");
+    };
+    assert!(matches!(stmt_b.code_list[0], ExprElem::ErrorElem(_)));
+    let StmtElem::ExprElem(stmt_b) = &dec_func.contents[2] else {
+        panic!("ExprElem expected");
+    };
+    assert!(matches!(stmt_b.code_list[0], ExprElem::FuncElem(_)));
+
+    // 回復モードでなければ最初のエラーのみを返却する
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    assert!(matches!(
+        s_parser.resolve(),
+        Err(ParserError::BraceNotOpened(_))
+    ));
+}
+
+#[test]
+fn recovery_test01() {
+    // 文字列やコメントが閉じられていない場合は終端までを一つの要素とする
+    let test_cases = [
+        ("a = 1;\nb = \"abc;\n", 2),
+        ("a = 1;\n/* comment\nb = 2;\n", 2),
+        ("a = (1;\nb = 2 /", 1),
+    ];
+    for (code, line) in test_cases {
+        let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+        let errors = s_parser.resolve_recovering();
+        assert_eq!(errors.len(), 1, "{}", code);
+        assert_eq!(errors[0].get_span().line, line, "{}", code);
+        assert!(matches!(s_parser.code_list[0], StmtElem::ExprElem(_)));
+    }
+}
+
+#[test]
+fn recovery_test02() {
+    // エラーがない場合は通常の解析と同じ木になる
+    let code = "
+        let a = 1 + 2;
+        if (a < 3) {
+            a = f(a, [1, 2]);
+        };
+        ";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve().unwrap();
+    let mut r_parser = StmtParser::new(code.to_string(), 0, 0);
+    assert!(r_parser.resolve_recovering().is_empty());
+
+    let show =
+        |list: &[StmtElem]| -> String { list.iter().map(|i| i.get_show_as_string()).collect() };
+    assert_eq!(show(&s_parser.code_list), show(&r_parser.code_list));
+}
+
+#[test]
+fn recovery_test03() {
+    // 括弧の中に回復で生成したエラーの要素があっても元のエラーを報告する
+    let code = "fn f() {\n    if (a) { b = 1; }};\n    c = (2;\n}\n";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    let errors = s_parser.resolve_recovering();
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(matches!(
+        errors[0],
+        ParserError::BraceNotClosed { open, close: ')', .. } if open.line == 3 && open.column == 9
+    ));
+    assert!(matches!(errors[1], ParserError::BraceNotOpened(span) if span.line == 4));
+}
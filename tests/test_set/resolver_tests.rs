@@ -0,0 +1,143 @@
+// 名前解決のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::errors::generate_errors::GenerateError;
+
+use wasmer::Value;
+
+#[test]
+fn resolver_test00() -> anyhow::Result<()> {
+    // 引数、ローカル変数、関数、`__mem`の参照
+    let code = "
+    fn double(x: i32) -> i32 {
+        return x * 2;
+    }
+    pub_fn f(n: i32) -> i32 {
+        fn inc(x: i32) -> i32 {
+            return double(x) + 1;
+        }
+        let_mut total = inc(n);
+        __mem[0] = total;
+        total += __mem[0];
+        return total;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[Value::I32(3)])?;
+    assert_eq!(result[0], Value::I32(14));
+    Ok(())
+}
+
+#[test]
+fn resolver_test01() {
+    // 宣言されていない変数の参照
+    let code = "
+    pub_fn f(count: i32) -> i32 {
+        let total = 0;
+        return totl + cont;
+    }
+    ";
+    let e = compile(code).unwrap_err();
+    let GenerateError::UndefinedVariable {
+        name, suggestion, ..
+    } = &e
+    else {
+        panic!("{:?}", e);
+    };
+    assert_eq!(name, "totl");
+    assert_eq!(suggestion.as_deref(), Some("total"));
+    assert_eq!(e.get_span().line, 4);
+
+    // 内側のスコープの変数は外側から参照できない
+    let code = "
+    pub_fn f() -> i32 {
+        if (1 > 0) {
+            let inner = 1;
+        };
+        return inner;
+    }
+    ";
+    let e = compile(code).unwrap_err();
+    assert!(matches!(
+        e,
+        GenerateError::UndefinedVariable {
+            suggestion: None,
+            ..
+        }
+    ));
+
+    // 似た名前がない場合
+    let code = "pub_fn f(a: i32) -> i32 {\nreturn something;\n}";
+    let e = compile(code).unwrap_err();
+    let rendered = e.to_diagnostic().render(code);
+    assert!(rendered.contains("cannot find variable `something` in this scope"));
+    assert!(!rendered.contains("did you mean"));
+
+    // 1文字の名前には無関係な1文字の名前を提案しない
+    let code = "pub_fn f(a: i32) {\ny = 2;\n}";
+    let e = compile(code).unwrap_err();
+    assert!(
+        matches!(
+            &e,
+            GenerateError::UndefinedVariable {
+                suggestion: None,
+                ..
+            }
+        ),
+        "{:?}",
+        e
+    );
+}
+
+#[test]
+fn resolver_test02() {
+    // 宣言されていない関数の呼び出し
+    let code =
+        "fn double(x: i32) -> i32 {\nreturn x * 2;\n}\npub_fn f() -> i32 {\nreturn doubel(1);\n}";
+    let e = compile(code).unwrap_err();
+    assert!(matches!(
+        &e,
+        GenerateError::UndefinedFunction { name, suggestion: Some(s), .. } if name == "doubel" && s == "double"
+    ));
+    let rendered = e.to_diagnostic().render(code);
+    println!("{}", rendered);
+    assert!(rendered.contains("cannot find function `doubel` in this scope"));
+    assert!(rendered.contains("did you mean `double`?"));
+
+    // 他の関数の中で宣言された関数は呼び出せない
+    let code = "
+    fn g() {
+        fn helper() {}
+    }
+    pub_fn f() {
+        helper();
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::UndefinedFunction { .. })
+    ));
+}
+
+#[test]
+fn resolver_test03() {
+    // 隣り合う2文字の入れ替えは1回の編集として候補にする
+    for (typo, expected) in [
+        ("cuont", Some("count")),
+        ("coutn", Some("count")),
+        ("ocunt", Some("count")),
+        ("cnout", None),
+    ] {
+        let code = format!("pub_fn f(count: i32) -> i32 {{\nreturn {};\n}}", typo);
+        let e = compile(&code).unwrap_err();
+        let GenerateError::UndefinedVariable {
+            name, suggestion, ..
+        } = &e
+        else {
+            panic!("{:?}", e);
+        };
+        assert_eq!(name, typo);
+        assert_eq!(suggestion.as_deref(), expected, "{}", typo);
+    }
+}
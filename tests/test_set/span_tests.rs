@@ -0,0 +1,144 @@
+// ソース上の位置(span)のテスト
+// 解析後の各要素が元のソースコードの位置を保持しているかチェックする
+
+extern crate lichen_lang;
+use lichen_lang::abs::ast::*;
+use lichen_lang::abs::span::Span;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::expr_parser::ExprParser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+/// spanが指すソースの文字列を返却する
+fn slice(code: &str, span: Span) -> &str {
+    &code[span.start..span.end]
+}
+
+#[test]
+fn span_test00() {
+    // 一文字ずつのtokenに位置が付与される
+    let code = "ab\n c";
+    let e_parser = ExprParser::new(code.to_string(), 0, 0);
+    let spans: Vec<Span> = e_parser.code_list.iter().map(|e| e.get_span()).collect();
+    assert_eq!(spans[0], Span::new(0, 1, 1, 1));
+    assert_eq!(spans[1], Span::new(1, 2, 1, 2));
+    assert_eq!(spans[2], Span::new(2, 3, 1, 3));
+    assert_eq!(spans[3], Span::new(3, 4, 2, 1));
+    assert_eq!(spans[4], Span::new(4, 5, 2, 2));
+}
+
+#[test]
+fn span_test01() {
+    // 演算子の解決後も位置が保持される
+    let code = "abc + 12*x";
+    let mut e_parser = ExprParser::new(code.to_string(), 0, 0);
+    e_parser.resolve().unwrap();
+    assert_eq!(e_parser.code_list.len(), 1);
+
+    let ExprElem::FuncElem(func_b) = &e_parser.code_list[0] else {
+        panic!("FuncElem expected");
+    };
+    assert_eq!(slice(code, func_b.span), "abc + 12*x");
+    assert_eq!(slice(code, func_b.name.get_span()), "+");
+    assert_eq!(slice(code, func_b.contents[0].get_span()), "abc");
+    assert_eq!(slice(code, func_b.contents[1].get_span()), "12*x");
+
+    let ExprElem::ItemElem(item_b) = &func_b.contents[1] else {
+        panic!("ItemElem expected");
+    };
+    let ExprElem::FuncElem(mul_b) = &item_b.contents[0] else {
+        panic!("FuncElem expected");
+    };
+    assert_eq!(slice(code, mul_b.name.get_span()), "*");
+    assert_eq!(mul_b.contents[1].get_span(), Span::new(9, 10, 1, 10));
+}
+
+#[test]
+fn span_test02() {
+    // 前置演算子の空のItemは演算子の位置を指す
+    let code = "-a";
+    let mut e_parser = ExprParser::new(code.to_string(), 0, 0);
+    e_parser.resolve().unwrap();
+    let ExprElem::FuncElem(func_b) = &e_parser.code_list[0] else {
+        panic!("FuncElem expected");
+    };
+    assert_eq!(func_b.contents[0].get_span(), Span::new(0, 0, 1, 1));
+    assert_eq!(slice(code, func_b.contents[1].get_span()), "a");
+}
+
+#[test]
+fn span_test03() {
+    // 文、関数呼び出しの引数、文字列、コメントの位置
+    let code = "a = 1; // first\n  b = foo(x, \"hi\");\nreturn a;";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve().unwrap();
+
+    let stmts: Vec<&StmtElem> = s_parser.code_list.iter().collect();
+    assert_eq!(slice(code, stmts[0].get_span()), "a = 1");
+    assert_eq!(slice(code, stmts[1].get_span()), "// first");
+    assert_eq!(slice(code, stmts[2].get_span()), "b = foo(x, \"hi\")");
+    assert_eq!(stmts[2].get_span().line, 2);
+    assert_eq!(stmts[2].get_span().column, 3);
+    assert_eq!(slice(code, stmts[3].get_span()), "return a");
+    assert_eq!(stmts[3].get_span().line, 3);
+
+    let StmtElem::ExprElem(expr_b) = stmts[2] else {
+        panic!("ExprElem expected");
+    };
+    let ExprElem::FuncElem(assign_b) = &expr_b.code_list[0] else {
+        panic!("FuncElem expected");
+    };
+    let ExprElem::ItemElem(item_b) = &assign_b.contents[1] else {
+        panic!("ItemElem expected");
+    };
+    let ExprElem::FuncElem(call_b) = &item_b.contents[0] else {
+        panic!("FuncElem expected");
+    };
+    assert_eq!(slice(code, call_b.span), "foo(x, \"hi\")");
+    assert_eq!(slice(code, call_b.name.get_span()), "foo");
+    assert_eq!(slice(code, call_b.contents[0].get_span()), "x");
+    assert_eq!(slice(code, call_b.contents[1].get_span()), "\"hi\"");
+}
+
+#[test]
+fn span_test04() {
+    // 入れ子のブロックの中でも位置が保持される
+    let code = "while (i < 10) {\n    if (i == 5) {\n        break;\n    };\n    i += 1;\n};";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve().unwrap();
+
+    let StmtElem::ExprElem(expr_b) = &s_parser.code_list[0] else {
+        panic!("ExprElem expected");
+    };
+    let ExprElem::SyntaxBoxElem(while_b) = &expr_b.code_list[0] else {
+        panic!("SyntaxBoxElem expected");
+    };
+    assert_eq!(while_b.span.start, 0);
+    assert_eq!(while_b.span.end, code.len() - 1);
+    assert_eq!(
+        slice(code, while_b.contents[0].expr[0].get_span()),
+        "i < 10"
+    );
+
+    let StmtElem::ExprElem(inner_if) = &while_b.contents[0].contents[0] else {
+        panic!("ExprElem expected");
+    };
+    assert_eq!(inner_if.span.line, 2);
+    assert_eq!(inner_if.span.column, 5);
+    let StmtElem::Special(break_b) = &match &inner_if.code_list[0] {
+        ExprElem::SyntaxBoxElem(b) => b,
+        _ => panic!("SyntaxBoxElem expected"),
+    }
+    .contents[0]
+        .contents[0]
+    else {
+        panic!("Special expected");
+    };
+    assert_eq!(slice(code, break_b.span), "break");
+    assert_eq!(break_b.span.line, 3);
+
+    let StmtElem::ExprElem(add_b) = &while_b.contents[0].contents[1] else {
+        panic!("ExprElem expected");
+    };
+    assert_eq!(slice(code, add_b.span), "i += 1");
+    assert_eq!(add_b.span.line, 5);
+}
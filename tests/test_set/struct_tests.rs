@@ -0,0 +1,322 @@
+// 構造体のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::abs::ast::*;
+use lichen_lang::errors::generate_errors::GenerateError;
+use lichen_lang::errors::parser_errors::ParserError;
+use lichen_lang::parser::core_parser::Parser;
+use lichen_lang::parser::stmt_parser::StmtParser;
+
+use wasmer::Value;
+
+#[test]
+fn struct_test00() {
+    // 構造体宣言、リテラル、フィールドへのアクセスの解析
+    let code = "
+    struct Point { x: f64, y: f64 }
+    fn f() {
+        let p = Point { x: 1.0, y: 2.0 + 1.0 };
+        p.x = p.y;
+    }
+    ";
+    let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+    s_parser.resolve().unwrap();
+    let StmtElem::DecStructElem(dec_struct) = &s_parser.code_list[0] else {
+        panic!("DecStructElem expected");
+    };
+    assert_eq!(dec_struct.name, "Point");
+    let names: Vec<&str> = dec_struct.fields.iter().map(|f| &*f.name).collect();
+    assert_eq!(names, ["x", "y"]);
+
+    let StmtElem::DecFuncElem(dec_func) = &s_parser.code_list[1] else {
+        panic!("DecFuncElem expected");
+    };
+    let show: Vec<String> = dec_func
+        .contents
+        .iter()
+        .map(|stmt| stmt.get_show_as_string())
+        .collect();
+    assert!(show[0].contains("Point { x: "), "{}", show[0]);
+    assert!(show[1].contains("\"p\".x"), "{}", show[1]);
+
+    // 不正な宣言とリテラル
+    for (code, expected) in [
+        ("struct { x: f64 }", "DecStructError"),
+        ("struct P { x f64 }", "DecStructError"),
+        ("fn f() { let p = P { 1.0 }; }", "StructLiteralError"),
+        ("fn f() { let p = P { x: }; }", "StructLiteralError"),
+    ] {
+        let mut s_parser = StmtParser::new(code.to_string(), 0, 0);
+        let r = s_parser.resolve();
+        let Err(e) = &r else {
+            panic!("{} -> {:?}", code, r);
+        };
+        assert!(
+            format!("{:?}", e).starts_with(expected),
+            "{} -> {:?}",
+            code,
+            e
+        );
+    }
+    let mut s_parser = StmtParser::new("struct 1 { }".to_string(), 0, 0);
+    assert!(matches!(
+        s_parser.resolve(),
+        Err(ParserError::DecStructError(_))
+    ));
+}
+
+#[test]
+fn struct_test01() -> anyhow::Result<()> {
+    // フィールドは宣言の順に、それぞれの大きさに揃えて配置される
+    let code = "
+    struct A { a: i32, b: f64, c: i32 }
+    pub_fn f(x: i32) -> i32 {
+        let s = A { c: x * 3, b: 2.5, a: x };
+        return s.a + s.c;
+    }
+    pub_fn g() -> f64 {
+        let s = A { a: 1, b: 2.5, c: 3 };
+        return s.b;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i32.const 24\ncall $__alloc\n"));
+    assert!(module_wat.contains("f64.store offset=8\n"));
+    assert!(module_wat.contains("i32.store offset=16\n"));
+    assert!(module_wat.contains("f64.load offset=8\n"));
+
+    let result = run_module(&module_wat, "f", &[Value::I32(5)])?;
+    assert_eq!(result[0], Value::I32(20));
+    let result = run_module(&module_wat, "g", &[])?;
+    assert_eq!(result[0], Value::F64(2.5));
+    Ok(())
+}
+
+#[test]
+fn struct_test02() -> anyhow::Result<()> {
+    // フィールドへの代入と複合代入
+    let code = "
+    struct Counter { count: i64, step: i64 }
+    pub_fn f(n: i32) -> i64 {
+        let_mut c = Counter { count: 0, step: 3 };
+        for (i in 0..n) {
+            c.count += c.step;
+        };
+        c.step = 100;
+        c.count = c.count + c.step;
+        return c.count;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[Value::I32(4)])?;
+    assert_eq!(result[0], Value::I64(112));
+    Ok(())
+}
+
+#[test]
+fn struct_test03() -> anyhow::Result<()> {
+    // 入れ子の構造体と、構造体を受け取る関数・返す関数
+    // 構造体は参照として渡される
+    let code = "
+    struct Point { x: f64, y: f64 }
+    struct Rect { min: Point, max: Point }
+    fn point(x: f64, y: f64) -> Point {
+        return Point { x: x, y: y };
+    }
+    fn area(r: Rect) -> f64 {
+        return (r.max.x - r.min.x) * (r.max.y - r.min.y);
+    }
+    fn grow(r: Rect, d: f64) {
+        r.max.x += d;
+        r.max.y += d;
+    }
+    pub_fn f(w: f64, h: f64) -> f64 {
+        let_mut r = Rect { min: point(1.0, 1.0), max: point(1.0 + w, 1.0 + h) };
+        grow(r, 1.0);
+        return area(r);
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[Value::F64(2.0), Value::F64(3.0)])?;
+    assert_eq!(result[0], Value::F64(12.0));
+    Ok(())
+}
+
+#[test]
+fn struct_test04() -> anyhow::Result<()> {
+    // 多数のレコードを確保するとメモリが追加される
+    let code = "
+    struct Big { a: f64, b: f64, c: f64, d: f64 }
+    pub_fn f(n: i32) -> f64 {
+        let_mut sum = 0.0;
+        for (i in 0..n) {
+            let b = Big { a: 1.0, b: 2.0, c: 3.0, d: 4.0 };
+            sum += b.d;
+        };
+        return sum;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[Value::I32(5000)])?;
+    assert_eq!(result[0], Value::F64(20000.0));
+    Ok(())
+}
+
+#[test]
+fn struct_test05() {
+    // 構造体を使用しない場合は補助関数を生成しない
+    let module_wat = compile("pub_fn f() -> i32 { return 1; }").unwrap();
+    assert!(!module_wat.contains("__alloc"));
+    assert!(!module_wat.contains("__heap"));
+}
+
+#[test]
+fn struct_test06() {
+    // 構造体に関するエラー
+    let header = "struct Point { x: f64, y: f64 }\n";
+    let test_cases = [
+        (
+            "fn f() { let p = Pont { x: 1.0, y: 2.0 }; }",
+            "UndefinedType",
+        ),
+        ("fn f(a: str) {}", "UndefinedType"),
+        (
+            "fn f() { let p = Point { x: 1.0, z: 2.0 }; }",
+            "UndefinedField",
+        ),
+        (
+            "fn f() -> f64 { let p = Point { x: 1.0, y: 2.0 }; return p.z; }",
+            "UndefinedField",
+        ),
+        ("fn f(a: i32) -> i32 { return a.x; }", "UndefinedField"),
+        ("fn f() { let p = Point { x: 1.0 }; }", "MissingFields"),
+        (
+            "fn f() { let p = Point { x: 1.0, x: 2.0, y: 3.0 }; }",
+            "DuplicateField",
+        ),
+        ("fn f() { let p = Point { x: 1, y: 2.0 }; }", "TypeMismatch"),
+        (
+            "fn f() { let p: i32 = Point { x: 1.0, y: 2.0 }; }",
+            "TypeMismatch",
+        ),
+        (
+            "fn f(p: Point, q: Point) { let r = p + q; }",
+            "InvalidOperandType",
+        ),
+        (
+            "fn f() { let p = Point { x: 1.0, y: 2.0 }; p.x = 1.0; }",
+            "AssignToImmutable",
+        ),
+    ];
+    for (code, expected) in test_cases {
+        let r = compile(&format!("{}{}", header, code));
+        let Err(e) = &r else {
+            panic!("{} -> {:?}", code, r);
+        };
+        assert!(
+            format!("{:?}", e).starts_with(expected),
+            "{} -> {:?}",
+            code,
+            e
+        );
+    }
+
+    // 宣言の誤り
+    for (code, expected) in [
+        ("struct P { x: f64, x: i32 }", "DuplicateField"),
+        (
+            "struct P { x: f64 }\nstruct P { y: f64 }",
+            "DuplicateDefinition",
+        ),
+        ("struct i32 { x: f64 }", "DuplicateDefinition"),
+        ("struct P { q: Q }", "UndefinedType"),
+    ] {
+        let r = compile(code);
+        assert!(
+            matches!(
+                (&r, expected),
+                (Err(GenerateError::DuplicateField { .. }), "DuplicateField")
+                    | (
+                        Err(GenerateError::DuplicateDefinition { .. }),
+                        "DuplicateDefinition"
+                    )
+                    | (Err(GenerateError::UndefinedType { .. }), "UndefinedType")
+            ),
+            "{} -> {:?}",
+            code,
+            r
+        );
+    }
+}
+
+#[test]
+fn struct_test07() -> anyhow::Result<()> {
+    // 構造体と配列の値は束縛・代入・格納・返却のたびに複製される
+    let code = "
+    struct Point { x: f64, y: f64 }
+    struct Line { a: Point, b: Point }
+    fn start(l: Line) -> Point {
+        return l.a;
+    }
+    pub_fn f() -> f64 {
+        let p = Point { x: 1.0, y: 2.0 };
+        let_mut q = p;
+        q.x = 10.0;
+        let_mut l = Line { a: p, b: q };
+        l.a.y = 20.0;
+        let_mut s = start(l);
+        s.x = 30.0;
+        q = l.b;
+        q.y = 40.0;
+        return p.x + p.y + l.a.x + l.a.y + l.b.y + q.x;
+    }
+    pub_fn g() -> i32 {
+        let a = [[1, 2], [3, 4]];
+        let_mut b = a;
+        b[0][1] = 20;
+        let_mut rows = [a[1]; 2];
+        rows[0][0] = 30;
+        return a[0][1] + a[1][0] * 10 + b[0][1] * 100 + rows[1][0] * 1000;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("(func $__copy.Point"));
+    assert!(module_wat.contains("(func $__copy.2x2xi32"));
+    assert!(module_wat.contains("(func $__copy.2xi32"));
+    let result = run_module(&module_wat, "f", &[])?;
+    assert_eq!(result[0], Value::F64(1.0 + 2.0 + 1.0 + 20.0 + 2.0 + 10.0));
+    let result = run_module(&module_wat, "g", &[])?;
+    assert_eq!(result[0], Value::I32(2 + 30 + 2000 + 3000));
+    Ok(())
+}
+
+#[test]
+fn struct_test08() -> anyhow::Result<()> {
+    // 関数の返り値や括弧で囲んだ式のフィールドを参照できる
+    let code = "
+    struct Point { x: f64, y: f64 }
+    struct Line { a: Point, b: Point }
+    fn mk(x: f64) -> Line {
+        return Line { a: Point { x: x, y: 2.0 }, b: Point { x: 3.0, y: 4.0 } };
+    }
+    pub_fn f() -> f64 {
+        let l = mk(1.0);
+        let ls = [l; 2];
+        return mk(10.0).a.x + (l).b.y + (l.a).y * 100.0 + mk(1.0).b.x * 1000.0 + (ls[1]).a.x;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[])?;
+    assert_eq!(result[0], Value::F64(10.0 + 4.0 + 200.0 + 3000.0 + 1.0));
+
+    // 構造体ではない式のフィールドは存在しない
+    let code = "fn g() -> i32 {\nreturn 1;\n}\npub_fn f() -> i32 {\nreturn g().a;\n}";
+    let e = compile(code).unwrap_err();
+    assert!(
+        matches!(&e, GenerateError::UndefinedField { ty, field, .. } if ty == "i32" && field == "a"),
+        "{:?}",
+        e
+    );
+    Ok(())
+}
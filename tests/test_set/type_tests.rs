@@ -0,0 +1,233 @@
+// 型検査のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::errors::generate_errors::GenerateError;
+use wasmer::Value;
+
+/// 型の不一致のエラーの(期待された型, 実際の型, 行)
+fn mismatch(code: &str) -> (String, String, usize) {
+    match compile(code) {
+        Err(GenerateError::TypeMismatch {
+            expected,
+            found,
+            span,
+        }) => (expected, found, span.line),
+        r => panic!("{:?}", r),
+    }
+}
+
+#[test]
+fn type_test00() {
+    // 型注釈、リテラル、関数のシグネチャからの推論
+    let code = "
+    fn to_i64(x: i64) -> i64 {
+        return x;
+    }
+    pub_fn f(a: i32, b: f64) -> f64 {
+        let c: i64 = 1;
+        let d = to_i64(c + 2);
+        let e = 2.5;
+        let_mut g;
+        g = d * 3;
+        if (a > 0) {
+            return e * b;
+        };
+        return b - 1.5;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("(local $c i64)"));
+    assert!(module_wat.contains("(local $d i64)"));
+    assert!(module_wat.contains("(local $e f64)"));
+    assert!(module_wat.contains("(local $g i64)"));
+}
+
+#[test]
+fn type_test01() {
+    // 異なる型同士の演算
+    let code = "
+    pub_fn f(a: i32, b: f64) {
+        let c = a + b;
+    }
+    ";
+    assert_eq!(mismatch(code), ("i32".into(), "f64".into(), 3));
+
+    // 型注釈と初期化式の型
+    let code = "
+    pub_fn f() {
+        let a: f32 = 1;
+    }
+    ";
+    assert_eq!(mismatch(code), ("f32".into(), "i32".into(), 3));
+
+    // 代入
+    let code = "
+    pub_fn f(a: i64) {
+        let_mut b = 1;
+        b = a;
+    }
+    ";
+    assert_eq!(mismatch(code), ("i32".into(), "i64".into(), 4));
+}
+
+#[test]
+fn type_test02() {
+    // 返り値の型
+    let code = "
+    pub_fn f(a: i64) -> i32 {
+        return a;
+    }
+    ";
+    assert_eq!(mismatch(code), ("i32".into(), "i64".into(), 3));
+
+    let code = "
+    pub_fn f() -> i32 {
+        return;
+    }
+    ";
+    assert_eq!(mismatch(code), ("i32".into(), "()".into(), 3));
+
+    // 引数の型と数
+    let code = "
+    fn g(x: f64) -> f64 {
+        return x;
+    }
+    pub_fn f(a: i32) -> f64 {
+        return g(a);
+    }
+    ";
+    assert_eq!(mismatch(code), ("f64".into(), "i32".into(), 6));
+    let code = "
+    fn g(x: f64) -> f64 {
+        return x;
+    }
+    pub_fn f() -> f64 {
+        return g(1.0, 2.0);
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::ArgumentCountMismatch {
+            expected: 1,
+            found: 2,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn type_test03() {
+    // 条件式は`i32`
+    let code = "
+    pub_fn f(a: f64) {
+        while (a) {
+            a -= 1.0;
+        };
+    }
+    ";
+    assert_eq!(mismatch(code), ("i32".into(), "f64".into(), 3));
+
+    // 浮動小数点数の剰余
+    let code = "pub_fn f(a: f64) -> f64 {\nreturn a % 2.0;\n}";
+    let e = compile(code).unwrap_err();
+    assert!(matches!(&e, GenerateError::InvalidOperandType { ope, .. } if ope == "%"));
+    let rendered = e.to_diagnostic().render(code);
+    println!("{}", rendered);
+    assert!(rendered.contains("cannot apply `%` to `f64`"));
+}
+
+#[test]
+fn type_test04() {
+    // 型注釈がなく、まだ値が代入されていない変数の参照
+    let code = "
+    pub_fn f() {
+        let_mut x;
+        let y = x + 1.0;
+        x = 2.0;
+    }
+    ";
+    let e = compile(code).unwrap_err();
+    assert!(
+        matches!(&e, GenerateError::UsedBeforeAssignment { name, span, .. } if name == "x" && span.line == 4),
+        "{:?}",
+        e
+    );
+    let rendered = e.to_diagnostic().render(code);
+    assert!(rendered.contains("used binding `x` before assigning a value"));
+
+    // 自身を参照する最初の代入
+    let code = "pub_fn f() {\nlet_mut x;\nx = x + 1;\n}";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::UsedBeforeAssignment { .. })
+    ));
+
+    // 代入した後は参照できる
+    let code =
+        "pub_fn f() -> f64 {\nlet_mut x: f64;\nx = 1.0;\nlet_mut y;\ny = x + 1.0;\nreturn y;\n}";
+    compile(code).unwrap();
+}
+
+#[test]
+fn type_test05() -> anyhow::Result<()> {
+    // 型注釈の有無によらず、全ての経路で代入されていない変数は参照できない
+    let code = "pub_fn f() -> i32 {\nlet b: i32;\nreturn b;\n}";
+    let e = compile(code).unwrap_err();
+    let GenerateError::UsedBeforeAssignment {
+        name,
+        span,
+        declared,
+    } = &e
+    else {
+        panic!("{:?}", e);
+    };
+    assert_eq!(name, "b");
+    assert_eq!((span.line, declared.line), (3, 2));
+    let rendered = e.to_diagnostic().render(code);
+    println!("{}", rendered);
+    assert!(rendered.contains("`b` used here but it is not assigned on every path"));
+    assert!(rendered.contains("binding declared here but left uninitialized"));
+    assert!(!rendered.contains("type annotations needed"));
+
+    for body in [
+        "let_mut x: i32;\nif (c > 0) { x = 1; };",
+        "let_mut x: i32;\nwhile (c > 0) { x = 1; c -= 1; };",
+        "let_mut x: i32;\nfor (i in 0..c) { x = i; };",
+        "let_mut x: i32;\nloop { if (c > 0) { break; }; x = 1; break; };",
+        "let_mut x: i32;\nif (c > 0) { x = 1; } elif (c < 0) { x = 2; };",
+        "let_mut x: i32;\nlet y = c > 0 && (x = 1) == 0;",
+    ] {
+        let code = format!("pub_fn f(c: i32) -> i32 {{\n{}\nreturn x;\n}}", body);
+        let r = compile(&code);
+        assert!(
+            matches!(&r, Err(GenerateError::UsedBeforeAssignment { name, .. }) if name == "x"),
+            "{} -> {:?}",
+            body,
+            r
+        );
+    }
+
+    // 全ての経路で代入されている場合は参照できる
+    let code = "
+    enum Sign { Neg, Zero, Pos }
+    pub_fn f(c: i32) -> i32 {
+        let_mut a: i32;
+        if (c > 0) { a = 1; } elif (c < 0) { a = 2; } else { a = 3; };
+        let_mut b;
+        if (c > 100) { return 0; } else { b = 10; };
+        let_mut d: i32;
+        loop { if (c > 0) { d = 100; break; }; d = 200; break; };
+        let s = Pos;
+        let_mut e;
+        match (s) { Neg => { e = 1000; }, Zero => { e = 2000; }, Pos => { e = 3000; }, };
+        return a + b + d + e;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    let result = run_module(&module_wat, "f", &[Value::I32(5)])?;
+    assert_eq!(result[0], Value::I32(3111));
+    let result = run_module(&module_wat, "f", &[Value::I32(-5)])?;
+    assert_eq!(result[0], Value::I32(3212));
+    Ok(())
+}
@@ -0,0 +1,88 @@
+// 型に応じた命令の選択のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+
+use wasmer::Value;
+
+#[test]
+fn typed_gen_test00() -> anyhow::Result<()> {
+    // i64
+    let code = "
+    pub_fn fact(n: i64) -> i64 {
+        let_mut result: i64 = 1;
+        let_mut i = n;
+        while (i > 1) {
+            result *= i;
+            i -= 1;
+        };
+        return result;
+    }
+    pub_fn div(a: i64, b: i64) -> i64 {
+        return a / b + a % b;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i64.mul"));
+    assert!(module_wat.contains("i64.gt_s"));
+    assert!(module_wat.contains("i64.const 1"));
+    assert!(module_wat.contains("i64.div_s"));
+
+    let result = run_module(&module_wat, "fact", &[Value::I64(20)])?;
+    assert_eq!(result[0], Value::I64(2432902008176640000));
+    // -7 / 2 + -7 % 2
+    let result = run_module(&module_wat, "div", &[Value::I64(-7), Value::I64(2)])?;
+    assert_eq!(result[0], Value::I64(-4));
+    Ok(())
+}
+
+#[test]
+fn typed_gen_test01() -> anyhow::Result<()> {
+    // f64
+    let code = "
+    pub_fn average(a: f64, b: f64) -> f64 {
+        return (a + b) / 2.0;
+    }
+    pub_fn abs(a: f64) -> f64 {
+        if (a < 0.0) {
+            return -a;
+        };
+        return a;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("f64.div\n"));
+    assert!(module_wat.contains("f64.const 2.0"));
+    assert!(module_wat.contains("f64.lt\n"));
+
+    let result = run_module(&module_wat, "average", &[Value::F64(1.5), Value::F64(2.0)])?;
+    assert_eq!(result[0], Value::F64(1.75));
+    let result = run_module(&module_wat, "abs", &[Value::F64(-2.5)])?;
+    assert_eq!(result[0], Value::F64(2.5));
+    Ok(())
+}
+
+#[test]
+fn typed_gen_test02() -> anyhow::Result<()> {
+    // f32と整数の除算
+    let code = "
+    pub_fn scale(a: f32) -> f32 {
+        let_mut b: f32 = a * 1.5;
+        b /= 3.0;
+        return b;
+    }
+    pub_fn half(a: i32) -> i32 {
+        return a / 2;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("f32.mul"));
+    assert!(module_wat.contains("f32.div"));
+    assert!(module_wat.contains("i32.div_s"));
+
+    let result = run_module(&module_wat, "scale", &[Value::F32(4.0)])?;
+    assert_eq!(result[0], Value::F32(2.0));
+    let result = run_module(&module_wat, "half", &[Value::I32(-9)])?;
+    assert_eq!(result[0], Value::I32(-4));
+    Ok(())
+}
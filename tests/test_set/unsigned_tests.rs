@@ -0,0 +1,110 @@
+// 符号なし整数型のテスト
+
+extern crate lichen_lang;
+use super::module_tests::{compile, run_module};
+use lichen_lang::errors::generate_errors::GenerateError;
+
+use wasmer::Value;
+
+#[test]
+fn unsigned_test00() -> anyhow::Result<()> {
+    // 符号なしの除算、剰余、大小比較
+    let code = "
+    pub_fn div(a: u32, b: u32) -> u32 {
+        return a / b + a % b;
+    }
+    pub_fn less(a: u64, b: u64) -> i32 {
+        if (a < b) {
+            return 1;
+        };
+        return 0;
+    }
+    pub_fn max() -> u32 {
+        let a: u32 = 4294967295;
+        return a;
+    }
+    pub_fn big() -> u64 {
+        return 18_446_744_073_709_551_615u64 / 2;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("(param $a i32)"));
+    assert!(module_wat.contains("i32.div_u"));
+    assert!(module_wat.contains("i32.rem_u"));
+    assert!(module_wat.contains("i64.lt_u"));
+    assert!(module_wat.contains("i64.div_u"));
+
+    // 0xFFFFFFF0 / 7 + 0xFFFFFFF0 % 7
+    let result = run_module(&module_wat, "div", &[Value::I32(-16), Value::I32(7)])?;
+    assert_eq!(result[0], Value::I32(613566756));
+    // u64::MAX < 1 は偽
+    let result = run_module(&module_wat, "less", &[Value::I64(-1), Value::I64(1)])?;
+    assert_eq!(result[0], Value::I32(0));
+    let result = run_module(&module_wat, "max", &[])?;
+    assert_eq!(result[0], Value::I32(-1));
+    let result = run_module(&module_wat, "big", &[])?;
+    assert_eq!(result[0], Value::I64((u64::MAX / 2) as i64));
+    Ok(())
+}
+
+#[test]
+fn unsigned_test01() -> anyhow::Result<()> {
+    // 符号なし整数の型変換
+    let code = "
+    pub_fn widen(a: u32) -> i64 {
+        return a as i64;
+    }
+    pub_fn to_float(a: u32) -> f64 {
+        return a as f64;
+    }
+    pub_fn from_float(a: f64) -> u32 {
+        return a as u32;
+    }
+    ";
+    let module_wat = compile(code).unwrap();
+    assert!(module_wat.contains("i64.extend_i32_u"));
+    assert!(module_wat.contains("f64.convert_i32_u"));
+    assert!(module_wat.contains("i32.trunc_sat_f64_u"));
+
+    let result = run_module(&module_wat, "widen", &[Value::I32(-1)])?;
+    assert_eq!(result[0], Value::I64(u32::MAX as i64));
+    let result = run_module(&module_wat, "to_float", &[Value::I32(-1)])?;
+    assert_eq!(result[0], Value::F64(u32::MAX as f64));
+    // 負の値は0に飽和する
+    let result = run_module(&module_wat, "from_float", &[Value::F64(-3.0)])?;
+    assert_eq!(result[0], Value::I32(0));
+    Ok(())
+}
+
+#[test]
+fn unsigned_test02() {
+    // 符号なし整数に負の値は使えない
+    let code = "
+    pub_fn f() {
+        let a: u32 = -1;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::LiteralOutOfRange { .. })
+    ));
+    let code = "
+    pub_fn f(a: u64) -> u64 {
+        return -a;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::InvalidOperandType { .. })
+    ));
+    // 符号付き整数との演算
+    let code = "
+    pub_fn f(a: u32, b: i32) -> i32 {
+        return a + b;
+    }
+    ";
+    assert!(matches!(
+        compile(code),
+        Err(GenerateError::TypeMismatch { .. })
+    ));
+}